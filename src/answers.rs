@@ -0,0 +1,171 @@
+//! Known answers for puzzle inputs, kept in answers.txt with one line per day, part and
+//! input file:
+//!
+//! ```text
+//! DAY PART INPUT ANSWER [BUDGET]
+//! ```
+//!
+//! Newlines in an answer are written `\n`. The optional budget, like `250ms` or `2s`, is
+//! how long a release build may take before it counts as a timing regression.
+
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::error::{Error, Result};
+use crate::input;
+use crate::registry;
+
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+const FORMAT: &str = "DAY PART INPUT ANSWER [BUDGET]";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expected {
+    pub day: u32,
+    pub part: u32,
+    // file name, looked up with input::named
+    pub input: String,
+    pub answer: String,
+    pub budget: Option<Duration>
+}
+
+#[derive(Debug)]
+pub enum Outcome {
+    Ok,
+    Wrong(String),
+    // right answer, but over budget
+    Slow(Duration),
+    Failed(Error),
+    // the input file isn't here, answers for other people's inputs are expected
+    Skipped
+}
+
+fn parse_budget(text: &str) -> Option<Duration> {
+    if let Some(ms) = text.strip_suffix("ms") {
+        ms.parse::<u64>().ok().map(Duration::from_millis)
+    } else {
+        text.strip_suffix('s')?.parse::<u64>().ok().map(Duration::from_secs)
+    }
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("\\n", "\n")
+}
+
+// path is only used in errors, blank lines and # comments are skipped
+pub fn parse(path: &str, text: &str) -> Result<Vec<Expected>> {
+    let mut answers = Vec::new();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let err = || Error::format(path, n + 1, line, FORMAT);
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 4 || fields.len() > 5 {
+            return Err(err());
+        }
+        let day = fields[0].parse::<u32>().map_err(|_| err())?;
+        let part = fields[1].parse::<u32>().map_err(|_| err())?;
+        if !(1..=25).contains(&day) || !(1..=2).contains(&part) {
+            return Err(err());
+        }
+        let budget = match fields.get(4) {
+            Some(budget) => Some(parse_budget(budget).ok_or_else(err)?),
+            None => None
+        };
+        answers.push(Expected { day, part, input: fields[2].to_string(), answer: unescape(fields[3]), budget });
+    }
+    Ok(answers)
+}
+
+pub fn load(path: impl AsRef<Path>) -> Result<Vec<Expected>> {
+    let name = path.as_ref().display().to_string();
+    let text = fs::read_to_string(path.as_ref()).map_err(|e| Error::io(&name, e))?;
+    parse(&name, &text)
+}
+
+// solves the entry again and compares, also returns how long solving took
+pub fn check(expected: &Expected) -> (Outcome, Duration) {
+    let solver = match registry::get(expected.day).and_then(|x| x.part(expected.part)) {
+        Some(solver) => solver,
+        None => {
+            let reason = format!("there is no part {} to check", expected.part);
+            return (Outcome::Failed(Error::solve(expected.day, &reason)), Duration::ZERO);
+        }
+    };
+    let path = match input::named(expected.day, &expected.input) {
+        Ok(path) => path,
+        Err(Error::NotFound { .. }) => return (Outcome::Skipped, Duration::ZERO),
+        Err(e) => return (Outcome::Failed(e), Duration::ZERO)
+    };
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) => return (Outcome::Failed(Error::io(&path.display().to_string(), e)), Duration::ZERO)
+    };
+
+    let start = Instant::now();
+    let answer = solver(&text);
+    let elapsed = start.elapsed();
+    let outcome = match answer {
+        Ok(answer) if answer != expected.answer => Outcome::Wrong(answer),
+        Ok(_) if expected.budget.is_some_and(|budget| elapsed > budget) => Outcome::Slow(elapsed),
+        Ok(_) => Outcome::Ok,
+        Err(e) => Outcome::Failed(e)
+    };
+    (outcome, elapsed)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_parse() {
+        use super::{parse, Expected};
+        use std::time::Duration;
+        let answers = parse("answers.txt", "\
+# day part input answer
+18 1 day18.txt 483840
+
+10 1 day10_a.txt #..#\\n####  2s
+11 2 day11.txt 235,268,13 150ms
+").unwrap();
+        assert_eq!(answers, vec![
+            Expected { day: 18, part: 1, input: String::from("day18.txt"), answer: String::from("483840"), budget: None },
+            Expected { day: 10, part: 1, input: String::from("day10_a.txt"), answer: String::from("#..#\n####"), budget: Some(Duration::from_secs(2)) },
+            Expected { day: 11, part: 2, input: String::from("day11.txt"), answer: String::from("235,268,13"), budget: Some(Duration::from_millis(150)) },
+        ]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        use super::parse;
+        assert_eq!(parse("answers.txt", "18 1 day18.txt\n").unwrap_err().to_string(),
+                   "answers.txt line 1: expected `DAY PART INPUT ANSWER [BUDGET]`, found `18 1 day18.txt`");
+        assert!(parse("answers.txt", "18 3 day18.txt 1\n").is_err());
+        assert!(parse("answers.txt", "26 1 day26.txt 1\n").is_err());
+        assert!(parse("answers.txt", "18 1 day18.txt 1 fast\n").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        use super::{check, Expected, Outcome};
+        let expected = |input: &str, answer: &str| Expected {
+            day: 22, part: 1, input: String::from(input), answer: String::from(answer), budget: None
+        };
+        assert!(matches!(check(&expected("day22_author.txt", "11575")).0, Outcome::Ok));
+        match check(&expected("day22_author.txt", "11576")).0 {
+            Outcome::Wrong(answer) => assert_eq!(answer, "11575"),
+            other => panic!("expected a wrong answer, got {:?}", other)
+        }
+        assert!(matches!(check(&expected("day22_nobody.txt", "1")).0, Outcome::Skipped));
+    }
+
+    #[test]
+    fn test_answers_file() {
+        use super::{load, ANSWERS_FILE};
+        let answers = load(ANSWERS_FILE).unwrap();
+        assert!(answers.iter().any(|x| (x.day, x.part, x.input.as_str(), x.answer.as_str()) == (18, 1, "day18.txt", "483840")));
+        assert!(answers.iter().all(|x| (1..=25).contains(&x.day) && (1..=2).contains(&x.part)));
+    }
+}
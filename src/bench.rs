@@ -0,0 +1,118 @@
+//! Times every implementation of a day's parts on stable Rust. Besides the trait's
+//! part1 and part2 a day can offer named variants; each is run the same number of
+//! times and has to give the same answer as the default.
+
+use std::time::{Duration, Instant};
+
+use crate::error::Result;
+use crate::solution::Solution;
+
+pub struct Variant<I> {
+    pub part: u32,
+    pub name: &'static str,
+    pub run: fn(&I) -> Result<String>
+}
+
+impl<I> Variant<I> {
+    pub fn new(part: u32, name: &'static str, run: fn(&I) -> Result<String>) -> Variant<I> {
+        Variant { part, name, run }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub mean: Duration,
+    pub min: Duration,
+    pub stddev: Duration
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        if samples.is_empty() {
+            return Stats::default();
+        }
+        let secs: Vec<f64> = samples.iter().map(|x| x.as_secs_f64()).collect();
+        let mean = secs.iter().sum::<f64>() / secs.len() as f64;
+        let variance = secs.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / secs.len() as f64;
+        Stats {
+            runs: samples.len(),
+            mean: Duration::from_secs_f64(mean),
+            min: *samples.iter().min().unwrap(),
+            stddev: Duration::from_secs_f64(variance.sqrt())
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Timing {
+    pub part: u32,
+    pub name: &'static str,
+    pub answer: String,
+    // false when the answer differs from the default for the part
+    pub agrees: bool,
+    pub stats: Stats
+}
+
+// one untimed run for the answer, then runs timed ones
+fn measure<F: FnMut() -> Result<String>>(runs: usize, mut f: F) -> Result<(String, Stats)> {
+    let answer = f()?;
+    let mut samples = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let start = Instant::now();
+        f()?;
+        samples.push(start.elapsed());
+    }
+    Ok((answer, Stats::from_samples(&samples)))
+}
+
+pub fn run<S: Solution>(input: &str, runs: usize) -> Result<Vec<Timing>> {
+    let input = S::parse(input)?;
+    let mut variants = vec![Variant::new(1, "default", |x: &S::Input| Ok(S::part1(x)?.to_string()))];
+    if S::PART2 {
+        variants.push(Variant::new(2, "default", |x: &S::Input| Ok(S::part2(x)?.to_string())));
+    }
+    variants.extend(S::variants());
+
+    let mut timings: Vec<Timing> = Vec::new();
+    for variant in &variants {
+        let (answer, stats) = measure(runs, || (variant.run)(&input))?;
+        let agrees = match timings.iter().find(|x| x.part == variant.part) {
+            Some(default) => default.answer == answer,
+            None => true
+        };
+        timings.push(Timing { part: variant.part, name: variant.name, answer, agrees, stats });
+    }
+    timings.sort_by_key(|x| x.part);
+    Ok(timings)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_stats() {
+        use super::Stats;
+        use std::time::Duration;
+        let samples = [2, 4, 4, 4, 5, 5, 7, 9].map(Duration::from_millis);
+        let stats = Stats::from_samples(&samples);
+        assert_eq!(stats.runs, 8);
+        assert_eq!(stats.mean, Duration::from_millis(5));
+        assert_eq!(stats.min, Duration::from_millis(2));
+        assert_eq!(stats.stddev, Duration::from_millis(2));
+        assert_eq!(Stats::from_samples(&[]), Stats::default());
+    }
+
+    #[test]
+    fn test_variants_agree() {
+        use super::run;
+        use crate::day05::Day05;
+        let timings = run::<Day05>("dabAcCaCBAcCcaDA", 2).unwrap();
+        let names: Vec<(u32, &str)> = timings.iter().map(|x| (x.part, x.name)).collect();
+        assert_eq!(names, vec![(1, "default"), (1, "strip2"), (2, "default"), (2, "strip"), (2, "rayon")]);
+        for timing in &timings {
+            assert!(timing.agrees, "{} part {} gave {}", timing.name, timing.part, timing.answer);
+            assert_eq!(timing.stats.runs, 2);
+        }
+        assert_eq!(timings[4].answer, "4");
+    }
+}
@@ -0,0 +1,217 @@
+//! Finds where a deterministic simulation starts repeating, so a state or a value
+//! billions of steps out can be worked out from the first lap of the cycle.
+//!
+//! States are compared through a key. Keying on the whole state finds exact repeats;
+//! keying on a normalised form, like plants with their position taken off, finds a
+//! shape that repeats while it moves, and `extrapolate` carries the drift forward.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Method {
+    // constant memory, finds the length first then the start
+    Brent,
+    // constant memory, tortoise and hare
+    Floyd,
+    // remembers every key, finds both in one pass
+    Hashed
+}
+
+// step start is the first state in the cycle and step start + length is the same as it
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize
+}
+
+impl Cycle {
+    // the step before the first repeat that n lands on
+    pub fn equivalent(&self, n: usize) -> usize {
+        if n < self.start { n } else { self.start + (n - self.start) % self.length }
+    }
+
+    // how many whole laps there are between equivalent(n) and n
+    pub fn laps(&self, n: usize) -> usize {
+        if n < self.start { 0 } else { (n - self.start) / self.length }
+    }
+
+    // the state after n steps, only stepping to equivalent(n)
+    pub fn state_at<S: Clone, F: FnMut(&S) -> S>(&self, initial: &S, mut step: F, n: usize) -> S {
+        let mut state = initial.clone();
+        for _ in 0..self.equivalent(n) {
+            state = step(&state);
+        }
+        state
+    }
+
+    // values[i] is a metric after step i, and needs at least start + 2 * length of them.
+    // The metric is assumed to change by the same amount every lap at each point in the
+    // lap, which is zero when the state repeats exactly. A shape that moves along can
+    // still change by a different amount at different points, when it has more plants
+    // on some steps than others.
+    pub fn extrapolate(&self, values: &[i64], n: usize) -> i64 {
+        let equivalent = self.equivalent(n);
+        let drift = values[equivalent + self.length] - values[equivalent];
+        values[equivalent] + drift * self.laps(n) as i64
+    }
+}
+
+// None when no cycle shows up in the first limit steps
+pub fn find<S, K, F, G>(method: Method, initial: &S, step: F, key: G, limit: usize) -> Option<Cycle>
+    where S: Clone, K: Eq + Hash, F: FnMut(&S) -> S, G: Fn(&S) -> K {
+    match method {
+        Method::Brent => brent(initial, step, key, limit),
+        Method::Floyd => floyd(initial, step, key, limit),
+        Method::Hashed => hashed(initial, step, key, limit)
+    }
+}
+
+pub fn brent<S, K, F, G>(initial: &S, mut step: F, key: G, limit: usize) -> Option<Cycle>
+    where S: Clone, K: PartialEq, F: FnMut(&S) -> S, G: Fn(&S) -> K {
+    // the hare searches successive powers of two for the length
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = key(initial);
+    let mut hare = step(initial);
+    let mut steps = 1;
+    while key(&hare) != tortoise {
+        if steps >= limit {
+            return None;
+        }
+        if power == length {
+            tortoise = key(&hare);
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+        steps += 1;
+    }
+
+    // then a pair length apart walks in from the start until they meet
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while key(&tortoise) != key(&hare) {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Some(Cycle { start, length })
+}
+
+pub fn floyd<S, K, F, G>(initial: &S, mut step: F, key: G, limit: usize) -> Option<Cycle>
+    where S: Clone, K: PartialEq, F: FnMut(&S) -> S, G: Fn(&S) -> K {
+    let mut tortoise = step(initial);
+    let mut hare = step(&tortoise);
+    let mut steps = 1;
+    while key(&tortoise) != key(&hare) {
+        if steps >= limit {
+            return None;
+        }
+        tortoise = step(&tortoise);
+        let next = step(&hare);
+        hare = step(&next);
+        steps += 1;
+    }
+
+    let mut start = 0;
+    let mut tortoise = initial.clone();
+    while key(&tortoise) != key(&hare) {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = step(&tortoise);
+    while key(&tortoise) != key(&hare) {
+        hare = step(&hare);
+        length += 1;
+    }
+    Some(Cycle { start, length })
+}
+
+pub fn hashed<S, K, F, G>(initial: &S, mut step: F, key: G, limit: usize) -> Option<Cycle>
+    where S: Clone, K: Eq + Hash, F: FnMut(&S) -> S, G: Fn(&S) -> K {
+    let mut seen = HashMap::new();
+    let mut state = initial.clone();
+    for n in 0..=limit {
+        if let Some(start) = seen.insert(key(&state), n) {
+            return Some(Cycle { start, length: n - start });
+        }
+        state = step(&state);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    // x -> x * x + 1 mod 255 from 3 goes 3 10 101 2 5 26 167 95 101, so the cycle
+    // starts at step 2 and is 6 long
+    fn step(x: &u32) -> u32 {
+        (x * x + 1) % 255
+    }
+
+    #[test]
+    fn test_methods_agree() {
+        use super::{find, Cycle, Method};
+        for method in [Method::Brent, Method::Floyd, Method::Hashed] {
+            assert_eq!(find(method, &3, step, |x| *x, 100), Some(Cycle { start: 2, length: 6 }), "{:?}", method);
+        }
+        // a cycle from the very first state
+        for method in [Method::Brent, Method::Floyd, Method::Hashed] {
+            assert_eq!(find(method, &0, |x: &u32| (x + 1) % 4, |x| *x, 100), Some(Cycle { start: 0, length: 4 }), "{:?}", method);
+        }
+    }
+
+    #[test]
+    fn test_limit() {
+        use super::{find, Method};
+        for method in [Method::Brent, Method::Floyd, Method::Hashed] {
+            assert_eq!(find(method, &0u64, |x| x + 1, |x| *x, 1000), None, "{:?}", method);
+        }
+    }
+
+    #[test]
+    fn test_state_at() {
+        use super::Cycle;
+        let cycle = Cycle { start: 2, length: 6 };
+        assert_eq!(cycle.equivalent(1), 1);
+        assert_eq!(cycle.equivalent(8), 2);
+        assert_eq!(cycle.laps(1_000_000_001), 166_666_666);
+        let mut brute = 3;
+        for _ in 0..1000 {
+            brute = step(&brute);
+        }
+        assert_eq!(cycle.state_at(&3, step, 1000), brute);
+    }
+
+    #[test]
+    fn test_extrapolate_shifted() {
+        use super::{hashed, Cycle};
+        // a glider of width 2 moving 3 to the right each step after a 2 step run up
+        let position = |n: &i64| if *n < 2 { 0 } else { 3 * (n - 2) };
+        let shape = |n: &i64| if *n < 2 { *n } else { 2 };
+        let cycle = hashed(&0i64, |n| n + 1, shape, 100).unwrap();
+        assert_eq!(cycle, Cycle { start: 2, length: 1 });
+        let values: Vec<i64> = (0..=3).map(|n| position(&n)).collect();
+        assert_eq!(cycle.extrapolate(&values, 50_000_000_000), position(&50_000_000_000));
+    }
+
+    #[test]
+    fn test_extrapolate_phases() {
+        use super::Cycle;
+        // a shape moving 2 every lap of 2 steps whose metric goes up by 8 on even steps
+        // and 10 on odd ones, like a row of 4 plants then 5
+        let metric = |n: usize| if n.is_multiple_of(2) { 4 * n as i64 } else { 5 * n as i64 };
+        let cycle = Cycle { start: 0, length: 2 };
+        let values: Vec<i64> = (0..4).map(metric).collect();
+        assert_eq!(cycle.extrapolate(&values, 2000), metric(2000));
+        assert_eq!(cycle.extrapolate(&values, 2001), metric(2001));
+    }
+}
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use log::debug;
+
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u32 = 1;
+    type Input = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<i32>> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<i32>) -> Result<i32> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Vec<i32>) -> Result<i32> {
+        Ok(part2(input))
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<i32>> {
+    let mut vec = Vec::new();
+    for (n, line) in input.lines().enumerate() {
+        match line.parse::<i32>() {
+            Ok(change) => vec.push(change),
+            Err(_) => return Err(Error::parse(Day01::DAY, n + 1, line, "+N or -N"))
+        }
+    }
+    if vec.is_empty() {
+        return Err(Error::parse(Day01::DAY, 1, "", "+N or -N"));
+    }
+    Ok(vec)
+}
+
+// https://adventofcode.com/2018/day/1#part1
+pub fn part1(changes: &Vec<i32>) -> i32 {
+    let mut sum = 0;
+    for change in changes {
+        sum += change;
+    }
+    sum
+}
+
+// https://adventofcode.com/2018/day/1#part2
+pub fn part2(vec: &Vec<i32>) -> i32 {
+    let count = vec.len();
+    let mut index : usize = 0;
+    let mut map = HashMap::new();
+    let mut frequency = 0;
+    let mut iters = 0;
+    loop {
+        frequency += vec[index];
+        if map.contains_key(&frequency) {
+            break;
+        } else {
+            map.insert(frequency, true);
+            iters += 1;
+        }
+        index = (index + 1) % count;
+    }
+
+    debug!("count: {}", count);
+    debug!("iters: {}", iters);
+    frequency
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_part1_ex() {
+        use super::{parse, part1};
+        assert_eq!(part1(&parse("+1\n-2\n+3\n+1\n").unwrap()), 3);
+    }
+
+    #[test]
+    fn test_part2_ex() {
+        use super::{parse, part2};
+        assert_eq!(part2(&parse("+1\n-2\n+3\n+1\n").unwrap()), 2);
+    }
+}
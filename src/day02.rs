@@ -0,0 +1,113 @@
+use log::debug;
+
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u32 = 2;
+    type Input = Vec<String>;
+    type Answer1 = i32;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Vec<String>> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(input: &Vec<String>) -> Result<i32> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Vec<String>) -> Result<String> {
+        part2(input).ok_or_else(|| Error::solve(Day02::DAY, "no two ids differ by exactly one character"))
+    }
+}
+
+// https://adventofcode.com/2018/day/2#part1
+pub fn part1(ids: &Vec<String>) -> i32 {
+    let mut two_count = 0;
+    let mut three_count = 0;
+    for line in ids {
+        let mut array: [i32; 256] = [0; 256];
+        for c in line.chars() {
+            let index = c as usize;
+            array[index] += 1;
+        }
+        let mut a = 0;
+        let mut b = 0;
+        for x in array.iter() {
+            let cmp = *x;
+            if cmp == 2 {
+                a = 1;
+            } else if cmp == 3 {
+                b = 1;
+            }
+        }
+        two_count += a;
+        three_count += b;
+    }
+    debug!("two_count: {}", two_count);
+    debug!("three_count: {}", three_count);
+    two_count * three_count
+}
+
+// https://adventofcode.com/2018/day/2#part2
+fn compare_ids(a:&String, b:&String) -> String {
+    let mut found = false;
+    let mut diff_index = 0;
+    let len = a.len();
+    assert_eq!(len, b.len());
+    for i in 0..len {
+        let c = a.chars().nth(i).unwrap();
+        let c2 = b.chars().nth(i).unwrap();
+        if c != c2 {
+            if !found {
+                diff_index = i;
+                found = true;
+            } else {
+                return String::from("");
+            }
+        }
+    }
+    if found {
+        let mut ret = a.clone();
+        ret.remove(diff_index);
+        return ret;
+    }
+    String::from("")
+}
+
+pub fn part2(ids: &Vec<String>) -> Option<String> {
+    let mut id_vec = ids.clone();
+    let mut results = Vec::new();
+    while let Some(a) = id_vec.pop() {
+        for b in &id_vec {
+            let result = compare_ids(&a, b);
+            if !result.is_empty() {
+                results.push(result);
+            }
+        }
+    }
+    if results.len() != 1 {
+        return None;
+    }
+    results.pop()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_part1_ex() {
+        use crate::solution::Solution;
+        use super::{Day02, part1};
+        assert_eq!(part1(&Day02::parse("abcdef\nbababc\nabbcde\nabcccd\naabcdd\nabcdee\nababab\n").unwrap()), 12);
+    }
+
+    #[test]
+    fn test_part2_ex() {
+        use crate::solution::Solution;
+        use super::{Day02, part2};
+        assert_eq!(part2(&Day02::parse("abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz\n").unwrap()).unwrap(), "fgij");
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+
+use crate::error::{Error, Result};
+use crate::format::Format;
+use crate::generate::{Knobs, Rng};
+use crate::solution::Solution;
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u32 = 3;
+    type Input = Vec<Claim>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<Claim>> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<Claim>) -> Result<i32> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Vec<Claim>) -> Result<i32> {
+        part2(input).ok_or_else(|| Error::solve(Day03::DAY, "expected exactly one claim with no overlap"))
+    }
+
+    const GENERATED: bool = true;
+
+    fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+        generate(knobs, rng)
+    }
+}
+
+pub struct Claim {
+    id: i32,
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32
+}
+
+fn parse(input: &str) -> Result<Vec<Claim>> {
+    let format = Format::new("#{id} @ {x},{y}: {w}x{h}");
+    let mut claims = Vec::new();
+    for (n, line) in input.lines().enumerate() {
+        let (id, x, y, w, h) = format.parse(Day03::DAY, n + 1, line)?;
+        claims.push(Claim { id, x, y, w, h });
+    }
+    Ok(claims)
+}
+
+// knobs.count claims on a square of fabric knobs.extent on a side, each up to a tenth of
+// the fabric across
+pub fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+    let side = knobs.extent.max(1) as i64;
+    let mut result = String::new();
+    for id in 1..=knobs.count {
+        let w = rng.range(1, (side / 10).max(1));
+        let h = rng.range(1, (side / 10).max(1));
+        let x = rng.range(0, side - w);
+        let y = rng.range(0, side - h);
+        result.push_str(&format!("#{} @ {},{}: {}x{}\n", id, x, y, w, h));
+    }
+    result
+}
+
+fn fill(claims: &Vec<Claim>) -> HashMap<i32, HashMap<i32, i32>> {
+    let mut cols = HashMap::new();
+    for claim in claims {
+        let xx = claim.x;
+        let yy = claim.y;
+        let ww = claim.w;
+        let hh = claim.h;
+        for i in xx..(xx + ww) {
+            if !cols.contains_key(&i) {
+                cols.insert(i, HashMap::new());
+            }
+            let col = cols.get_mut(&i).unwrap();
+            for j in yy..(yy + hh) {
+                if col.contains_key(&j) {
+                    *col.get_mut(&j).unwrap() += 1;
+                } else {
+                    col.insert(j, 1);
+                }
+            }
+        }
+    }
+    cols
+}
+
+pub fn part1(claims: &Vec<Claim>) -> i32 {
+    let cols = fill(claims);
+
+    let mut overlaps = 0;
+    for (_k, v) in cols {
+        for (_kk, vv) in v {
+            if vv >= 2 {
+                overlaps += 1;
+            }
+        }
+    }
+    overlaps
+}
+
+pub fn part2(claims: &Vec<Claim>) -> Option<i32> {
+    let cols = fill(claims);
+
+    let mut found = Vec::new();
+    for claim in claims {
+        let xx = claim.x;
+        let yy = claim.y;
+        let ww = claim.w;
+        let hh = claim.h;
+
+        let mut multi = false;
+        for i in xx..(xx + ww) {
+            let col = cols.get(&i).unwrap();
+            for j in yy..(yy + hh) {
+                if col.contains_key(&j)
+                    && *col.get(&j).unwrap() > 1 {
+                        multi = true;
+                        break;
+                    }
+            }
+        }
+
+        if !multi {
+            found.push(claim.id);
+        }
+    }
+    if found.len() != 1 {
+        return None;
+    }
+    found.pop()
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = "\
+#1 @ 1,3: 4x4
+#2 @ 3,1: 4x4
+#3 @ 5,5: 2x2
+";
+
+    #[test]
+    fn test_part1_ex() {
+        use super::{parse, part1};
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 4);
+    }
+
+    #[test]
+    fn test_part2_ex() {
+        use super::{parse, part2};
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), Some(3));
+    }
+}
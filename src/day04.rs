@@ -0,0 +1,250 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use log::trace;
+use std::collections::HashMap;
+
+use crate::error::Result;
+use crate::format::{first_of, Fields, Format, Mismatch};
+use crate::generate::{Knobs, Rng};
+use crate::solution::Solution;
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u32 = 4;
+    type Input = Vec<Event>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<Event>> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<Event>) -> Result<i32> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Vec<Event>) -> Result<i32> {
+        Ok(part2(input))
+    }
+
+    const GENERATED: bool = true;
+
+    fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+        generate(knobs, rng)
+    }
+}
+
+pub struct Event {
+    dt: NaiveDateTime,
+    id: i32,
+    sleep: bool
+}
+
+impl Event {
+    pub fn get_id(&self) -> Option<i32> {
+        if self.id >= 0 {
+            Some(self.id)
+        } else {
+            None
+        }
+    }
+
+    pub fn sleep(&self) -> bool {
+        self.sleep
+    }
+
+    pub fn mins(&self) -> u32 {
+        use chrono::Timelike;
+        self.dt.minute()
+    }
+}
+
+fn get_date_time(fields: &Fields) -> std::result::Result<NaiveDateTime, Mismatch> {
+    NaiveDate::from_ymd_opt(fields.get("year")?, fields.get("month")?, fields.get("day")?)
+        .and_then(|date| date.and_hms_opt(fields.get("hour").ok()?, fields.get("minute").ok()?, 0))
+        .ok_or_else(|| Mismatch { column: fields.column("year"), expected: String::from("a valid date and time") })
+}
+
+fn parse(input: &str) -> Result<Vec<Event>> {
+    let stamp = Format::new("[{year}-{month}-{day} {hour}:{minute}] {event}");
+    let begin = Format::new("Guard #{id} begins shift");
+    let wake = Format::new("wakes up");
+    let sleep = Format::new("falls asleep");
+
+    let mut event_vec = Vec::new();
+    for (n, line) in input.lines().enumerate() {
+        let event = || {
+            let fields = stamp.fields(line)?;
+            let dt = get_date_time(&fields)?;
+            let column = fields.column("event");
+            match first_of(&[&begin, &wake, &sleep], fields.str("event")).map_err(|e| e.within(column))? {
+                (0, event) => Ok(Event { dt, id: event.get("id").map_err(|e| e.within(column))?, sleep: false }),
+                (which, _) => Ok(Event { dt, id: -1, sleep: which == 2 })
+            }
+        };
+        event_vec.push(event().map_err(|e: Mismatch| e.error(Day04::DAY, n + 1, line))?);
+    }
+
+    event_vec.sort_by_key(|x| x.dt);
+    Ok(event_vec)
+}
+
+// knobs.count shifts on consecutive days shared between knobs.extent guards, each with up
+// to three naps. The lines come out shuffled like the real log.
+pub fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+    let guards: Vec<i64> = (0..knobs.extent.max(1)).map(|_| rng.range(1, 3499)).collect();
+    let first = NaiveDate::from_ymd_opt(1518, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    let mut lines = Vec::new();
+    for shift in 0..knobs.count {
+        let midnight = first + chrono::Duration::days(shift as i64 + 1);
+        // guards turn up a little before or just after midnight, and nap after that
+        let start = midnight + chrono::Duration::minutes(rng.range(-15, 2));
+        lines.push(format!("{} Guard #{} begins shift", start.format("[%Y-%m-%d %H:%M]"), rng.choose(&guards)));
+        let mut minutes: Vec<i64> = (3..60).collect();
+        rng.shuffle(&mut minutes);
+        let mut naps = minutes[..2 * rng.below(4)].to_vec();
+        naps.sort();
+        for (i, minute) in naps.iter().enumerate() {
+            let time = midnight + chrono::Duration::minutes(*minute);
+            let event = if i % 2 == 0 { "falls asleep" } else { "wakes up" };
+            lines.push(format!("{} {}", time.format("[%Y-%m-%d %H:%M]"), event));
+        }
+    }
+    rng.shuffle(&mut lines);
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+#[allow(unused_parens)]
+fn solution(event_vec: &Vec<Event>, part1: bool) -> i32 {
+    let mut map = HashMap::new();
+    let num_events = event_vec.len();
+    let mut x = 0;
+    while x < num_events {
+        let ev = &event_vec[x];
+        x += 1;
+        let id = ev.get_id();
+        if id.is_some() {
+            let record = map.entry(id.unwrap()).or_insert([0; 61]);
+            let mut start: usize = 0;
+            let mut end;
+            let mut iters = 0;
+            for y in x..num_events {
+                let ev2 = &event_vec[y];
+                if ev2.get_id().is_some() {
+                    break;
+                }
+
+                iters += 1;
+                if ev2.sleep() {
+                    start = ev2.mins() as usize;
+                } else {
+                    end = ev2.mins() as usize;
+                    assert!(start < end);
+                    for i in start..end {
+                        record[i] += 1;
+                        record[60] += 1;
+                    }
+                }
+            }
+            x += iters;
+        }
+    }
+
+    let mut best_id = -1;
+    let mut best_minute = 0;
+    if (part1) {
+        // part1
+        let mut best_total = 0;
+        for (k, v) in map {
+            let total = v[60];
+            if total > best_total {
+                best_id = k;
+                best_total = total;
+                let mut min = -1;
+                for i in 0..60 {
+                    let tmp = v[i];
+                    if tmp > min {
+                        min = tmp;
+                        best_minute = i;
+                    }
+                }
+                trace!("guard {} minutes: {:?}", k, &v[0..60]);
+            }
+        }
+    } else {
+        // part2
+        let mut best_count = 0;
+        for (k, v) in map {
+            let mut count = -1;
+            let mut min = 0;
+            for i in 0..60 {
+                let tmp= v[i];
+                if tmp > count {
+                    count = tmp;
+                    min = i;
+                }
+            }
+            if count > best_count {
+                best_count = count;
+                best_id = k;
+                best_minute = min;
+                trace!("guard {} minutes: {:?}", k, &v[0..60]);
+            }
+        }
+    }
+
+    best_id * best_minute as i32
+}
+
+// part1 and part2 in same sln
+pub fn part1(events: &Vec<Event>) -> i32 {
+    solution(events, true)
+}
+
+pub fn part2(events: &Vec<Event>) -> i32 {
+    solution(events, false)
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = "\
+[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-05 00:55] wakes up
+";
+
+    #[test]
+    fn test_parse_error() {
+        use super::parse;
+        let err = parse("[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] falls awake\n").err().unwrap();
+        assert_eq!(err.to_string(), "day 4 line 2 column 27: expected `sleep`, found `wake`");
+        let err = parse("[1518-11-01 00:00] Guard #x begins shift\n").err().unwrap();
+        assert_eq!(err.to_string(), "day 4 line 1 column 27: expected `i32 for {id}`, found `x begins shift`");
+    }
+
+    #[test]
+    fn test_part1_ex() {
+        use super::{parse, part1};
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 240);
+    }
+
+    #[test]
+    fn test_part2_ex() {
+        use super::{parse, part2};
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 4455);
+    }
+}
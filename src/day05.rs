@@ -0,0 +1,212 @@
+use log::debug;
+use rayon::prelude::*;
+
+use crate::bench::Variant;
+use crate::diagnostics;
+use crate::error::{Error, Result};
+use crate::generate::{Knobs, Rng};
+use crate::oracle::Oracle;
+use crate::solution::Solution;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u32 = 5;
+    type Input = Vec<u8>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<u8>> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<u8>) -> Result<usize> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Vec<u8>) -> Result<usize> {
+        Ok(part2(input, true))
+    }
+
+    fn variants() -> Vec<Variant<Vec<u8>>> {
+        vec![
+            Variant::new(1, "strip2", |input| Ok(strip2(input).len().to_string())),
+            Variant::new(2, "strip", |input| Ok(part2(input, false).to_string())),
+            Variant::new(2, "rayon", |input| Ok(part2_rayon(input).to_string())),
+        ]
+    }
+
+    const GENERATED: bool = true;
+
+    fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+        generate(knobs, rng)
+    }
+
+    fn oracles() -> Vec<Oracle<Vec<u8>>> {
+        vec![
+            Oracle::new("strip", |input| Ok(part1(input).to_string()), |input| Ok(react(input).len().to_string())),
+            Oracle::new("strip2", |input| Ok(strip2(input).len().to_string()), |input| Ok(react(input).len().to_string())),
+            Oracle::new("part2", |input| Ok(part2(input, true).to_string()), |input| Ok(part2_naive(input).to_string())),
+        ]
+    }
+}
+
+// v2 fixed
+fn strip(input:&Vec<u8>) -> Vec<u8> {
+    let mut result = Vec::new();
+    let end = input.len();
+    let mut it = 0;
+    while it < end {
+        let i0 = input[it] as i32;
+        if it >= end-1 {
+            result.push(i0 as u8);
+            return result;
+        }
+
+        let i1 = input[it+1] as i32;
+        if (i0 - i1).abs() != 32 {
+            result.push(i0 as u8);
+            it += 1;
+        } else {
+            it += 2;
+        }
+    }
+    result
+}
+
+// added after looking at work answers, stack version
+fn strip2(input:&Vec<u8>) -> Vec<u8> {
+    let mut result = Vec::new();
+    let end = input.len();
+    for it in 0..end {
+        let len = result.len();
+        if len == 0 {
+            result.push(input[it]);
+        } else {
+            let u0 = input[it];
+            let i0 = u0 as i32;
+            let i1 = result[len-1] as i32;
+            if (i0 - i1).abs() == 32 {
+                result.pop();
+            } else {
+                result.push(u0);
+            }
+        }
+    }
+    result
+}
+
+fn parse(input: &str) -> Result<Vec<u8>> {
+    let line = input.trim();
+    if !line.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(Error::parse_input(Day05::DAY, input, "a single line of letters"));
+    }
+    Ok(line.as_bytes().to_vec())
+}
+
+// a polymer of knobs.count units using the first knobs.extent letters. Few letters give
+// lots of reactions.
+pub fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+    let letters = knobs.extent.clamp(1, 26) as u8;
+    let mut result: String = (0..knobs.count.max(1)).map(|_| {
+        let letter = b'a' + rng.below(letters as usize) as u8;
+        (if rng.one_in(2) { letter.to_ascii_uppercase() } else { letter }) as char
+    }).collect();
+    result.push('\n');
+    result
+}
+
+// the reference, removes the first reacting pair and starts over until there is none
+fn react(input: &Vec<u8>) -> Vec<u8> {
+    let mut result = input.clone();
+    while let Some(i) = result.windows(2).position(|x| x[0] != x[1] && x[0].eq_ignore_ascii_case(&x[1])) {
+        result.drain(i..i + 2);
+    }
+    result
+}
+
+fn part2_naive(input: &Vec<u8>) -> usize {
+    (b'a'..=b'z').map(|c| {
+        let removed = input.iter().copied().filter(|x| !x.eq_ignore_ascii_case(&c)).collect();
+        react(&removed).len()
+    }).min().unwrap()
+}
+
+pub fn part1(original_input: &Vec<u8>) -> usize {
+    let mut result = original_input.clone();
+    let mut length= result.len();
+    let mut passes = 0;
+    loop {
+        result = strip(&result);
+        passes += 1;
+        let new_length = result.len();
+        if new_length == length {
+            break;
+        }
+        length = new_length;
+    }
+    debug!("passes: {}", passes);
+    diagnostics::record("passes", passes);
+    debug!("result2: {}", strip2(original_input).len());
+    length
+}
+
+pub fn part2(original_input: &Vec<u8>, use_strip2: bool) -> usize {
+    let mut best_len = usize::MAX;
+    for i in 0..26 {
+        let mut result = original_input.clone();
+        let c0 = i as u8 + b'A';
+        let c1 = i as u8 + b'A' + 32u8;
+        result.retain(|&x| x != c0 && x != c1);
+
+        if use_strip2 {
+            let length = strip2(&result).len();
+            if length < best_len {
+                best_len = length;
+            }
+        } else {
+            let mut length = result.len();
+            loop {
+                result = strip(&result);
+                let new_length = result.len();
+                if new_length == length {
+                    break;
+                }
+                length = new_length;
+            }
+            if length < best_len {
+                best_len = length;
+            }
+        }
+    }
+    best_len
+}
+
+pub fn part2_rayon(original_input: &Vec<u8>) -> i32 {
+    let result = (0..26).into_par_iter().map(|i| {
+        let mut result = original_input.clone();
+        let c0 = i as u8 + b'A';
+        let c1 = i as u8 + b'A' + 32u8;
+        result.retain(|&x| x != c0 && x != c1);
+        strip2(&result).len() as i32
+    });
+    result.min().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_strip() {
+        use super::strip;
+        assert!(String::from_utf8(strip(&"aA".to_string().into_bytes())).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_oracles() {
+        use crate::generate::Knobs;
+        use crate::oracle::{disagreements, run};
+        use super::Day05;
+        let cases = run::<Day05>(&Knobs { count: 200, extent: 3 }, 30).unwrap();
+        assert!(disagreements(&cases).is_empty(), "{:?}", disagreements(&cases));
+    }
+}
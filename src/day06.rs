@@ -0,0 +1,179 @@
+use crate::error::{Error, Result};
+use crate::format::Format;
+use crate::grid::Grid;
+use crate::point::{Bounds, Point, Vector};
+use crate::solution::Solution;
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u32 = 6;
+    type Input = Vec<Point<2>>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<Point<2>>> {
+        parse_coords(input)
+    }
+
+    fn part1(input: &Vec<Point<2>>) -> Result<i32> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Vec<Point<2>>) -> Result<i32> {
+        Ok(part2(input, 10000))
+    }
+}
+
+const INFINITE:i32 = -1;
+const UNVISITED:i32 = -1;
+const SHARED:i32 = -2;
+
+// shifts the points so the grid starts at 0 with a border a cell wide, except on a side
+// already at 0, and returns the grid size
+fn normalize(points: &mut Vec<Point<2>>) -> (usize, usize) {
+    let bounds = Bounds::of(points.iter()).unwrap();
+    let mut min = bounds.min;
+    for axis in 0..2 {
+        if min[axis] > 0 {
+            min[axis] -= 1;
+        }
+    }
+    let max = bounds.max + Vector::new([1, 1]);
+    let shift = min - Point::origin();
+    for point in points.iter_mut() {
+        *point -= shift;
+    }
+    ((max[0] - min[0]) as usize, (max[1] - min[1]) as usize)
+}
+
+fn parse_coords(input:&str) -> Result<Vec<Point<2>>> {
+    let format = Format::new("{x}, {y}");
+    let mut coords = Vec::new();
+    for (n, line) in input.lines().enumerate() {
+        let (x, y) = format.parse(Day06::DAY, n + 1, line)?;
+        coords.push(Point::new([x, y]));
+    }
+    if coords.is_empty() {
+        return Err(Error::parse(Day06::DAY, 1, "", format.pattern()));
+    }
+    Ok(coords)
+}
+
+pub fn part1(input:&Vec<Point<2>>) -> i32 {
+    let mut coords = input.clone();
+
+    let (width, height) = normalize(&mut coords);
+
+    // this is a dumb algorithm, order is higher than needed should do simultaneous flood from the origins
+    let mut grid = Grid::new(width, height, UNVISITED);
+    let dimx = grid.width();
+    let dimy = grid.height();
+    let coords_len = coords.len();
+
+    for (x, y) in grid.positions().collect::<Vec<_>>() {
+        let test_coord = Point::new([x as i64, y as i64]);
+
+        let mut min_dist = i64::MAX;
+        let mut shared = false;
+        let mut coord_index = 0;
+
+        for j in 0..coords_len {
+            let dist = test_coord.manhattan(&coords[j]);
+            if dist < min_dist {
+                min_dist = dist;
+                shared = false;
+                coord_index = j;
+            } else if dist == min_dist {
+                min_dist = dist;
+                shared = true;
+                coord_index = 0;
+            }
+        }
+
+        if shared {
+            grid[(x, y)] = SHARED;
+        } else {
+            grid[(x, y)] = coord_index as i32;
+        }
+    }
+
+    let mut areas = vec![0; coords_len];
+    for ((tcx, tcy), &idx) in grid.iter() {
+        assert_ne!(idx, UNVISITED);
+        if idx >= 0 {
+            if tcx == 0 || tcx == dimx - 1 || tcy == 0 || tcy == dimy - 1 {
+                areas[idx as usize] = INFINITE;
+            } else {
+                areas[idx as usize] += 1;
+            }
+        }
+    }
+
+    areas.retain(|&x| x >= 1);
+    *areas.iter().max().unwrap()
+}
+
+pub fn part2(input:&Vec<Point<2>>, total_dist:i32) -> i32 {
+    let mut coords = input.clone();
+
+    let (width, height) = normalize(&mut coords);
+
+    let mut grid = Grid::new(width, height, 0i64);
+    let coords_len = coords.len();
+
+    for (x, y) in grid.positions().collect::<Vec<_>>() {
+        let test_coord = Point::new([x as i64, y as i64]);
+
+        let mut sum = 0;
+        for j in 0..coords_len {
+            sum += test_coord.manhattan(&coords[j]);
+        }
+
+        grid[(x, y)] = sum;
+    }
+
+    grid.count(|&x| x < total_dist as i64) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = "\
+1, 1
+1, 6
+8, 3
+3, 4
+5, 5
+8, 9
+";
+
+    #[test]
+    fn test_part1_example() {
+        use super::{parse_coords, part1};
+        assert_eq!(part1(&parse_coords(EXAMPLE).unwrap()), 17);
+    }
+
+    #[test]
+    #[ignore = "needs the original author's puzzle input in inputs/ or AOC_INPUT_DIR"]
+    fn test_part1_input() {
+        use crate::solution::load_input;
+        use super::Day06;
+        use super::part1;
+        assert_eq!(part1(&load_input::<Day06>().unwrap()), 5975);
+    }
+
+    #[test]
+    fn test_part2_example() {
+        use super::{parse_coords, part2};
+        assert_eq!(part2(&parse_coords(EXAMPLE).unwrap(), 32), 16);
+    }
+
+    #[test]
+    #[ignore = "needs the original author's puzzle input in inputs/ or AOC_INPUT_DIR"]
+    fn test_part2_input() {
+        use crate::solution::load_input;
+        use super::Day06;
+        use super::part2;
+        assert_eq!(part2(&load_input::<Day06>().unwrap(), 10000), 38670);
+    }
+}
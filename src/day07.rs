@@ -1,9 +1,34 @@
-use std::error::Error;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use crate::error::{Error, Result};
+use crate::format::Format;
+use crate::generate::{Knobs, Rng};
+use crate::solution::Solution;
 
-#[macro_use]
-extern crate scan_fmt;
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u32 = 7;
+    type Input = Vec<(char, char)>;
+    type Answer1 = String;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<(char, char)>> {
+        parse_requirements(input)
+    }
+
+    fn part1(input: &Vec<(char, char)>) -> Result<String> {
+        Ok(part1(input, 1))
+    }
+
+    fn part2(input: &Vec<(char, char)>) -> Result<i32> {
+        Ok(part2(input, 60, 5))
+    }
+
+    const GENERATED: bool = true;
+
+    fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+        generate(knobs, rng)
+    }
+}
 
 struct Step {
     name: char,
@@ -118,41 +143,47 @@ impl Graph {
     }
 }
 
-fn parse_requirements(path:&str) -> Vec<(char, char)> {
-    let file = match File::open(path) {
-        Err(why) => panic!("couldn't open {}: {}", path, Error::description(&why)),
-        Ok(file) => file,
-    };
-
+fn parse_requirements(input:&str) -> Result<Vec<(char, char)>> {
+    let format = Format::new("Step {a} must be finished before step {b} can begin.");
     let mut requirements = Vec::new();
-    let reader = BufReader::new(file);
-    for line in reader.lines() {
-        match line {
-            Ok(line) => {
-                let (a, b) = scan_fmt!(&line, "Step {} must be finished before step {} can begin.", char, char);
-                requirements.push((a.unwrap(), b.unwrap()));
-            }
-            Err(e) => println!("err: {}", e)
+    for (n, line) in input.lines().enumerate() {
+        let (a, b): (char, char) = format.parse(Day07::DAY, n + 1, line)?;
+        if !a.is_ascii_uppercase() || !b.is_ascii_uppercase() {
+            return Err(Error::parse(Day07::DAY, n + 1, line, "steps named A to Z"));
+        }
+        requirements.push((a, b));
+    }
+    Ok(requirements)
+}
+
+// up to knobs.count requirements between the first knobs.extent letters, never more than
+// 26. The steps are put in a random order first and only ever depend on earlier ones, so
+// there are no cycles.
+pub fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+    let mut order: Vec<char> = ('A'..='Z').take(knobs.extent.clamp(2, 26)).collect();
+    rng.shuffle(&mut order);
+    let mut pairs = Vec::new();
+    for i in 0..order.len() {
+        for j in i + 1..order.len() {
+            pairs.push((order[i], order[j]));
         }
     }
-    return requirements;
+    rng.shuffle(&mut pairs);
+    pairs.truncate(knobs.count);
+    pairs.iter().map(|(a, b)| format!("Step {} must be finished before step {} can begin.\n", a, b)).collect()
 }
 
-#[allow(dead_code)]
-fn part1(path:&str, num_workers:i32) -> String {
-    let reqs = parse_requirements(path);
+pub fn part1(reqs:&Vec<(char, char)>, num_workers:i32) -> String {
     let mut graph = Graph {steps: Vec::new(), idle_workers: num_workers, active_workers: Vec::new()};
-    for req in &reqs {
+    for req in reqs {
         graph.add_requirement(req);
     }
     graph.execute()
 }
 
-#[allow(dead_code)]
-fn part2(path:&str, base_duration:i32, num_workers:i32) -> i32 {
-    let reqs = parse_requirements(path);
+pub fn part2(reqs:&Vec<(char, char)>, base_duration:i32, num_workers:i32) -> i32 {
     let mut graph = Graph {steps: Vec::new(), idle_workers: num_workers, active_workers: Vec::new()};
-    for req in &reqs {
+    for req in reqs {
         graph.add_requirement(req);
     }
     for step in graph.steps.iter_mut() {
@@ -163,30 +194,43 @@ fn part2(path:&str, base_duration:i32, num_workers:i32) -> i32 {
 
 #[cfg(test)]
 mod tests {
+    const EXAMPLE: &str = "\
+Step C must be finished before step A can begin.
+Step C must be finished before step F can begin.
+Step A must be finished before step B can begin.
+Step A must be finished before step D can begin.
+Step B must be finished before step E can begin.
+Step D must be finished before step E can begin.
+Step F must be finished before step E can begin.
+";
+
     #[test]
     fn test_part1_example() {
-        use part1;
-        assert_eq!(part1("C:\\Users\\lgascoigne\\IdeaProjects\\advent\\test.txt", 1), "CABDFE");
+        use super::{parse_requirements, part1};
+        assert_eq!(part1(&parse_requirements(EXAMPLE).unwrap(), 1), "CABDFE");
     }
 
     #[test]
+    #[ignore = "needs the original author's puzzle input in inputs/ or AOC_INPUT_DIR"]
     fn test_part1_input() {
-        use part1;
-        assert_eq!(part1("C:\\Users\\lgascoigne\\IdeaProjects\\advent\\input.txt", 1), "GDHOSUXACIMRTPWNYJLEQFVZBK");
+        use crate::solution::load_input;
+        use super::Day07;
+        use super::part1;
+        assert_eq!(part1(&load_input::<Day07>().unwrap(), 1), "GDHOSUXACIMRTPWNYJLEQFVZBK");
     }
 
     #[test]
     fn test_part2_example() {
-        use part2;
-        assert_eq!(part2("C:\\Users\\lgascoigne\\IdeaProjects\\advent\\test.txt", 0, 2), 15);
+        use super::{parse_requirements, part2};
+        assert_eq!(part2(&parse_requirements(EXAMPLE).unwrap(), 0, 2), 15);
     }
 
     #[test]
+    #[ignore = "needs the original author's puzzle input in inputs/ or AOC_INPUT_DIR"]
     fn test_part2_input() {
-        use part2;
-        assert_eq!(part2("C:\\Users\\lgascoigne\\IdeaProjects\\advent\\input.txt", 60, 5), 1024);
+        use crate::solution::load_input;
+        use super::Day07;
+        use super::part2;
+        assert_eq!(part2(&load_input::<Day07>().unwrap(), 60, 5), 1024);
     }
 }
-
-fn main() {
-}
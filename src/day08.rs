@@ -0,0 +1,200 @@
+use crate::error::{Error, Result};
+use crate::generate::{Knobs, Rng};
+use crate::solution::Solution;
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u32 = 8;
+    type Input = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<i32>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Vec<i32>) -> Result<i32> {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<i32>) -> Result<i32> {
+        part2(input)
+    }
+
+    const GENERATED: bool = true;
+
+    fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+        generate(knobs, rng)
+    }
+}
+
+struct NodeData {
+    metadata: Vec<i32>
+}
+
+fn parse_input(input:&str) -> Result<Vec<i32>> {
+    let mut result = Vec::new();
+    for x in input.split_whitespace() {
+        match x.parse::<i32>() {
+            Ok(value) if value >= 0 => result.push(value),
+            _ => return Err(Error::parse_input(Day08::DAY, input, "space separated numbers"))
+        }
+    }
+    Ok(result)
+}
+
+// a tree of knobs.count nodes with metadata entries up to knobs.extent, so entries can
+// point past the last child
+pub fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+    let count = knobs.count.max(1);
+    // each node after the root hangs off a random earlier one
+    let mut children = vec![Vec::new(); count];
+    for node in 1..count {
+        children[rng.below(node)].push(node);
+    }
+    // the header goes out before the children and the metadata after them
+    let mut numbers = Vec::new();
+    let mut todo = vec![Ok(0)];
+    while let Some(next) = todo.pop() {
+        match next {
+            Ok(node) => {
+                let metadata = rng.range(1, 3);
+                numbers.push(children[node].len() as i64);
+                numbers.push(metadata);
+                todo.push(Err(metadata));
+                todo.extend(children[node].iter().rev().map(|child| Ok(*child)));
+            },
+            Err(metadata) => {
+                for _ in 0..metadata {
+                    numbers.push(rng.range(1, knobs.extent.max(1) as i64));
+                }
+            }
+        }
+    }
+    numbers.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" ") + "\n"
+}
+
+// the number at cursor, moving past it, or an error when the input stops short of it
+fn next_number(input: &[i32], cursor: &mut usize) -> Result<i32> {
+    let Some(&value) = input.get(*cursor) else {
+        let text = input.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" ");
+        return Err(Error::parse_input(Day08::DAY, &text, "a header and metadata for every node"));
+    };
+    *cursor += 1;
+    Ok(value)
+}
+
+fn build_tree_recursive(arena: &mut indextree::Arena<NodeData>, input: &Vec<i32>, cursor: &mut usize, parent: Option<indextree::NodeId> ) -> Result<indextree::NodeId> {
+    let child_count = next_number(input, cursor)?;
+    let meta_count = next_number(input, cursor)?;
+
+    let new_id = arena.new_node(NodeData {metadata:Vec::new()});
+    if child_count > 0 {
+        for _x in 0..child_count {
+            build_tree_recursive(arena, input, cursor, Some(new_id))?;
+        }
+    }
+
+    if parent.is_some() {
+        assert!(parent.unwrap().checked_append(new_id, arena).is_ok());
+    }
+
+    let node = arena.get_mut(new_id).unwrap();
+    for _x in 0..meta_count {
+        node.get_mut().metadata.push(next_number(input, cursor)?);
+    }
+    Ok(new_id)
+}
+
+fn get_node_value(arena: &indextree::Arena<NodeData>, node_id: indextree::NodeId, val: &mut i32) {
+    let child_count = node_id.children(arena).count();
+    if child_count == 0 {
+        let node= arena.get(node_id).unwrap();
+        let meta_sum: i32 = node.get().metadata.iter().sum();
+        *val += meta_sum;
+    } else {
+        let node= arena.get(node_id).unwrap();
+        for x in &node.get().metadata {
+            let index = *x as usize;
+            if index > 0 && index <= child_count {
+                let child_node = node_id.children(arena).nth(index - 1_usize);
+                if child_node.is_some() {
+                    get_node_value(arena, child_node.unwrap(), val);
+                }
+            }
+        }
+    }
+}
+
+pub fn part1(input:&Vec<i32>) -> Result<i32> {
+    use indextree::Arena;
+    let arena = &mut Arena::new();
+
+    let mut cursor: usize = 0;
+    build_tree_recursive(arena, input, &mut cursor, None)?;
+    let mut sum: i32 = 0;
+    for node in arena.iter() {
+        let meta_sum: i32 = node.get().metadata.iter().sum();
+        sum += meta_sum;
+    }
+    Ok(sum)
+}
+
+pub fn part2(input:&Vec<i32>) -> Result<i32> {
+    use indextree::Arena;
+    let arena = &mut Arena::new();
+
+    let mut cursor: usize = 0;
+    let root = build_tree_recursive(arena, input, &mut cursor, None)?;
+    let mut val = 0;
+    get_node_value(arena, root, &mut val);
+    Ok(val)
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = "\
+2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2
+";
+
+    #[test]
+    fn test_part1_example() {
+        use super::{parse_input, part1};
+        assert_eq!(part1(&parse_input(EXAMPLE).unwrap()).unwrap(), 138);
+    }
+
+    #[test]
+    #[ignore = "needs the original author's puzzle input in inputs/ or AOC_INPUT_DIR"]
+    fn test_part1_input() {
+        use crate::solution::load_input;
+        use super::Day08;
+        use super::part1;
+        assert_eq!(part1(&load_input::<Day08>().unwrap()).unwrap(), 43996);
+    }
+
+    #[test]
+    fn test_part2_example() {
+        use super::{parse_input, part2};
+        assert_eq!(part2(&parse_input(EXAMPLE).unwrap()).unwrap(), 66);
+    }
+
+    #[test]
+    fn test_truncated() {
+        use super::{parse_input, part1, part2};
+        // a root with two children and three entries that stops after its header, and
+        // no tree at all
+        assert_eq!(part1(&parse_input("2 3").unwrap()).unwrap_err().to_string(), "day 8 line 1: expected `a header and metadata for every node`, found `2 3`");
+        assert_eq!(part2(&parse_input("").unwrap()).unwrap_err().to_string(), "day 8 line 1: expected `a header and metadata for every node`, found ``");
+        assert!(part2(&parse_input("0 2 5").unwrap()).is_err());
+    }
+
+    #[test]
+    #[ignore = "needs the original author's puzzle input in inputs/ or AOC_INPUT_DIR"]
+    fn test_part2_input() {
+        use crate::solution::load_input;
+        use super::Day08;
+        use super::part2;
+        assert_eq!(part2(&load_input::<Day08>().unwrap()).unwrap(), 35189);
+    }
+}
@@ -1,8 +1,38 @@
-#![feature(test)]
-
 use std::collections::VecDeque;
 
-extern crate test;
+use crate::error::{Error, Result};
+use crate::format::Format;
+use crate::solution::Solution;
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u32 = 9;
+    type Input = (usize, u32);
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<(usize, u32)> {
+        parse_input(input)
+    }
+
+    fn part1(input: &(usize, u32)) -> Result<u32> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &(usize, u32)) -> Result<u32> {
+        Ok(part2(input))
+    }
+}
+
+fn parse_input(input: &str) -> Result<(usize, u32)> {
+    let format = Format::new("{players} players; last marble is worth {points} points");
+    let (a, b) = format.parse(Day09::DAY, 1, input.lines().next().unwrap_or(""))?;
+    if a == 0 {
+        return Err(Error::parse_input(Day09::DAY, input, "at least 1 player"));
+    }
+    Ok((a, b))
+}
 
 fn solution(num_players:usize, last_marble:u32) -> u32 {
     let mut circle = VecDeque::with_capacity(last_marble as usize);
@@ -28,58 +58,63 @@ fn solution(num_players:usize, last_marble:u32) -> u32 {
     *scores.iter().max().unwrap()
 }
 
+pub fn part1(&(num_players, last_marble): &(usize, u32)) -> u32 {
+    solution(num_players, last_marble)
+}
+
+pub fn part2(&(num_players, last_marble): &(usize, u32)) -> u32 {
+    solution(num_players, last_marble * 100)
+}
+
 #[cfg(test)]
 mod tests {
     //use test::Bencher;
 
     #[test]
     fn test_part1_ex_0() {
-        use solution;
+        use super::solution;
         assert_eq!(solution(9, 25), 32);
     }
 
     #[test]
     fn test_part1_ex_1() {
-        use solution;
+        use super::solution;
         assert_eq!(solution(10, 1618), 8317);
     }
 
     #[test]
     fn test_part1_ex_2() {
-        use solution;
+        use super::solution;
         assert_eq!(solution(13, 7999), 146373);
     }
 
     #[test]
     fn test_part1_ex_3() {
-        use solution;
+        use super::solution;
         assert_eq!(solution(17, 1104), 2764);
     }
 
     #[test]
     fn test_part1_ex_4() {
-        use solution;
+        use super::solution;
         assert_eq!(solution(21, 6111), 54718);
     }
 
     #[test]
     fn test_part1_ex_5() {
-        use solution;
+        use super::solution;
         assert_eq!(solution(30, 5807), 37305);
     }
 
     #[test]
     fn test_part1_input() {
-        use solution;
+        use super::solution;
         assert_eq!(solution(486, 70833), 373597);
     }
 
     #[test]
     fn test_part2_input() {
-        use solution;
+        use super::solution;
         assert_eq!(solution(486, 70833 * 100), 2954067253);
     }
 }
-
-fn main() {
-}
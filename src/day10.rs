@@ -0,0 +1,216 @@
+use log::{debug, trace};
+
+use crate::error::{Error, Result};
+use crate::format::Format;
+use crate::grid::Grid;
+use crate::point::{Bounds, Point, Vector};
+use crate::recorder::{Palette, Recorder, Settings};
+use crate::solution::Solution;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+    type Input = Vec<Light>;
+    type Answer1 = String;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<Light>> {
+        parse_points(input)
+    }
+
+    fn part1(input: &Vec<Light>) -> Result<String> {
+        part1(input).ok_or_else(|| Error::solve(Day10::DAY, "the points never converge"))
+    }
+
+    fn part2(input: &Vec<Light>) -> Result<u32> {
+        part2(input).ok_or_else(|| Error::solve(Day10::DAY, "the points never converge"))
+    }
+
+    const ANIMATED: bool = true;
+
+    fn animate(input: &Vec<Light>, settings: &Settings) -> Result<usize> {
+        animate(input, settings)
+    }
+}
+
+#[derive(Debug)]
+pub struct Light {
+    pos: Point<2>,
+    vel: Vector<2>
+}
+
+fn parse_points(input:&str) -> Result<Vec<Light>> {
+    let format = Format::new("position=<{x},{y}> velocity=<{dx},{dy}>");
+    let mut result = Vec::new();
+    for (n, line) in input.lines().enumerate() {
+        let (x, y, dx, dy) = format.parse(Day10::DAY, n + 1, line)?;
+        result.push(Light { pos: Point::new([x, y]), vel: Vector::new([dx, dy]) });
+    }
+    if result.is_empty() {
+        return Err(Error::parse(Day10::DAY, 1, "", format.pattern()));
+    }
+    Ok(result)
+}
+
+fn points_at_time(points: &Vec<Light>, time: u32) -> Vec<Point<2>> {
+    points.iter().map(|x| x.pos + x.vel * time as i64).collect()
+}
+
+fn extents_at_time(points: &Vec<Light>, time: u32) -> Bounds<2> {
+    Bounds::of(&points_at_time(points, time)).unwrap()
+}
+
+fn find_start_time_dims(points: &Vec<Light>) -> Option<(usize, u32)> {
+    let mut start = 0;
+    let mut step = 512;
+
+    loop {
+        let result = find_start_time_dims_step(points, start, step);
+        if result.is_some() {
+            let std = result.unwrap();
+            start = std.1.saturating_sub(step);
+            step /= 2;
+            if step == 0 {
+                return result;
+            }
+        } else {
+            break;
+        }
+    }
+    None
+}
+
+fn find_start_time_dims_step(points: &Vec<Light>, start: u32, step: u32) -> Option<(usize, u32)> {
+    let mut time = start;
+    let mut last_dim = usize::MAX;
+    let mut last_time = 0u32;
+
+    for _x in 0..128 {
+        let dims = extents_at_time(points, time);
+        let max = (dims.max - dims.min).chebyshev() as usize;
+        if max < last_dim {
+            last_dim = max;
+            last_time = time;
+        } else {
+            return Some((last_dim, last_time));
+        }
+        time += step;
+    }
+    None
+}
+
+fn render(points: &Vec<Light>, time: u32) -> String {
+    let points_t = points_at_time(points, time);
+    let bounds = Bounds::of(&points_t).unwrap();
+    let size = bounds.size();
+    let mut rows = vec![vec!['.'; size[0] as usize]; size[1] as usize];
+    for p in &points_t {
+        let v = *p - bounds.min;
+        rows[v[1] as usize][v[0] as usize] = '#';
+    }
+    rows.iter().map(|row| row.iter().collect::<String>()).collect::<Vec<String>>().join("\n")
+}
+
+// the message has to be read off the output
+pub fn part1(points: &Vec<Light>) -> Option<String> {
+    let time = find_start_time_dims(points)?.1;
+    Some(render(points, time))
+}
+
+pub fn part2(points: &Vec<Light>) -> Option<u32> {
+    Some(find_start_time_dims(points)?.1)
+}
+
+// frames leading up to the message, one per second
+const ANIMATION_SECONDS: u32 = 20;
+
+fn frame(points: &Vec<Light>, time: u32, origin: Point<2>, width: usize, height: usize) -> Grid<bool> {
+    let points_t = points_at_time(points, time);
+    debug!("dims: {:?}", Bounds::of(&points_t).unwrap().size());
+    let mut grid = Grid::new(width, height, false);
+    for p in points_t {
+        trace!("pos_x = {} pos_y = {}", p[0], p[1]);
+        let v = p - origin;
+        if let Some(cell) = grid.get_mut(v[0] as isize, v[1] as isize) {
+            *cell = true;
+        }
+    }
+    grid
+}
+
+// the points converging, framed around where the message appears
+pub fn animate(points: &Vec<Light>, settings: &Settings) -> Result<usize> {
+    let end = part2(points).ok_or_else(|| Error::solve(Day10::DAY, "the points never converge"))?;
+    debug!("start_time: {}", end);
+    let message = extents_at_time(points, end);
+    let pad = message.size()[1];
+    let window = message.expand(pad);
+    let size = window.size();
+    debug!("max_dims: {:?}", size);
+
+    let mut recorder = Recorder::new(settings, Palette::new([0xFF, 0xFF, 0xFF]).with(true, [0, 0, 0]));
+    for time in end.saturating_sub(ANIMATION_SECONDS)..=end {
+        recorder.push(&frame(points, time, window.min, size[0] as usize, size[1] as usize))?;
+        debug!("fr: {}", time);
+    }
+    recorder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = "\
+position=< 9,  1> velocity=< 0,  2>
+position=< 7,  0> velocity=<-1,  0>
+position=< 3, -2> velocity=<-1,  1>
+position=< 6, 10> velocity=<-2, -1>
+position=< 2, -4> velocity=< 2,  2>
+position=<-6, 10> velocity=< 2, -2>
+position=< 1,  8> velocity=< 1, -1>
+position=< 1,  7> velocity=< 1,  0>
+position=<-3, 11> velocity=< 1, -2>
+position=< 7,  6> velocity=<-1, -1>
+position=<-2,  3> velocity=< 1,  0>
+position=<-4,  3> velocity=< 2,  0>
+position=<10, -3> velocity=<-1,  1>
+position=< 5, 11> velocity=< 1, -2>
+position=< 4,  7> velocity=< 0, -1>
+position=< 8, -2> velocity=< 0,  1>
+position=<15,  0> velocity=<-2,  0>
+position=< 1,  6> velocity=< 1,  0>
+position=< 8,  9> velocity=< 0, -1>
+position=< 3,  3> velocity=<-1,  1>
+position=< 0,  5> velocity=< 0, -1>
+position=<-2,  2> velocity=< 2,  0>
+position=< 5, -2> velocity=< 1,  2>
+position=< 1,  4> velocity=< 2,  1>
+position=<-2,  7> velocity=< 2, -2>
+position=< 3,  6> velocity=<-1, -1>
+position=< 5,  0> velocity=< 1,  0>
+position=<-6,  0> velocity=< 2,  0>
+position=< 5,  9> velocity=< 1, -2>
+position=<14,  7> velocity=<-2,  0>
+position=<-3,  6> velocity=< 2, -1>
+";
+
+    #[test]
+    fn test_part1_ex_0() {
+        use super::{parse_points, part1};
+        let message = "\
+#...#..###
+#...#...#.
+#...#...#.
+#####...#.
+#...#...#.
+#...#...#.
+#...#...#.
+#...#..###";
+        assert_eq!(part1(&parse_points(EXAMPLE).unwrap()).unwrap(), message);
+    }
+
+    #[test]
+    fn test_part2_ex_0() {
+        use super::{parse_points, part2};
+        assert_eq!(part2(&parse_points(EXAMPLE).unwrap()), Some(3));
+    }
+}
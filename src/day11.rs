@@ -0,0 +1,215 @@
+use crate::bench::Variant;
+use crate::error::{Error, Result};
+use crate::grid::Grid;
+use crate::solution::Solution;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+    type Input = u32;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<u32> {
+        input.trim().parse::<u32>().map_err(|_| Error::parse_input(Day11::DAY, input, "grid serial number"))
+    }
+
+    fn part1(input: &u32) -> Result<String> {
+        let (x, y, _) = part1(*input);
+        Ok(format!("{},{}", x, y))
+    }
+
+    fn part2(input: &u32) -> Result<String> {
+        let (x, y, n) = part2_summed_area(*input);
+        Ok(format!("{},{},{}", x, y, n))
+    }
+
+    fn variants() -> Vec<Variant<u32>> {
+        vec![Variant::new(1, "summed_area", |input| {
+            let (x, y, _) = part1_summed_area(*input);
+            Ok(format!("{},{}", x, y))
+        })]
+    }
+}
+
+const GRID_DIM: usize = 300;
+
+// the puzzle numbers cells from 1, stored from 0
+struct FuelCells {
+    cells: Grid<i64>,
+    table: Grid<i64>
+}
+
+impl FuelCells {
+    pub fn new(serial: u32) -> FuelCells {
+        let mut cells = Grid::new(GRID_DIM, GRID_DIM, 0i64);
+        for (x, y) in cells.positions().collect::<Vec<_>>() {
+            cells[(x, y)] = get_cell_power_level(x as i32 + 1, y as i32 + 1, serial);
+        }
+        FuelCells { cells, table: Grid::new(GRID_DIM, GRID_DIM, 0i64) }
+    }
+
+    fn cell(grid: &Grid<i64>, x: i32, y: i32) -> Option<i64> {
+        grid.get(x as isize - 1, y as isize - 1).copied()
+    }
+
+    pub fn sample_nxn(&self, x: i32, y: i32, n: usize) -> Option<(i32, i32, i64)> {
+        let mut power: i64 = 0;
+        for i in x..x + n as i32 {
+            for j in y..y + n as i32 {
+                power += FuelCells::cell(&self.cells, i, j)?;
+            }
+        }
+        Some((x, y, power))
+    }
+
+    pub fn build_table(&mut self) {
+        for y in 0..GRID_DIM {
+            self.table[(0, y)] = self.cells[(0, y)];
+            for x in 1..GRID_DIM {
+                self.table[(x, y)] = self.cells[(x, y)] + self.table[(x - 1, y)];
+            }
+        }
+        for y in 1..GRID_DIM {
+            for x in 0..GRID_DIM {
+                self.table[(x, y)] += self.table[(x, y - 1)];
+            }
+        }
+    }
+
+    pub fn sample_table_nxn(&self, x: i32, y: i32, n: usize) -> Option<(i32, i32, i64)> {
+        if n == 0 {
+            return None;
+        }
+
+        let n1 = n - 1;
+        let xn = x + n1 as i32;
+        let yn = y + n1 as i32;
+
+        let mut power = FuelCells::cell(&self.table, xn, yn)?;
+        if x > 1 {
+            power -= FuelCells::cell(&self.table, x - 1, yn)?;
+        }
+
+        if y > 1 {
+            power -= FuelCells::cell(&self.table, xn, y - 1)?;
+        }
+
+        if x > 1 && y > 1 {
+            power += FuelCells::cell(&self.table, x - 1, y - 1)?;
+        }
+        Some((x, y, power))
+    }
+
+    fn coords(&self) -> impl Iterator<Item = (i32, i32)> {
+        self.cells.positions().map(|(x, y)| (x as i32 + 1, y as i32 + 1))
+    }
+}
+
+pub fn get_cell_power_level(x: i32, y: i32, serial: u32) -> i64 {
+    let rack_id = x as i64 + 10;
+    let mut power_level: i64 = rack_id * y as i64;
+    power_level += serial as i64;
+    power_level *= rack_id;
+    power_level = (power_level / 100) % 10;
+    power_level - 5
+}
+
+pub fn part1(input: u32) -> (i32, i32, i64) {
+    let grid = FuelCells::new(input);
+    let mut results = Vec::new();
+    for (x, y) in grid.coords() {
+        let result = grid.sample_nxn(x, y, 3);
+        if result.is_some() {
+            results.push(result.unwrap());
+        }
+    }
+    *results.iter().max_by_key(|x| x.2).unwrap()
+}
+
+// same as part1 but sampling the summed area table
+pub fn part1_summed_area(input: u32) -> (i32, i32, i64) {
+    let mut grid = FuelCells::new(input);
+    grid.build_table();
+    grid.coords().filter_map(|(x, y)| grid.sample_table_nxn(x, y, 3)).max_by_key(|x| x.2).unwrap()
+}
+
+// without summed area it's too slow
+pub fn part2_summed_area(input: u32) -> (i32, i32, i64) {
+    let mut grid = FuelCells::new(input);
+    grid.build_table();
+
+    let mut results = vec![(0i32, 0i32, 0i64); GRID_DIM];
+    for n in 1..=GRID_DIM {
+        let mut result = Vec::new();
+        for (x, y) in grid.coords() {
+            let tmp = grid.sample_table_nxn(x, y, n);
+            if tmp.is_some() {
+                result.push(tmp.unwrap());
+            }
+        }
+        results[n-1] = *result.iter().max_by_key(|x| x.2).unwrap();
+    }
+
+    let mut best: i64 = i64::MIN;
+    let mut output: (i32, i32, i64) = (0, 0, 0);
+    let mut n = 1;
+    for x in &results {
+        if x.2 > best {
+            output = (x.0, x.1, n);
+            best = x.2;
+        }
+        n += 1;
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_part1_power_level() {
+        use super::get_cell_power_level;
+        assert_eq!(get_cell_power_level(3, 5, 8), 4);
+        assert_eq!(get_cell_power_level(122, 79, 57), -5);
+        assert_eq!(get_cell_power_level(217, 196, 39), 0);
+        assert_eq!(get_cell_power_level(101, 153, 71), 4);
+    }
+
+    #[test]
+    fn test_part1_ex_0() {
+        use super::part1;
+        assert_eq!(part1(18), (33, 45, 29));
+    }
+
+    #[test]
+    fn test_part1_ex_1() {
+        use super::part1;
+        assert_eq!(part1(42), (21, 61, 30));
+    }
+
+    #[test]
+    fn test_part1_input() {
+        use super::part1;
+        assert_eq!(part1(9110), (21, 13, 28));
+    }
+
+    #[test]
+    fn test_part1_summed_area() {
+        use super::part1_summed_area;
+        assert_eq!(part1_summed_area(18), (33, 45, 29));
+        assert_eq!(part1_summed_area(42), (21, 61, 30));
+    }
+
+    #[test]
+    fn test_part2_ex_0() {
+        use super::part2_summed_area;
+        assert_eq!(part2_summed_area(18), (90, 269, 16));
+    }
+
+    #[test]
+    fn test_part2_ex_1() {
+        use super::part2_summed_area;
+        assert_eq!(part2_summed_area(42), (232, 251, 12));
+    }
+}
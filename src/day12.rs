@@ -0,0 +1,272 @@
+use log::{debug, trace};
+
+use crate::cycle;
+use crate::diagnostics;
+use crate::error::{Error, Result};
+use crate::format::Format;
+use crate::generate::{Knobs, Rng};
+use crate::oracle::Oracle;
+use crate::solution::Solution;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+    type Input = Problem;
+    type Answer1 = i32;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Problem> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Problem) -> Result<i32> {
+        Ok(part1(input, 20))
+    }
+
+    fn part2(input: &Problem) -> Result<i64> {
+        part2(input, GENERATIONS, 8000).ok_or_else(|| Error::solve(Day12::DAY, "the plants never settle into a repeating pattern"))
+    }
+
+    const GENERATED: bool = true;
+
+    fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+        generate(knobs, rng)
+    }
+
+    // far enough out that the answer comes from the cycle, near enough to step through
+    fn oracles() -> Vec<Oracle<Problem>> {
+        vec![Oracle::new("cycle", |input| {
+            part2(input, 2000, 500).map(|x| x.to_string()).ok_or_else(|| Error::solve(Day12::DAY, "no cycle within 500 generations"))
+        }, |input| Ok(part1(input, 2000).to_string()))]
+    }
+}
+
+const PATTERN_LENGTH: usize = 5;
+const GENERATIONS: usize = 50000000000;
+
+#[derive(Clone)]
+struct Note {
+    pattern: u8,
+    output: bool
+}
+
+#[derive(Clone)]
+pub struct Problem {
+    state: Vec<bool>,
+    zero: usize,
+    notes: Vec<Note>
+}
+
+impl Problem {
+    pub fn num_plants(&self) -> u32 {
+        self.state.iter().fold(0, |sum, val| if *val { sum + 1 } else { sum })
+    }
+
+    pub fn get_window(&self, pos: i32) -> u8 {
+        let index = pos + self.zero as i32;
+        let plant = |i: i32| usize::try_from(i).ok().and_then(|i| self.state.get(i)).map_or(0, |x| *x as u8);
+        (0..PATTERN_LENGTH as i32).fold(0, |window, bit| window | plant(index + bit - 2) << bit)
+    }
+
+    pub fn set(state: &mut Vec<bool>, zero: &mut usize, pos: i32, value: bool) {
+        let tmp = *zero as i32 + pos;
+        if tmp < 0 {
+            let to_insert = tmp.unsigned_abs() as usize;
+            for _x in 0..to_insert {
+               state.insert(0, false);
+            }
+            *zero += to_insert;
+        }
+
+        let index = *zero as i32 + pos;
+        assert!(index >= 0);
+        while (state.len() as i32) < (index + 1) {
+            state.push(false);
+        }
+        assert!(index < state.len() as i32);
+        *state.get_mut(index as usize).unwrap() = value;
+    }
+
+    pub fn state_to_string(&self) -> String {
+        let mut result = String::new();
+        for x in &self.state {
+            if *x { result.push('#'); } else { result.push('.'); }
+        }
+        result
+    }
+
+    pub fn sum_pots(&self) -> i32 {
+        let mut pot = -(self.zero as i32) + 1;
+        let mut result = 0;
+        for val in &self.state {
+            if *val {
+                result += pot;
+            }
+            pot += 1;
+        }
+        result
+    }
+}
+
+fn parse_input(input:&str) -> Result<Problem> {
+    let note = Format::new("{pattern} => {output}");
+    let mut problem = Problem { state: vec![false; 3], zero: 4, notes: Vec::new() };
+    for (line_count, line) in input.lines().enumerate() {
+        if line_count == 0 {
+            let (a,): (String,) = Format::new("initial state: {state}").parse(Day12::DAY, 1, line)?;
+            if !a.chars().all(|c| c == '#' || c == '.') {
+                return Err(Error::parse(Day12::DAY, 1, line, "a state of # and ."));
+            }
+            for char in a.chars() {
+                if char == '#' {
+                    problem.state.push(true);
+                } else {
+                    problem.state.push(false);
+                }
+            }
+
+        } else if line_count >= 2 {
+            let (pattern_str, b): (String, char) = note.parse(Day12::DAY, line_count + 1, line)?;
+            if pattern_str.len() != PATTERN_LENGTH {
+                return Err(Error::parse(Day12::DAY, line_count + 1, line, "a pattern of 5 pots"));
+            }
+            let mut pattern = 0u8;
+            let mut bit = 0u8;
+            for char in pattern_str.chars() {
+                if char == '#' {
+                    pattern |= 1u8 << bit;
+                }
+                bit += 1;
+            }
+
+            let output = b == '#';
+
+            problem.notes.push(Note {pattern, output });
+        }
+    }
+    Ok(problem)
+}
+
+fn compute_generation(problem: &Problem) -> (Vec<bool>, usize) {
+    let len = problem.state.len() as i32;
+    let mut result = Vec::new();
+    let mut zero = problem.zero;
+    // plants can spread up to two pots past either end, in whichever way the notes say
+    for pot in -(problem.zero as i32) - 2..len - problem.zero as i32 + 2 {
+        let window = problem.get_window(pot);
+        if problem.notes.iter().any(|note| note.pattern == window && note.output) {
+            Problem::set(&mut result, &mut zero, pot, true);
+        }
+    }
+    (result, zero)
+}
+
+// an initial state of knobs.count pots and a note for every pattern. Empty pots stay
+// empty so the plants can't fill the infinite row, and only one pattern in
+// knobs.extent grows a plant, which keeps most rows sparse enough to settle down.
+pub fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+    let pot = |plant: bool| if plant { '#' } else { '.' };
+    let state: String = (0..knobs.count.max(1)).map(|_| pot(rng.one_in(2))).collect();
+    let mut result = format!("initial state: {}\n\n", state);
+    for pattern in 0..1 << PATTERN_LENGTH {
+        let pots: String = (0..PATTERN_LENGTH).map(|bit| pot(pattern & 1 << bit != 0)).collect();
+        let output = pattern != 0 && rng.one_in(knobs.extent.max(1));
+        result.push_str(&format!("{} => {}\n", pots, pot(output)));
+    }
+    result
+}
+
+pub fn part1(input: &Problem, n:u32) -> i32 {
+    let mut problem = input.clone();
+    trace!("0: {} plants: {}", problem.state_to_string(), problem.num_plants());
+    for x in 1..=n {
+        let gen = compute_generation(&problem);
+        problem.state = gen.0;
+        problem.zero = gen.1;
+        trace!("{}: {} zero: {} num_plants: {}", x, problem.state_to_string(), problem.zero, problem.num_plants());
+    }
+    problem.sum_pots()
+}
+
+fn next_generation(problem: &Problem) -> Problem {
+    let (state, zero) = compute_generation(problem);
+    Problem { state, zero, notes: problem.notes.clone() }
+}
+
+// the plants without the empty pots in front, the same shape can turn up anywhere
+fn shape(problem: &Problem) -> Vec<bool> {
+    problem.state.iter().skip_while(|x| !**x).copied().collect()
+}
+
+// the plants end up as a shape that moves along at a steady rate, find it within limit
+// generations and carry the pot sum forward from there
+pub fn part2(input: &Problem, generations: usize, limit: usize) -> Option<i64> {
+    let cycle = cycle::hashed(input, next_generation, shape, limit)?;
+    let mut sums = Vec::with_capacity(cycle.start + 2 * cycle.length);
+    let mut problem = input.clone();
+    for _ in 0..cycle.start + 2 * cycle.length {
+        sums.push(problem.sum_pots() as i64);
+        problem = next_generation(&problem);
+    }
+    debug!("cycle start: {} length: {}", cycle.start, cycle.length);
+    diagnostics::record("cycle_start", cycle.start);
+    diagnostics::record("cycle_length", cycle.length);
+    Some(cycle.extrapolate(&sums, generations))
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = "\
+initial state: #..#.#..##......###...###
+
+...## => #
+..#.. => #
+.#... => #
+.#.#. => #
+.#.## => #
+.##.. => #
+.#### => #
+#.#.# => #
+#.### => #
+##.#. => #
+##.## => #
+###.. => #
+###.# => #
+####. => #
+";
+
+    #[test]
+    fn test_part1_ex_0() {
+        use super::{parse_input, part1};
+        assert_eq!(part1(&parse_input(EXAMPLE).unwrap(), 20), 325);
+    }
+
+    #[test]
+    fn test_part2_ex_0() {
+        use super::{parse_input, part1, part2};
+        let problem = parse_input(EXAMPLE).unwrap();
+        assert_eq!(part2(&problem, 20, 8000), Some(325));
+        assert_eq!(part2(&problem, 300, 8000), Some(part1(&problem, 300) as i64));
+        assert_eq!(part2(&problem, 300, 10), None);
+    }
+
+    #[test]
+    fn test_oracles() {
+        use crate::generate::Knobs;
+        use crate::oracle::{disagreements, run, Verdict};
+        use super::Day12;
+        let cases = run::<Day12>(&Knobs { count: 10, extent: 4 }, 8).unwrap();
+        assert!(disagreements(&cases).is_empty(), "{:?}", disagreements(&cases));
+        assert!(cases.iter().any(|x| x.verdict == Verdict::Agree));
+    }
+
+    #[test]
+    fn test_spread_left() {
+        use super::{parse_input, part1};
+        // a plant that moves one pot left every generation, past the pots there were
+        let problem = parse_input("initial state: #\n\n...#. => #\n").unwrap();
+        assert_eq!(part1(&problem, 1), -1);
+        assert_eq!(part1(&problem, 5), -5);
+    }
+}
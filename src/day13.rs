@@ -0,0 +1,387 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use crate::error::{Error, Result};
+use crate::generate::{Knobs, Rng};
+use crate::grid::Grid;
+use crate::recorder::{Palette, Recorder, Settings};
+use crate::solution::Solution;
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+    type Input = Problem;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Problem> {
+        parse(input)
+    }
+
+    fn part1(input: &Problem) -> Result<String> {
+        match part1(input) {
+            Some((x, y)) => Ok(format!("{},{}", x, y)),
+            None => Err(Error::solve(Day13::DAY, "no carts crashed"))
+        }
+    }
+
+    fn part2(input: &Problem) -> Result<String> {
+        match part2(input) {
+            Some((x, y)) => Ok(format!("{},{}", x, y)),
+            None => Err(Error::solve(Day13::DAY, "more than one cart is still running"))
+        }
+    }
+
+    const ANIMATED: bool = true;
+
+    fn animate(input: &Problem, settings: &Settings) -> Result<usize> {
+        animate(input, settings)
+    }
+
+    const GENERATED: bool = true;
+
+    fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+        generate(knobs, rng)
+    }
+}
+
+#[derive(Copy, Clone, Default)]
+struct Cart {
+    x: u32,
+    y: u32,
+    orientation: char,
+    next_turn: u8
+}
+
+#[derive(Clone, Default)]
+pub struct Problem {
+    graph: Grid<char>,
+    carts: HashMap<(u32, u32), Cart>
+}
+
+impl Problem {
+    // the track with the carts drawn on it
+    pub fn frame(&self) -> Grid<char> {
+        let mut graph = self.graph.clone();
+        for cart in self.carts.values() {
+            graph[(cart.x as usize, cart.y as usize)] = cart.orientation;
+        }
+        graph
+    }
+
+    pub fn tick(&mut self, remove_mode: bool) -> Option<(u32, u32)> {
+        let mut carts: Vec<Cart> = Vec::new();
+        for x in self.carts.values() {
+            carts.push(*x);
+        }
+
+        carts.sort_by(|a, b| {
+            if a.y == b.y {
+                a.x.cmp(&b.x)
+            } else {
+                a.y.cmp(&b.y)
+            }
+        });
+
+        let mut dead_cells = HashSet::new();
+        for cart in carts.iter_mut() {
+            self.carts.remove(&(cart.x, cart.y));
+            if remove_mode && dead_cells.contains(&(cart.x, cart.y)) {
+                continue;
+            }
+
+            let mut next_intersection = cart.orientation;
+            match cart.orientation {
+                '<' => {
+                    cart.x -= 1;
+                    match cart.next_turn {
+                        0 => { next_intersection = 'v' }
+                        2 => { next_intersection = '^' }
+                        _ => {}
+                    }
+                }
+                '>' => {
+                    cart.x += 1;
+                    match cart.next_turn {
+                        0 => { next_intersection = '^' }
+                        2 => { next_intersection = 'v' }
+                        _ => {}
+                    }
+                }
+                '^' => {
+                    cart.y -= 1;
+                    match cart.next_turn {
+                        0 => { next_intersection = '<' }
+                        2 => { next_intersection = '>' }
+                        _ => {}
+                    }
+                }
+                'v' => {
+                    cart.y += 1;
+                    match cart.next_turn {
+                        0 => { next_intersection = '>' }
+                        2 => { next_intersection = '<' }
+                        _ => {}
+                    }
+                }
+                _ => panic!("invalid orientation: {}", cart.orientation)
+            }
+
+            let cell = self.graph[(cart.x as usize, cart.y as usize)];
+            match cell {
+                '/' => {
+                    match cart.orientation {
+                        '<' => { cart.orientation = 'v' }
+                        '>' => { cart.orientation = '^' }
+                        '^' => { cart.orientation = '>' }
+                        'v' => { cart.orientation = '<' }
+                        _ => panic!("invalid orientation: {}", cart.orientation)
+                    }
+                }
+                '\\' => {
+                    match cart.orientation {
+                        '<' => { cart.orientation = '^' }
+                        '>' => { cart.orientation = 'v' }
+                        '^' => { cart.orientation = '<' }
+                        'v' => { cart.orientation = '>' }
+                        _ => panic!("invalid orientation: {}", cart.orientation)
+                    }
+                }
+                '+' => {
+                    cart.next_turn = (cart.next_turn + 1) % 3;
+                    cart.orientation = next_intersection;
+                }
+                '|' => {
+                    assert_ne!(cart.orientation, '>');
+                    assert_ne!(cart.orientation, '<');
+                }
+                '-' => {
+                    assert_ne!(cart.orientation, '^');
+                    assert_ne!(cart.orientation, 'v');
+                }
+                _ => panic!("invalid cell {} at ({}, {})", cell, cart.x, cart.y)
+            }
+
+            if remove_mode {
+                if self.carts.contains_key(&(cart.x, cart.y)) {
+                    dead_cells.insert((cart.x, cart.y));
+                    self.carts.remove(&(cart.x, cart.y));
+                } else if !dead_cells.contains(&(cart.x, cart.y)) {
+                    self.carts.insert((cart.x, cart.y), *cart);
+                }
+            } else {
+                if self.carts.contains_key(&(cart.x, cart.y)) {
+                    return Some((cart.x, cart.y));
+                } else {
+                    self.carts.insert((cart.x, cart.y), *cart);
+                }
+            }
+        }
+        None
+    }
+}
+
+fn parse(input: &str) -> Result<Problem> {
+    // trailing spaces may have been trimmed, pad every line out to the widest
+    let width = input.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+    let padded: String = input.lines().map(|line| format!("{:width$}\n", line, width = width)).collect();
+
+    let mut carts = HashMap::new();
+    let graph = Grid::parse_with(Day13::DAY, &padded, "track made of - | / \\ + and carts < > ^ v", |(x, y), ch| {
+        let (x, y) = (x as u32, y as u32);
+        match ch {
+            '<' | '>' => {
+                carts.insert((x, y), Cart {x, y, orientation:ch, next_turn:0});
+                Some('-')
+            }
+            '^' | 'v' => {
+                carts.insert((x, y), Cart {x, y, orientation:ch, next_turn:0});
+                Some('|')
+            }
+            ' ' | '-' | '|' | '/' | '\\' | '+' => Some(ch),
+            _ => None
+        }
+    })?;
+    Ok(Problem { graph, carts })
+}
+
+// rectangular loops of track on a square knobs.extent on a side, crossing where they
+// meet, with knobs.count carts on the straight pieces. Crashes aren't guaranteed, carts
+// can chase each other around forever.
+pub fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+    let side = knobs.extent.max(3);
+    let mut grid = Grid::new(side, side, ' ');
+    for _ in 0..side {
+        let (w, h) = (rng.range(2, side as i64 - 1) as usize, rng.range(2, side as i64 - 1) as usize);
+        let (x0, y0) = (rng.below(side - w), rng.below(side - h));
+        let (x1, y1) = (x0 + w, y0 + h);
+        let mut track = Vec::new();
+        for x in x0 + 1..x1 {
+            track.push(((x, y0), '-'));
+            track.push(((x, y1), '-'));
+        }
+        for y in y0 + 1..y1 {
+            track.push(((x0, y), '|'));
+            track.push(((x1, y), '|'));
+        }
+        track.extend([((x0, y0), '/'), ((x1, y0), '\\'), ((x0, y1), '\\'), ((x1, y1), '/')]);
+
+        // a loop can only cross straight track at right angles, anything else would run
+        // two pieces of track together
+        let fits = track.iter().all(|(pos, ch)| matches!((grid[*pos], *ch), (' ', _) | ('-', '|') | ('|', '-')));
+        if fits {
+            for (pos, ch) in track {
+                grid[pos] = if grid[pos] == ' ' { ch } else { '+' };
+            }
+        }
+    }
+
+    let mut straights: Vec<(usize, usize)> = grid.iter().filter(|(_, ch)| **ch == '-' || **ch == '|').map(|(pos, _)| pos).collect();
+    rng.shuffle(&mut straights);
+    for pos in straights.into_iter().take(knobs.count) {
+        let carts = if grid[pos] == '-' { ['<', '>'] } else { ['^', 'v'] };
+        grid[pos] = *rng.choose(&carts);
+    }
+    let rows: Vec<String> = grid.rows().map(|row| row.iter().collect::<String>().trim_end().to_string()).collect();
+    // no empty rows after the last of the track
+    format!("{}\n", rows.join("\n").trim_end())
+}
+
+pub fn part1(input: &Problem) -> Option<(u32, u32)> {
+    let mut problem = input.clone();
+    let mut panic = 4096;
+    while panic > 0 {
+        let collision = problem.tick(false);
+        if collision.is_some() {
+            return Some(collision.unwrap());
+        }
+        panic -= 1;
+    }
+    None
+}
+
+pub fn part2(input: &Problem) -> Option<(u32, u32)> {
+    let mut problem = input.clone();
+    let mut panic = 1000000;
+    while panic > 0 {
+        problem.tick(true);
+        if problem.carts.len() == 1 {
+            let cart = problem.carts.iter().next().unwrap().1;
+            return Some((cart.x, cart.y));
+        }
+        panic -= 1;
+    }
+    None
+}
+
+// records part 2, carts crashing until one is left
+pub fn animate(input: &Problem, settings: &Settings) -> Result<usize> {
+    let track = [0x80, 0x80, 0x80];
+    let cart = [0xFF, 0x40, 0x40];
+    let palette = Palette::new([0, 0, 0])
+        .with('-', track).with('|', track).with('/', track).with('\\', track).with('+', track)
+        .with('<', cart).with('>', cart).with('^', cart).with('v', cart);
+    let mut recorder = Recorder::new(settings, palette);
+
+    let mut problem = input.clone();
+    let mut panic = 1000000;
+    recorder.push(&problem.frame())?;
+    while problem.carts.len() > 1 && panic > 0 {
+        problem.tick(true);
+        recorder.push(&problem.frame())?;
+        panic -= 1;
+    }
+    recorder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_part1_ex0() {
+        use super::{parse, part1};
+        let input = concat!(
+            "/->-\\        \n",
+            "|   |  /----\\ \n",
+            "| /-+--+-\\  |\n",
+            "| | |  | v  |\n",
+            "\\-+-/  \\-+--/\n",
+            "  \\------/   \n",
+        );
+        assert_eq!(part1(&parse(input).unwrap()).unwrap(), (7, 3));
+    }
+
+    #[test]
+    fn test_part1_extra() {
+        // additional test I added because originally passing test_part1_ex0 but input not working
+        use super::{parse, part1};
+        let input = concat!(
+            "    +--\\\n",
+            "    +  |\n",
+            "->--+  |\n",
+            "       |\n",
+            "       |\n",
+            "       |\n",
+            "       |\n",
+            "       |\n",
+            "       |\n",
+            "       ^\n",
+        );
+        assert_eq!(part1(&parse(input).unwrap()).unwrap(), (7, 1));
+    }
+
+    #[test]
+    #[ignore = "needs the original author's puzzle input in inputs/ or AOC_INPUT_DIR"]
+    fn test_part1_input() {
+        use crate::solution::load_input;
+        use super::Day13;
+        use super::part1;
+        assert_eq!(part1(&load_input::<Day13>().unwrap()).unwrap(), (64, 57));
+    }
+
+    #[test]
+    fn test_part2_ex() {
+        use super::{parse, part2};
+        let input = concat!(
+            "/>-<\\  \n",
+            "|   |  \n",
+            "| /<+-\\\n",
+            "| | | v\n",
+            "\\>+</ |\n",
+            "  |   ^\n",
+            "  \\<->/\n",
+        );
+        assert_eq!(part2(&parse(input).unwrap()).unwrap(), (6, 4));
+    }
+
+    #[test]
+    fn test_animate() {
+        use super::{animate, parse};
+        use crate::recorder::{Output, Settings};
+        let input = concat!(
+            "/>-<\\  \n",
+            "|   |  \n",
+            "| /<+-\\\n",
+            "| | | v\n",
+            "\\>+</ |\n",
+            "  |   ^\n",
+            "  \\<->/\n",
+        );
+        let path = std::env::temp_dir().join(format!("aoc-day13-{}.gif", std::process::id()));
+        let mut settings = Settings::new(Output::Gif(path.clone()));
+        settings.scale = 2;
+        // the start and one frame per tick until the last cart is alone
+        assert_eq!(animate(&parse(input).unwrap(), &settings).unwrap(), 4);
+        assert!(std::fs::metadata(&path).unwrap().len() > 0);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    #[ignore = "needs the original author's puzzle input in inputs/ or AOC_INPUT_DIR"]
+    fn test_part2_input() {
+        use crate::solution::load_input;
+        use super::Day13;
+        use super::part2;
+        assert_eq!(part2(&load_input::<Day13>().unwrap()).unwrap(), (136, 8));
+    }
+}
@@ -1,9 +1,38 @@
-#![feature(test)]
 
-extern crate test;
 
-#[allow(dead_code)]
-fn part1(input: &str, to_create: i32) -> String {
+use crate::deadline;
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+    type Input = String;
+    type Answer1 = String;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<String> {
+        let input = input.trim();
+        if input.is_empty() || !input.chars().all(|c| c.is_ascii_digit()) {
+            return Err(Error::parse_input(Day14::DAY, input, "a number of recipes"));
+        }
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &String) -> Result<String> {
+        match input.parse::<i32>() {
+            Ok(to_create) => Ok(part1("37", to_create)),
+            Err(_) => Err(Error::parse_input(Day14::DAY, input, "a number of recipes"))
+        }
+    }
+
+    fn part2(input: &String) -> Result<u32> {
+        Ok(part2("37", input))
+    }
+}
+
+pub fn part1(input: &str, to_create: i32) -> String {
     let mut scoreboard = Vec::new();
     for ch in input.chars() {
         scoreboard.push(ch.to_digit(10).unwrap());
@@ -19,7 +48,7 @@ fn part1(input: &str, to_create: i32) -> String {
             if recipes_created >= to_create + 10 {
                 let mut output = String::new();
                 for x in 0..10 {
-                    let ch2 = scoreboard[to_create as usize + x as usize].to_string().chars().nth(0).unwrap();
+                    let ch2 = scoreboard[to_create as usize + x as usize].to_string().chars().next().unwrap();
                     output.push(ch2);
                 }
                 return output;
@@ -32,7 +61,7 @@ fn part1(input: &str, to_create: i32) -> String {
     }
 }
 
-fn part2(input: &str, sequence: &str) -> u32 {
+pub fn part2(input: &str, sequence: &str) -> u32 {
     let mut seq_vec = Vec::new();
     let mut scoreboard = Vec::new();
     for ch in input.chars() {
@@ -43,7 +72,13 @@ fn part2(input: &str, sequence: &str) -> u32 {
     }
     let seq_len = seq_vec.len();
     let mut elfs: Vec<usize> = (0..scoreboard.len()).collect();
+    // the sequence may never turn up, the clock is checked every so often
+    let mut rounds = 0u32;
     loop {
+        rounds = rounds.wrapping_add(1);
+        if rounds.is_multiple_of(65536) && deadline::expired() {
+            return 0;
+        }
         let sum: u32 = elfs.iter().fold(0, |sum, x| sum + scoreboard[*x]);
         let created = sum.to_string();
         for ch in created.chars() {
@@ -76,59 +111,55 @@ fn part2(input: &str, sequence: &str) -> u32 {
 mod tests {
     #[test]
     fn test_part1_ex0() {
-        use part1;
+        use super::part1;
         assert_eq!(part1("37", 9), "5158916779");
     }
 
     #[test]
     fn test_part1_ex1() {
-        use part1;
+        use super::part1;
         assert_eq!(part1("37", 5), "0124515891");
     }
 
     #[test]
     fn test_part1_ex2() {
-        use part1;
+        use super::part1;
         assert_eq!(part1("37", 18), "9251071085");
     }
 
     #[test]
     fn test_part1_ex3() {
-        use part1;
+        use super::part1;
         assert_eq!(part1("37", 2018), "5941429882");
     }
 
     #[test]
     fn test_part1_input() {
-        use part1;
+        use super::part1;
         assert_eq!(part1("37", 513401), "5371393113");
     }
 
     #[test]
     fn test_part2_ex0() {
-        use part2;
+        use super::part2;
         assert_eq!(part2("37", "51589"), 9);
     }
 
     #[test]
     fn test_part2_ex1() {
-        use part2;
+        use super::part2;
         assert_eq!(part2("37", "01245"), 5);
     }
 
     #[test]
     fn test_part2_ex2() {
-        use part2;
+        use super::part2;
         assert_eq!(part2("37", "92510"), 18);
     }
 
     #[test]
     fn test_part2_ex3() {
-        use part2;
+        use super::part2;
         assert_eq!(part2("37", "59414"), 2018);
     }
 }
-
-fn main() {
-    println!("result: {}", part2("37", "513401"));
-}
@@ -0,0 +1,597 @@
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::default::Default;
+
+use log::{debug, trace};
+
+use crate::deadline;
+use crate::diagnostics;
+use crate::error::{Error, Result};
+use crate::generate::{Knobs, Rng};
+use crate::grid::Grid;
+use crate::recorder::{Palette, Recorder, Settings};
+use crate::solution::Solution;
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u32 = 15;
+    type Input = Map;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Map> {
+        parse(input)
+    }
+
+    fn part1(input: &Map) -> Result<u32> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Map) -> Result<u32> {
+        part2(input).ok_or_else(|| Error::solve(Day15::DAY, "no attack power lets the elves win without losses"))
+    }
+
+    const ANIMATED: bool = true;
+
+    fn animate(input: &Map, settings: &Settings) -> Result<usize> {
+        animate(input, settings)
+    }
+
+    const GENERATED: bool = true;
+
+    fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+        generate(knobs, rng)
+    }
+}
+
+#[derive(Copy, Clone, Default)]
+pub struct Npc {
+    class: char,
+    x: u32,
+    y: u32,
+    ap: u32,
+    hp: u32
+}
+
+impl Npc {
+    pub fn get_hostile(&self) -> char {
+        if self.class == 'E' { 'G' } else { 'E' }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Map {
+    squares: Grid<char>,
+    npcs: HashMap<(u32, u32), Npc>
+}
+
+impl Map {
+    // the map with the units drawn on it
+    pub fn frame(&self) -> Grid<char> {
+        let mut squares = self.squares.clone();
+        for npc in self.npcs.values() {
+            squares[(npc.x as usize, npc.y as usize)] = npc.class;
+        }
+        squares
+    }
+
+    // the map with each row's units and their hit points beside it
+    pub fn render(&self) -> String {
+        let mut result = String::new();
+        let mut string = String::new();
+        for (y, line) in self.squares.rows().enumerate() {
+            let mut units = String::new();
+            for (x, contents) in line.iter().enumerate() {
+                match self.npcs.get(&(x as u32, y as u32)) {
+                    Some(npc) => {
+                        string.push(npc.class);
+                        units.push_str(format!("{}({}), ", npc.class, npc.hp).as_str());
+                    },
+                    None => string.push(*contents)
+                }
+            }
+            result.push_str(&format!("{}    {}\n", string, units));
+            string.clear();
+        }
+        result
+    }
+
+    #[allow(dead_code)]
+    pub fn try_add_defender(&self, npc: &Npc, coord: &(u32, u32)) -> bool {
+        let tmp = self.npcs.get(coord);
+        if tmp.is_some() {
+            let x = tmp.unwrap();
+            if x.class != npc.class {
+                return true;
+            }
+        }
+        false
+    }
+
+    #[allow(dead_code)]
+    pub fn get_defenders(&self, npc: &Npc) -> Vec<Npc> {
+        let mut result = Vec::new();
+        let mut coord = (npc.x+1, npc.y);
+        if self.try_add_defender(npc, &coord) { result.push(*self.npcs.get(&coord).unwrap()); }
+        coord = (npc.x-1, npc.y);
+        if self.try_add_defender(npc, &coord) { result.push(*self.npcs.get(&coord).unwrap()); }
+        coord = (npc.x, npc.y+1);
+        if self.try_add_defender(npc, &coord) { result.push(*self.npcs.get(&coord).unwrap()); }
+        coord = (npc.x, npc.y-1);
+        if self.try_add_defender(npc, &coord) { result.push(*self.npcs.get(&coord).unwrap()); }
+        result
+    }
+
+    #[allow(dead_code)]
+    pub fn process_attack(&mut self, npc: &Npc) -> Option<(u32, u32)> {
+        let mut defenders = self.get_defenders(npc);
+        defenders.sort_by(|a, b| {
+           if a.hp != b.hp {
+               a.hp.cmp(&b.hp)
+           } else {
+               if a.y == b.y {
+                   a.x.cmp(&b.x)
+               } else {
+                   a.y.cmp(&b.y)
+               }
+           }
+        });
+
+        if !defenders.is_empty() {
+            let defender = &defenders[0];
+            if defender.hp > npc.ap {
+                self.npcs.get_mut(&(defender.x, defender.y)).unwrap().hp -= npc.ap;
+            } else {
+                return Some((defender.x, defender.y));
+            }
+        }
+        None
+    }
+
+    pub fn in_range_of_enemy(&self, npc: &Npc) -> bool {
+        !self.get_defenders(npc).is_empty()
+    }
+
+    pub fn is_vacant(&self, coord: &(u32, u32)) -> bool {
+        match self.squares[(coord.0 as usize, coord.1 as usize)] {
+            '.' => !self.npcs.contains_key(coord),
+            _ => false
+        }
+    }
+
+    pub fn get_vacant(&self, coord: &(u32, u32)) -> Vec<(u32, u32)> {
+        self.squares.neighbours4(coord.0 as usize, coord.1 as usize)
+            .map(|(x, y)| (x as u32, y as u32))
+            .filter(|tmp| self.is_vacant(tmp))
+            .collect()
+    }
+
+    pub fn find_steps(&self, from: &(u32, u32), to: &(u32, u32)) -> Option<Vec<((u32, u32), u32, (u32, u32))>> {
+        let vacant = self.get_vacant(from);
+        if vacant.is_empty() {
+            return None;
+        }
+
+        // distance, no early terminate, no biasing i.e. slow and lame
+        let mut queue = VecDeque::new();
+        let mut visited = HashMap::new();
+        queue.push_back(*to);
+        visited.insert(*to, 0);
+        while !queue.is_empty() {
+            let square = queue.pop_front().unwrap();
+            let neighbors = self.get_vacant(&square);
+            for neighbor in &neighbors {
+                if !visited.contains_key(neighbor) {
+                    let dist = *visited.get(&square).unwrap() + 1;
+                    visited.insert(*neighbor, dist);
+                    queue.push_back(*neighbor);
+                }
+            }
+        }
+
+        let mut result = Vec::new();
+        for square in &vacant {
+            let visit = visited.get(square);
+            if visit.is_some() {
+                result.push((*square, *visit.unwrap(), *to));
+            }
+        }
+        Some(result)
+    }
+
+    pub fn get_target_squares(&self, class: char) -> Vec<(u32, u32)> {
+        let mut result = Vec::new();
+        for npc in self.npcs.values() {
+            if npc.class == class {
+                let coord = (npc.x, npc.y);
+                let mut squares = self.get_vacant(&coord);
+                if !squares.is_empty() {
+                    result.append(&mut squares);
+                }
+            }
+        }
+        result
+    }
+
+    pub fn process_move(&mut self, npc: &Npc) -> Option<(u32, u32)> {
+        let targets = self.get_target_squares(npc.get_hostile());
+        if targets.is_empty() {
+            return None;
+        }
+
+        let mut steps = Vec::new();
+        for target in &targets {
+            let tmp = self.find_steps(&(npc.x, npc.y), target);
+            if tmp.is_some() {
+                steps.append(&mut tmp.unwrap());
+            }
+        }
+
+        if !steps.is_empty() {
+            steps.sort_by(|a, b| {
+                // the guy who made this problem ruined my xmas
+                let da = a.1;
+                let db = b.1;
+                if da != db {
+                    da.cmp(&db)
+                } else {
+                    if a.2 == b.2 {
+                        if (a.0).1 == (b.0).1 {
+                            (a.0).0.cmp(&(b.0).0)
+                        } else {
+                            (a.0).1.cmp(&(b.0).1)
+                        }
+                    } else {
+                        if (a.2).1 == (b.2).1 {
+                            (a.2).0.cmp(&(b.2).0)
+                        } else {
+                            (a.2).1.cmp(&(b.2).1)
+                        }
+                    }
+                }
+            });
+
+            // do best step
+            let step = steps[0];
+            self.npcs.remove(&(npc.x, npc.y));
+            let mut moved = *npc;
+            moved.x = (step.0).0;
+            moved.y = (step.0).1;
+            self.npcs.insert((moved.x, moved.y), moved);
+            return Some((moved.x, moved.y));
+        }
+        None
+    }
+
+    pub fn is_done(&self) -> bool {
+        let mut found_goblin = false;
+        let mut found_elf = false;
+        for x in self.npcs.values() {
+            found_goblin |= x.class == 'G';
+            found_elf |= x.class == 'E';
+            if found_goblin && found_elf {
+                return false;
+            }
+        }
+        true
+    }
+
+    pub fn process_turn(&mut self, early: bool) -> (bool, bool) {
+        let mut npc_coords: Vec<(u32, u32)> = Vec::new();
+        for npc in self.npcs.values() {
+            npc_coords.push((npc.x, npc.y));
+        }
+
+        npc_coords.sort_by(|a, b| {
+            if a.1 == b.1 {
+                a.0.cmp(&b.0)
+            } else {
+                a.1.cmp(&b.1)
+            }
+        });
+
+        let mut interrupted = false;
+        for coord in &npc_coords {
+            if !self.npcs.contains_key(coord) {
+                continue;
+            }
+
+            let mut npc = *self.npcs.get(coord).unwrap();
+            if !self.in_range_of_enemy(&npc) {
+                let new_coord = self.process_move(&npc);
+                if new_coord.is_some() {
+                    npc = *self.npcs.get(&new_coord.unwrap()).unwrap();
+                } else {
+                    continue;
+                }
+            }
+
+            let remove = self.process_attack(&npc);
+            if remove.is_some() {
+                let tmp = remove.unwrap();
+                if early && self.npcs.get(&tmp).unwrap().class == 'E' {
+                    return (true, true);
+                }
+                self.npcs.remove(&tmp);
+            }
+
+            if self.is_done() && npc_coords.last().unwrap() != coord {
+                interrupted = true;
+                break;
+            }
+        }
+        (interrupted, false)
+    }
+
+    fn hp_remaining(&self) -> u32 {
+        self.npcs.values().fold(0,|sum, x| sum + x.hp)
+    }
+
+    fn num_class(&self, class: char) -> u32 {
+        self.npcs.values().fold( 0, |sum, x| if x.class == class { sum + 1 } else { sum } )
+    }
+}
+
+fn parse(input: &str) -> Result<Map> {
+    let mut npcs = HashMap::new();
+    let squares = Grid::parse_with(Day15::DAY, input, "map made of # . E G", |(x, y), ch| {
+        let (x, y) = (x as u32, y as u32);
+        match ch {
+            'E' | 'G' => {
+                npcs.insert((x, y), Npc { class: ch, x, y, ap: 3, hp: 200 });
+                Some('.')
+            }
+            '#' | '.' => Some(ch),
+            _ => None
+        }
+    })?;
+    Ok(Map { squares, npcs })
+}
+
+// a walled cave knobs.extent on a side with rock scattered through it and knobs.count
+// units, elves and goblins taking turns. Only the biggest open area is kept so every
+// unit can reach every other one.
+pub fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+    let side = knobs.extent.max(3);
+    let mut cave = Grid::new(side, side, '#');
+    for y in 1..side - 1 {
+        for x in 1..side - 1 {
+            if !rng.one_in(5) {
+                cave[(x, y)] = '.';
+            }
+        }
+    }
+
+    let mut area = Grid::new(side, side, usize::MAX);
+    let mut sizes = Vec::new();
+    for start in cave.positions().collect::<Vec<(usize, usize)>>() {
+        if cave[start] != '.' || area[start] != usize::MAX {
+            continue;
+        }
+        area[start] = sizes.len();
+        let mut size = 0;
+        let mut todo = vec![start];
+        while let Some(pos) = todo.pop() {
+            size += 1;
+            for next in cave.neighbours4(pos.0, pos.1) {
+                if cave[next] == '.' && area[next] == usize::MAX {
+                    area[next] = sizes.len();
+                    todo.push(next);
+                }
+            }
+        }
+        sizes.push(size);
+    }
+    let biggest = (0..sizes.len()).max_by_key(|i| sizes[*i]);
+    let mut open = Vec::new();
+    for pos in cave.positions().collect::<Vec<(usize, usize)>>() {
+        if Some(area[pos]) == biggest {
+            open.push(pos);
+        } else {
+            cave[pos] = '#';
+        }
+    }
+
+    rng.shuffle(&mut open);
+    for (i, pos) in open.into_iter().take(knobs.count).enumerate() {
+        cave[pos] = if i % 2 == 0 { 'E' } else { 'G' };
+    }
+    cave.to_string()
+}
+
+// copy of the parsed map with the elves given attack power ap
+fn with_elf_ap(input: &Map, ap: u32) -> Map {
+    let mut map = input.clone();
+    for npc in map.npcs.values_mut() {
+        if npc.class == 'E' {
+            npc.ap = ap;
+        }
+    }
+    map
+}
+
+fn battle(input: &Map, ap: u32) -> u32 {
+    let mut map = with_elf_ap(input, ap);
+    let mut round = 0;
+
+    trace!("Initially:\n{}", map.render());
+
+    loop {
+        if deadline::expired() {
+            return 0;
+        }
+        round += 1;
+        trace!("simulating {}", round);
+        let done = map.is_done();
+        if done || map.process_turn(false).0 {
+            trace!("Round {} incomplete:\n{}", round, map.render());
+            debug!("round: {} hp: {} done: {}", round - 1, map.hp_remaining(), done);
+            diagnostics::record("rounds", round - 1);
+            diagnostics::record("hp", map.hp_remaining());
+            return (round - 1) * map.hp_remaining();
+        }
+
+        trace!("After {} rounds:\n{}", round, map.render());
+    }
+}
+
+// records the part 1 battle a round at a time
+pub fn animate(input: &Map, settings: &Settings) -> Result<usize> {
+    let palette = Palette::new([0, 0, 0])
+        .with('#', [0x6B, 0x4F, 0x2F])
+        .with('E', [0x40, 0xE0, 0x40])
+        .with('G', [0xE0, 0x40, 0x40]);
+    let mut recorder = Recorder::new(settings, palette);
+
+    let mut map = with_elf_ap(input, 3);
+    recorder.push(&map.frame())?;
+    while !map.is_done() {
+        map.process_turn(false);
+        recorder.push(&map.frame())?;
+    }
+    recorder.finish()
+}
+
+pub fn part1(input: &Map) -> u32 {
+    battle(input, 3)
+}
+
+fn find_ap(input: &Map) -> Option<u32> {
+    // lame but tired of this problem
+    // use to figure out ap then use ap in battle
+    for x in 4..50u32 {
+        trace!("ap: {}", x);
+        let mut map = with_elf_ap(input, x);
+        let mut iter = 1;
+        loop {
+            if deadline::expired() {
+                return None;
+            }
+            let done = map.is_done();
+            let result = map.process_turn(true);
+            if done || result.0 {
+                if result.1 {
+                    break;
+                } else {
+                    return Some(x);
+                }
+            }
+            if iter % 10 == 0 {
+                trace!("iter: {} hp: {} goblins: {} elves: {}", iter, map.hp_remaining(), map.num_class('G'), map.num_class('E'));
+            }
+            iter += 1;
+        }
+    }
+    None
+}
+
+pub fn part2(input: &Map) -> Option<u32> {
+    let ap = find_ap(input)?;
+    diagnostics::record("elf_ap", ap);
+    Some(battle(input, ap))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_part1_ex0() {
+        use super::{parse, part1};
+        let input = "\
+#######
+#.G...#
+#...EG#
+#.#.#G#
+#..G#E#
+#.....#
+#######
+";
+        assert_eq!(part1(&parse(input).unwrap()), 27730);
+    }
+
+    #[test]
+    fn test_part1_ex1() {
+        use super::{parse, part1};
+        let input = "\
+#######
+#G..#E#
+#E#E.E#
+#G.##.#
+#...#E#
+#...E.#
+#######
+";
+        assert_eq!(part1(&parse(input).unwrap()), 36334);
+    }
+
+    #[test]
+    fn test_part1_ex2() {
+        use super::{parse, part1};
+        let input = "\
+#######
+#E..EG#
+#.#G.E#
+#E.##E#
+#G..#.#
+#..E#.#
+#######
+";
+        assert_eq!(part1(&parse(input).unwrap()), 39514);
+    }
+
+    #[test]
+    fn test_part1_ex3() {
+        use super::{parse, part1};
+        let input = "\
+#######
+#E.G#.#
+#.#G..#
+#G.#.G#
+#G..#.#
+#...E.#
+#######
+";
+        assert_eq!(part1(&parse(input).unwrap()), 27755);
+    }
+
+    #[test]
+    fn test_part1_ex4() {
+        use super::{parse, part1};
+        let input = "\
+#######
+#.E...#
+#.#..G#
+#.###.#
+#E#G#G#
+#...#G#
+#######
+";
+        assert_eq!(part1(&parse(input).unwrap()), 28944);
+    }
+
+    #[test]
+    fn test_part1_ex5() {
+        use super::{parse, part1};
+        let input = "\
+#########
+#G......#
+#.E.#...#
+#..##..G#
+#...##..#
+#...#...#
+#.G...G.#
+#.....G.#
+#########
+";
+        assert_eq!(part1(&parse(input).unwrap()), 18740);
+    }
+
+    #[test]
+    #[ignore = "needs the original author's puzzle input in inputs/ or AOC_INPUT_DIR"]
+    fn test_part1_input() {
+        use crate::solution::load_input;
+        use super::Day15;
+        use super::part1;
+        assert_eq!(part1(&load_input::<Day15>().unwrap()), 250594);
+    }
+}
@@ -0,0 +1,199 @@
+use log::{debug, log_enabled, trace, Level};
+
+use crate::elfcode;
+use crate::error::{Error, Result};
+use crate::format::Format;
+use crate::solution::Solution;
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u32 = 16;
+    type Input = Vec<String>;
+    type Answer1 = u32;
+    type Answer2 = u64;
+
+    // the samples and program are checked as they are run
+    fn parse(input: &str) -> Result<Vec<String>> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(input: &Vec<String>) -> Result<u32> {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<String>) -> Result<u64> {
+        part2(input)
+    }
+}
+
+type Device = elfcode::Device<4>;
+
+// tries every instruction on a sample, returns how many give the result
+fn execute_sample(device: &mut Device, regs: &[u32; 4], inst: &[u32; 4], result: &[u32; 4], stats: &mut Vec<Vec<u32>>) -> u32 {
+    let mut count = 0u32;
+    let instructions = device.instructions.clone();
+    for (index, instr) in instructions.iter().enumerate() {
+        device.registers = regs.map(u64::from);
+        device.apply(instr, inst);
+        if device.registers == result.map(u64::from) {
+            count += 1;
+            let line = &mut stats[inst[0] as usize];
+            line[index] += 1;
+        }
+    }
+    count
+}
+
+fn scan_quad(format: &Format, line: &str, lc: usize) -> Result<[u32; 4]> {
+    let (a, b, c, d) = format.parse(Day16::DAY, lc + 1, line)?;
+    Ok([a, b, c, d])
+}
+
+// an instruction line, opcode a b c, every operand has to be usable as a register
+fn scan_instruction(line: &str, lc: usize) -> Result<[u32; 4]> {
+    let inst = scan_quad(&Format::new("{opcode} {a} {b} {c}"), line, lc)?;
+    if inst[0] >= 16 || inst[1..].iter().any(|x| *x >= 4) {
+        return Err(Error::parse(Day16::DAY, lc + 1, line, "an opcode below 16 and registers below 4"));
+    }
+    Ok(inst)
+}
+
+// returns the number of samples matching 3 or more instructions and the line the test program starts on
+fn run_samples(lines: &Vec<String>, device: &mut Device, stats: &mut Vec<Vec<u32>>) -> Result<(u32, usize)> {
+    let mut regs: [u32; 4] = [0u32; 4];
+    let mut inst: [u32; 4] = [0u32; 4];
+
+    let before = Format::new("Before: [{a}, {b}, {c}, {d}]");
+    let after = Format::new("After: [{a}, {b}, {c}, {d}]");
+    let mut lc = 0;
+    let mut count = 0u32;
+
+    for line in lines {
+        match lc % 4 {
+            0 => {
+                if !line.starts_with("Before") {
+                    break;
+                }
+                regs = scan_quad(&before, line, lc)?;
+            },
+            1 => {
+                inst = scan_instruction(line, lc)?;
+            },
+            2 => {
+                let result = scan_quad(&after, line, lc)?;
+
+                if execute_sample(device, &regs, &inst, &result, stats) >= 3 {
+                    count += 1;
+                }
+            },
+            _ => {}
+        }
+        lc += 1;
+    }
+    Ok((count, lc))
+}
+
+// false if the samples don't narrow every opcode down to one instruction
+fn resolve_opcodes(device: &mut Device, stats: &Vec<Vec<u32>>) -> bool {
+    let mut assigned: [bool; 16] = [false; 16];
+    let mut assigned_ops: [bool; 16] = [false; 16];
+    let mut assign_count = 0;
+    while assign_count < 16 {
+        let mut opcode = 0u32;
+        let orig_count = assign_count;
+        for arr in stats {
+            if assigned_ops[opcode as usize] {
+                opcode += 1;
+                continue;
+            }
+
+            // find unique
+            let mut num = 0u32;
+            let mut idx = 0;
+            for x in 0..16 {
+                if arr[x] > 0 && !assigned[x] {
+                    num += 1;
+                    idx = x;
+                }
+            }
+            if num == 1 {
+                trace!("assign {} to {}", opcode, device.instructions[idx].mnemonic);
+                assign_count += 1;
+                assigned[idx] = true;
+                assigned_ops[opcode as usize] = true;
+                device.instructions[idx].opcode = opcode;
+                break;
+            } else {
+                trace!("{} num {}", opcode, num);
+                opcode += 1;
+            }
+        }
+        if assign_count == orig_count {
+            return false;
+        }
+    }
+    true
+}
+
+pub fn part1(lines: &Vec<String>) -> Result<u32> {
+    let mut device = Device::default();
+    let mut stats: Vec<Vec<u32>> = vec![vec![0u32; 16]; 16];
+    let (count, _) = run_samples(lines, &mut device, &mut stats)?;
+
+    // the opcodes aren't needed for part 1, only worked out to be logged
+    if log_enabled!(Level::Debug) && resolve_opcodes(&mut device, &stats) {
+        for inst in &device.instructions {
+            debug!("{}, {}", inst.mnemonic, inst.opcode);
+        }
+    }
+
+    Ok(count)
+}
+
+pub fn part2(lines: &Vec<String>) -> Result<u64> {
+    let mut device = Device::default();
+    let mut stats: Vec<Vec<u32>> = vec![vec![0u32; 16]; 16];
+    let (_, program_start) = run_samples(lines, &mut device, &mut stats)?;
+    if !resolve_opcodes(&mut device, &stats) {
+        return Err(Error::solve(Day16::DAY, "the samples don't identify every opcode"));
+    }
+
+    device.instructions.sort_by_key(|a| a.opcode);
+    device.registers = [0u64; 4];
+    for (lc, line) in lines.iter().enumerate().skip(program_start) {
+        if line.is_empty() {
+            continue;
+        }
+        let inst = scan_instruction(line, lc)?;
+        trace!("exec: {:?}", inst);
+        device.execute(&inst);
+    }
+
+    Ok(device.registers[0])
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_part1_ex0() {
+        use super::{run_samples, Device, Day16};
+        use crate::solution::Solution;
+        let lines = Day16::parse("\
+Before: [3, 2, 1, 1]
+9 2 1 2
+After:  [3, 2, 2, 1]
+").unwrap();
+        let mut stats: Vec<Vec<u32>> = vec![vec![0u32; 16]; 16];
+        assert_eq!(run_samples(&lines, &mut Device::default(), &mut stats).unwrap().0, 1);
+    }
+
+    #[test]
+    #[ignore = "needs the original author's puzzle input in inputs/ or AOC_INPUT_DIR"]
+    fn test_part1_input() {
+        use crate::solution::load_input;
+        use super::Day16;
+        use super::part1;
+        assert_eq!(part1(&load_input::<Day16>().unwrap()).unwrap(), 677);
+    }
+}
@@ -0,0 +1,352 @@
+use std::collections::HashSet;
+
+use log::{debug, trace};
+
+use crate::error::{Error, Result};
+use crate::format::{first_of, Format};
+use crate::generate::{Knobs, Rng};
+use crate::grid::Grid;
+use crate::recorder::{Canvas, Palette, Recorder, Settings};
+use crate::solution::Solution;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u32 = 17;
+    // clay veins as (x0, x1, y0, y1)
+    type Input = Vec<(usize, usize, usize, usize)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize> {
+        Ok(solution(input, None)?.0)
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize> {
+        Ok(solution(input, None)?.1)
+    }
+
+    const ANIMATED: bool = true;
+
+    fn animate(input: &Self::Input, settings: &Settings) -> Result<usize> {
+        let mut recorder = Recorder::new(settings, palette());
+        solution(input, Some(&mut recorder))?;
+        recorder.finish()
+    }
+
+    const GENERATED: bool = true;
+
+    fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+        generate(knobs, rng)
+    }
+}
+
+#[derive(Default)]
+struct Scan {
+    grid: Grid<char>,
+    xmin: usize,
+    xmax: usize,
+    ymin: usize,
+    ymax: usize,
+    active_water: Vec<(usize, usize)>,
+    reached: HashSet<(usize, usize)>,
+    sand: HashSet<(usize, usize)>
+}
+
+impl Scan {
+    pub fn init(&mut self, xmin: usize, xmax: usize, ymin: usize, ymax: usize) {
+        self.xmin = xmin;
+        self.xmax = xmax;
+        self.ymin = ymin;
+        self.ymax = ymax;
+
+        self.grid = Grid::new(xmax - xmin + 1, ymax + 1, '.');
+
+        self.active_water = Vec::new();
+        self.active_water.push((500 - xmin, 0));
+        self.reached = HashSet::new();
+        self.sand = HashSet::new();
+    }
+
+    // the scan with squares the water has reached marked x
+    pub fn render(&self) -> String {
+        let mut marked = self.grid.clone();
+        for (x, y) in &self.reached {
+            if marked[(*x, *y)] == '.' {
+                marked[(*x, *y)] = 'x';
+            }
+        }
+        format!("{}rows: {}", marked, self.grid.height())
+    }
+
+    // water at rest, drawn blue when recorded
+    pub fn retained(&self) -> usize {
+        self.grid.iter().filter(|(pos, cell)| (**cell == '~' || **cell == '|') && !self.sand.contains(pos)).count()
+    }
+
+    pub fn fill_row_at(&mut self, coord: (usize, usize), forw: bool) -> (Vec<(usize, usize)>, bool, usize) {
+        let mut result = Vec::new();
+        let grid = &mut self.grid;
+        let mut iter = coord.0;
+        let mut revisit = false;
+        loop {
+            match grid[(iter, coord.1)] {
+                '.' => {
+                    let next = grid[(iter, coord.1+1)];
+                    self.reached.insert((iter, coord.1));
+                    if next == '.' {
+                        grid[(iter, coord.1)] = '|';
+                        result.push((iter, coord.1 + 1));
+                        revisit = true;
+                        break;
+                    } else {
+                        grid[(iter, coord.1)] = '~';
+                    }
+                },
+                '|' => { revisit = true; break; }
+                '~' => {},
+                '#' => { break; },
+                _ => { panic!("unkown contents at {:?} {}", (iter, coord.1), grid[(iter, coord.1)]) }
+            }
+            if forw { iter += 1 } else { iter -= 1; }
+        }
+        (result, revisit, iter)
+    }
+
+    pub fn bounded(&self, coord: &(usize, usize)) -> bool {
+        let grid = &self.grid;
+        let mut iter = coord.0;
+        loop {
+            match grid[(iter, coord.1)] {
+                '#' => { break; },
+                '|' => { return false; },
+                _ => { iter += 1; }
+            }
+        }
+        iter = coord.0;
+        loop {
+            match grid[(iter, coord.1)] {
+                '#' => { break; },
+                '|' => { return false; },
+                _ => { iter -= 1; }
+            }
+        }
+        true
+    }
+
+    pub fn step_fill(&mut self) -> Vec<(usize, usize)> {
+        let mut next_active = Vec::new();
+        let active = self.active_water.clone();
+        for coord in &active {
+            if coord.1 > self.ymax {
+                continue;
+            }
+
+            let ch = self.grid[(coord.0, coord.1)];
+            match ch {
+                '.' => {
+                    next_active.push((coord.0, coord.1 + 1));
+                    if coord.1 >= self.ymin {
+                        self.reached.insert(*coord);
+                        self.sand.insert(*coord);
+                    }
+                },
+                '#' | '~' | '|' => {
+                    if ch == '~'
+                        && !self.bounded(&(coord.0, coord.1)) {
+                            continue;
+                        }
+                    let mut y = coord.1;
+                    loop {
+                        y -= 1;
+                        self.grid[(coord.0, y)] = '~';
+                        self.reached.insert((coord.0, y));
+                        let prev = next_active.len();
+                        let result_left = &mut self.fill_row_at((coord.0-1, y), false);
+                        next_active.append(&mut (result_left.0));
+                        let result_right = &mut self.fill_row_at((coord.0+1, y), true);
+                        next_active.append(&mut (result_right.0));
+                        if prev != next_active.len() || result_left.1 || result_right.1 {
+                            // adjust sand
+                            for tmp in result_left.2..=result_right.2 {
+                                self.sand.insert((tmp, y));
+                                let mut iter = y + 1;
+                                while iter < self.grid.height() {
+                                    match self.grid[(tmp, iter)] {
+                                        '#' | '.' => { break; },
+                                        _ => { self.sand.remove(&(tmp, iter)); }
+                                    }
+                                    iter += 1;
+                                }
+                            }
+                            break;
+                        }
+                    }
+                },
+                _ => {}
+            }
+        }
+        next_active
+    }
+}
+
+// sand the water has passed through shows as 's', all water as '~'
+impl Canvas for Scan {
+    type Cell = char;
+
+    fn width(&self) -> usize {
+        self.grid.width()
+    }
+
+    fn height(&self) -> usize {
+        self.grid.height()
+    }
+
+    fn cell(&self, x: usize, y: usize) -> char {
+        if self.sand.contains(&(x, y)) {
+            return 's';
+        }
+        match self.grid[(x, y)] {
+            '~' | '|' => '~',
+            ch => ch
+        }
+    }
+}
+
+fn palette() -> Palette<char> {
+    Palette::new([0xCB, 0xCB, 0xCB])
+        .with('#', [0, 0, 0])
+        .with('~', [0x45, 0xE7, 0xFF])
+        .with('s', [0xF1, 0xE3, 0x46])
+}
+
+fn parse(input: &str) -> Result<Vec<(usize, usize, usize, usize)>> {
+    let vertical = Format::new("x={x}, y={y0}..{y1}");
+    let horizontal = Format::new("y={y}, x={x0}..{x1}");
+    let mut lines = Vec::new();
+    for (n, line) in input.lines().enumerate() {
+        let vein = first_of(&[&vertical, &horizontal], line).and_then(|(which, fields)| if which == 0 {
+            let x = fields.get("x")?;
+            Ok((x, x, fields.get("y0")?, fields.get("y1")?))
+        } else {
+            let y = fields.get("y")?;
+            Ok((fields.get("x0")?, fields.get("x1")?, y, y))
+        }).map_err(|e| e.error(Day17::DAY, n + 1, line))?;
+        // the scan is padded by a column either side
+        if vein.0 == 0 || vein.0 > vein.1 || vein.2 > vein.3 {
+            return Err(Error::parse(Day17::DAY, n + 1, line, "x above 0 and ranges from low to high"));
+        }
+        lines.push(vein);
+    }
+    if lines.is_empty() {
+        return Err(Error::parse(Day17::DAY, 1, "", vertical.pattern()));
+    }
+    Ok(lines)
+}
+
+// knobs.count veins of clay within knobs.extent of the spring across and below it, about
+// half of them as the sides and bottom of buckets that hold water
+pub fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+    let extent = knobs.extent.clamp(2, 499) as i64;
+    let mut veins = Vec::new();
+    while veins.len() < knobs.count {
+        let x0 = rng.range(500 - extent, 500 + extent - 2);
+        let y0 = rng.range(1, extent);
+        if rng.one_in(2) && knobs.count - veins.len() >= 3 {
+            let x1 = rng.range(x0 + 2, (x0 + extent / 4).max(x0 + 2));
+            let y1 = rng.range(y0 + 1, (y0 + extent / 4).max(y0 + 1));
+            veins.push(format!("x={}, y={}..{}", x0, y0, y1));
+            veins.push(format!("x={}, y={}..{}", x1, y0, y1));
+            veins.push(format!("y={}, x={}..{}", y1, x0, x1));
+        } else if rng.one_in(2) {
+            veins.push(format!("x={}, y={}..{}", x0, y0, rng.range(y0, y0 + extent / 4)));
+        } else {
+            veins.push(format!("y={}, x={}..{}", y0, x0, rng.range(x0, x0 + extent / 4)));
+        }
+    }
+    rng.shuffle(&mut veins);
+    veins.iter().map(|vein| format!("{}\n", vein)).collect()
+}
+
+pub fn solution(lines: &Vec<(usize, usize, usize, usize)>, mut recorder: Option<&mut Recorder<char>>) -> Result<(usize, usize)> {
+    let xmin = lines.iter().min_by(|a, b| a.0.cmp(&b.0)).unwrap().0 - 1;
+    let xmax = lines.iter().max_by(|a, b| a.1.cmp(&b.1)).unwrap().1 + 1;
+    let ymin = lines.iter().min_by(|a, b| a.2.cmp(&b.2)).unwrap().2;
+    let ymax = lines.iter().max_by(|a, b| a.3.cmp(&b.3)).unwrap().3;
+    debug!("xmin: {}, xmax: {}, ymin: {}, ymax: {}", xmin, xmax, ymin, ymax);
+
+    let mut scan = Scan::default();
+    scan.init(xmin, xmax, ymin, ymax);
+
+    let trans: Vec<(usize, usize, usize, usize)> = lines.iter().map(|a| (a.0 - xmin, a.1 - xmin, a.2, a.3)).collect();
+    for entry in &trans {
+        if entry.0 == entry.1 {
+            for row_idx in entry.2..=entry.3 {
+                scan.grid[(entry.0, row_idx)] = '#';
+            }
+        } else if entry.2 == entry.3 {
+            for col_idx in entry.0..=entry.1 {
+                scan.grid[(col_idx, entry.2)] = '#';
+            }
+        } else {
+            panic!("bogus entry {:?}", entry);
+        }
+    }
+
+    trace!("{}", scan.render());
+
+    while !scan.active_water.is_empty() {
+        if let Some(recorder) = recorder.as_mut() {
+            recorder.push(&scan)?;
+        }
+
+        scan.active_water = scan.step_fill();
+        trace!("{}", scan.render());
+    }
+    if let Some(recorder) = recorder {
+        recorder.push(&scan)?;
+    }
+
+    Ok((scan.reached.len(), scan.retained()))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_part1_ex0() {
+        use super::{parse, solution};
+        let input = "\
+x=495, y=2..7
+y=7, x=495..501
+x=501, y=3..7
+x=498, y=2..4
+x=506, y=1..2
+x=498, y=10..13
+x=504, y=10..13
+y=13, x=498..504
+";
+        assert_eq!(solution(&parse(input).unwrap(), None).unwrap(), (57, 29));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        use super::parse;
+        let err = parse("x=495, y=2..7\ny=7, x=495\n").err().unwrap();
+        assert_eq!(err.to_string(), "day 17 line 2 column 11: expected `..`, found ``");
+    }
+
+    #[test]
+    fn test_wide_floor() {
+        // the floor reaches further right than the vein with the largest x
+        use super::{parse, solution};
+        let input = "\
+x=501, y=1..2
+y=5, x=495..505
+";
+        assert_eq!(solution(&parse(input).unwrap(), None).unwrap(), (18, 0));
+    }
+}
@@ -0,0 +1,240 @@
+use std::collections::HashSet;
+
+use log::{debug, trace};
+
+use crate::cycle;
+use crate::diagnostics;
+use crate::error::{Error, Result};
+use crate::generate::{Knobs, Rng};
+use crate::grid::Grid;
+use crate::oracle::Oracle;
+use crate::recorder::{Palette, Recorder, Settings};
+use crate::solution::Solution;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u32 = 18;
+    type Input = Area;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Area> {
+        parse(input)
+    }
+
+    fn part1(input: &Area) -> Result<usize> {
+        Ok(part1(input, 10))
+    }
+
+    fn part2(input: &Area) -> Result<usize> {
+        part2(input, MINUTES).ok_or_else(|| Error::solve(Day18::DAY, "the area never repeats"))
+    }
+
+    const ANIMATED: bool = true;
+
+    fn animate(input: &Area, settings: &Settings) -> Result<usize> {
+        animate(input, settings)
+    }
+
+    const GENERATED: bool = true;
+
+    fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+        generate(knobs, rng)
+    }
+
+    fn oracles() -> Vec<Oracle<Area>> {
+        vec![Oracle::new("cycle", |input| {
+            part2(input, 1000).map(|x| x.to_string()).ok_or_else(|| Error::solve(Day18::DAY, "the area never repeats"))
+        }, |input| Ok(part1(input, 1000).to_string()))]
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Area {
+    grid: Grid<char>,
+    next: Grid<char>
+}
+
+impl Area {
+    pub fn sample(&self, x: usize, y: usize, result: &mut Vec<char>) {
+        result.clear();
+        for pos in self.grid.neighbours8(x, y) {
+            result.push(self.grid[pos]);
+        }
+    }
+
+    pub fn count_cells(src: &Vec<char>, key: char) -> usize {
+        src.iter().fold(0usize,|sum, x| if *x == key { sum + 1 } else { sum })
+    }
+
+    pub fn count_all_cells(&self, key: char) -> usize {
+        self.grid.count(|x| *x == key)
+    }
+
+    pub fn tick(&mut self) {
+        let mut samples= Vec::with_capacity(8);
+        self.next = self.grid.clone();
+        for ((x, y), ch) in self.grid.iter() {
+            self.sample(x, y, &mut samples);
+            self.next[(x, y)] = match ch {
+                '.' => {
+                    if Area::count_cells(&samples, '|') >= 3 { '|' } else { *ch }
+                },
+                '|' => {
+                    if Area::count_cells(&samples, '#') >= 3 { '#' } else { *ch }
+                },
+                '#' => {
+                    if Area::count_cells(&samples, '#') < 1 || Area::count_cells(&samples, '|') < 1 { '.' } else { *ch }
+                },
+                _ => panic!("invalid cell {}, {} {}", x, y, ch)
+            };
+        }
+    }
+
+    pub fn update_from_next(&mut self) {
+        std::mem::swap(&mut self.grid, &mut self.next);
+    }
+
+    pub fn to_string(&self) -> String {
+        self.grid.values().collect()
+    }
+}
+
+fn parse(input: &str) -> Result<Area> {
+    let grid = Grid::parse_with(Day18::DAY, input, "row of . | #", |_, ch| if ".|#".contains(ch) { Some(ch) } else { None })?;
+    Ok(Area { next: grid.clone(), grid })
+}
+
+// a knobs.extent square of open ground with up to knobs.count acres of trees and as many
+// lumberyards scattered over it
+pub fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+    let side = knobs.extent.max(1);
+    let mut acres = vec!['.'; side * side];
+    for _ in 0..knobs.count {
+        acres[rng.below(side * side)] = '|';
+        acres[rng.below(side * side)] = '#';
+    }
+    acres.chunks(side).map(|row| row.iter().collect::<String>() + "\n").collect()
+}
+
+pub fn part1(input: &Area, iters: usize) -> usize {
+    let mut area = input.clone();
+
+    trace!("Initial state:\n{}", area.grid);
+
+    for x in 1..=iters {
+        area.tick();
+        area.update_from_next();
+        trace!("After {} minute{}:\n{}", x, if x == 1 { "" } else { "s" }, area.grid);
+    }
+
+    area.count_all_cells('|') * area.count_all_cells('#')
+}
+
+const MINUTES: usize = 1000000000;
+
+fn next_minute(area: &Area) -> Area {
+    let mut next = area.clone();
+    next.tick();
+    next.update_from_next();
+    next
+}
+
+// the area settles into a cycle long before a billion minutes
+pub fn part2(input: &Area, minutes: usize) -> Option<usize> {
+    let key = |area: &Area| area.grid.clone();
+    let cycle = cycle::brent(input, next_minute, key, 10000)?;
+    debug!("cycle start: {} length: {}", cycle.start, cycle.length);
+    diagnostics::record("cycle_start", cycle.start);
+    diagnostics::record("cycle_length", cycle.length);
+    let area = cycle.state_at(input, next_minute, minutes);
+    Some(area.count_all_cells('|') * area.count_all_cells('#'))
+}
+
+// every minute up to the first state seen before, when the area starts cycling
+pub fn animate(input: &Area, settings: &Settings) -> Result<usize> {
+    let palette = Palette::new([0xE8, 0xD9, 0xA8]).with('|', [0x2E, 0x8B, 0x3A]).with('#', [0x8B, 0x5A, 0x2B]);
+    let mut recorder = Recorder::new(settings, palette);
+    let mut area = input.clone();
+    let mut seen = HashSet::new();
+    while seen.insert(area.to_string()) {
+        recorder.push(&area.grid)?;
+        area.tick();
+        area.update_from_next();
+    }
+    recorder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_part1_ex0() {
+        use super::{parse, part1};
+        let input = "\
+.#.#...|#.
+.....#|##|
+.|..|...#.
+..|#.....#
+#.#|||#|#|
+...#.||...
+.|....|...
+||...#|.#|
+|.||||..|.
+...#.|..|.
+";
+        assert_eq!(part1(&parse(input).unwrap(), 10), 1147);
+    }
+
+    #[test]
+    fn test_part2_ex0() {
+        use super::{parse, part1, part2};
+        let input = "\
+||#.#|..#.
+||.|##||.#
+|.#..#.||.
+#.|#|.####
+....|....|
+||..##||..
+#.#|..|#|#
+.#..##|#.#
+|..##..|..
+#######.#|
+";
+        // settles into a 28 minute cycle after 22 minutes
+        let area = parse(input).unwrap();
+        assert_eq!(part2(&area, 500), Some(part1(&area, 500)));
+        assert_eq!(part2(&area, 501), Some(part1(&area, 501)));
+    }
+
+    #[test]
+    #[ignore = "needs the original author's puzzle input in inputs/ or AOC_INPUT_DIR"]
+    fn test_part1_input() {
+        use crate::solution::load_input;
+        use super::Day18;
+        use super::part1;
+        assert_eq!(part1(&load_input::<Day18>().unwrap(), 10), 483840);
+    }
+
+    #[test]
+    fn test_animate() {
+        use super::{animate, parse};
+        use crate::recorder::{Output, Settings};
+        let dir = std::env::temp_dir().join(format!("aoc-day18-{}", std::process::id()));
+        let area = parse(".|.\n|#|\n.|.\n").unwrap();
+        // the yard goes bare, trees fill the centre then the corners, then nothing changes
+        let frames = animate(&area, &Settings::new(Output::Png(dir.clone()))).unwrap();
+        assert_eq!(frames, 4);
+        assert!(dir.join("frame_00003.png").exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_oracles() {
+        use crate::generate::Knobs;
+        use crate::oracle::{disagreements, run};
+        use super::Day18;
+        let cases = run::<Day18>(&Knobs { count: 30, extent: 8 }, 10).unwrap();
+        assert!(disagreements(&cases).is_empty(), "{:?}", disagreements(&cases));
+    }
+}
@@ -0,0 +1,170 @@
+use crate::elfcode;
+use crate::error::Result;
+use crate::generate::{Knobs, Rng};
+use crate::oracle::Oracle;
+use crate::solution::Solution;
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u32 = 19;
+    type Input = Device;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Device> {
+        load(input)
+    }
+
+    fn part1(input: &Device) -> Result<u64> {
+        part1(input)
+    }
+
+    fn part2(input: &Device) -> Result<u64> {
+        part2(input)
+    }
+
+    const GENERATED: bool = true;
+
+    fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+        generate(knobs, rng)
+    }
+
+    fn oracles() -> Vec<Oracle<Device>> {
+        vec![Oracle::new("factors", |input| Ok(part2(input)?.to_string()), |input| Ok(run(input, 1).to_string()))]
+    }
+}
+
+const NUM_REGS: usize = 6;
+
+pub type Device = elfcode::Device<NUM_REGS>;
+
+fn load(input: &str) -> Result<Device> {
+    Device::load(Day19::DAY, input)
+}
+
+// the same divisor summing program as the puzzle's, with the registers shuffled. Part 1
+// sums the divisors of a number up to knobs.count and part 2 adds up to
+// knobs.count * knobs.extent to it, small enough to run the program through.
+pub fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+    let mut regs = [1, 2, 3, 4, 5];
+    rng.shuffle(&mut regs);
+    let [p, i, j, t, n] = regs;
+    let target = rng.range(1, knobs.count.max(1) as i64);
+    let extra = rng.range(0, (knobs.count * knobs.extent) as i64);
+    // the operands nothing reads
+    let mut any = || rng.below(NUM_REGS);
+    let program = [
+        format!("addi {p} 16 {p}"),
+        format!("seti 1 {} {i}", any()),
+        format!("seti 1 {} {j}", any()),
+        format!("mulr {i} {j} {t}"),
+        format!("eqrr {t} {n} {t}"),
+        format!("addr {t} {p} {p}"),
+        format!("addi {p} 1 {p}"),
+        format!("addr {i} 0 0"),
+        format!("addi {j} 1 {j}"),
+        format!("gtrr {j} {n} {t}"),
+        format!("addr {p} {t} {p}"),
+        format!("seti 2 {} {p}", any()),
+        format!("addi {i} 1 {i}"),
+        format!("gtrr {i} {n} {t}"),
+        format!("addr {t} {p} {p}"),
+        format!("seti 1 {} {p}", any()),
+        format!("mulr {p} {p} {p}"),
+        format!("seti {target} {} {n}", any()),
+        format!("addr {p} 0 {p}"),
+        format!("seti 0 {} {p}", any()),
+        format!("seti {extra} {} {t}", any()),
+        format!("addr {n} {t} {n}"),
+        format!("seti 0 {} 0", any()),
+        format!("seti 0 {} {p}", any()),
+    ];
+    let mut result = format!("#ip {}\n", p);
+    for line in program {
+        result.push_str(&line);
+        result.push('\n');
+    }
+    result
+}
+
+// the program as it is, the reference for part 2 on inputs small enough to wait for
+fn run(input: &Device, reg0: u64) -> u64 {
+    let mut device = input.clone();
+    device.registers[0] = reg0;
+    while device.execute_ip() {}
+    device.registers[0]
+}
+
+// the program as it is, with the divisor sum it spends its time in run natively (see
+// elfcode::optimize)
+fn solution(input: &Device, reg0: u64) -> Result<u64> {
+    let mut device = input.clone();
+    device.optimize();
+    device.registers[0] = reg0;
+    device.run();
+    Ok(device.registers[0])
+}
+
+pub fn part1(input: &Device) -> Result<u64> {
+    solution(input, 0)
+}
+
+// the sum of the divisors of 10551355, O(n²) instructions without the shortcut
+pub fn part2(input: &Device) -> Result<u64> {
+    solution(input, 1)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_part1_ex() {
+        use super::{load, part1};
+        let input = "\
+#ip 0
+seti 5 0 1
+seti 6 0 2
+addi 0 1 0
+addr 1 2 3
+setr 1 0 0
+seti 8 0 4
+seti 9 0 5
+";
+        assert_eq!(part1(&load(input).unwrap()).unwrap(), 6);
+    }
+
+    #[test]
+    fn test_oracles() {
+        use crate::generate::Knobs;
+        use crate::oracle::{disagreements, run};
+        use super::Day19;
+        let cases = run::<Day19>(&Knobs { count: 20, extent: 3 }, 10).unwrap();
+        assert!(disagreements(&cases).is_empty(), "{:?}", disagreements(&cases));
+    }
+
+    #[test]
+    fn test_part2_large() {
+        use super::{load, part2};
+        // the puzzle's loop on its number, which the device would take forever over
+        let input = "#ip 2\nseti 16 0 2\nseti 1 0 1\nseti 1 0 3\nmulr 1 3 4\neqrr 4 5 4\naddr 4 2 2\naddi 2 1 2\naddr 1 0 0\naddi 3 1 3\ngtrr 3 5 4\naddr 2 4 2\nseti 2 0 2\naddi 1 1 1\ngtrr 1 5 4\naddr 4 2 2\nseti 1 0 2\nmulr 2 2 2\nseti 10551355 0 5\nseti 0 0 0\nseti 0 0 2\n";
+        assert_eq!(part2(&load(input).unwrap()).unwrap(), 12690000);
+    }
+
+    #[test]
+    #[ignore = "needs the original author's puzzle input in inputs/ or AOC_INPUT_DIR"]
+    fn test_part1_input() {
+        use crate::solution::load_input;
+        use super::Day19;
+        use super::solution;
+        assert_eq!(solution(&load_input::<Day19>().unwrap(), 0).unwrap(), 1152);
+    }
+
+    #[test]
+    #[ignore = "needs the original author's puzzle input in inputs/ or AOC_INPUT_DIR"]
+    fn test_part2_input() {
+        use crate::solution::load_input;
+        use super::Day19;
+        use super::part2;
+        assert_eq!(part2(&load_input::<Day19>().unwrap()).unwrap(), 12690000);
+    }
+}
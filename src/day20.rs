@@ -0,0 +1,293 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::bench::Variant;
+use crate::error::{Error, Result};
+use crate::generate::{Knobs, Rng};
+use crate::oracle::Oracle;
+use crate::solution::Solution;
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u32 = 20;
+    type Input = String;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<String> {
+        let regex = input.trim();
+        let body = regex.strip_prefix('^').and_then(|r| r.strip_suffix('$'));
+        if !body.is_some_and(|b| b.chars().all(|ch| "NEWS|()".contains(ch))) {
+            return Err(Error::parse_input(Day20::DAY, input, "^NEWS|()$"));
+        }
+        Ok(regex.to_string())
+    }
+
+    fn part1(input: &String) -> Result<u32> {
+        Ok(solution(input).0)
+    }
+
+    fn part2(input: &String) -> Result<u32> {
+        Ok(solution(input).1)
+    }
+
+    const GENERATED: bool = true;
+
+    fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+        generate(knobs, rng)
+    }
+
+    fn variants() -> Vec<Variant<String>> {
+        vec![
+            Variant::new(1, "stack", |input| Ok(stack_walk(input).0.to_string())),
+            Variant::new(2, "stack", |input| Ok(stack_walk(input).1.to_string())),
+        ]
+    }
+
+    fn oracles() -> Vec<Oracle<String>> {
+        vec![
+            Oracle::new("furthest", |input| Ok(solution(input).0.to_string()), |input| Ok(brute_force(input).0.to_string())),
+            Oracle::new("1000 doors", |input| Ok(solution(input).1.to_string()), |input| Ok(brute_force(input).1.to_string())),
+        ]
+    }
+}
+
+// a route of about knobs.count directions with branches nested up to knobs.extent deep,
+// some of them detours with an empty option like (NEWS|)
+pub fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+    let mut result = String::from("^");
+    let mut budget = knobs.count;
+    route(&mut result, &mut budget, knobs.extent, true, rng);
+    result.push_str("$\n");
+    result
+}
+
+fn route(result: &mut String, budget: &mut usize, depth: usize, top: bool, rng: &mut Rng) {
+    // branches take at least one step and stop at random after that, the whole route
+    // only once the budget is spent
+    let mut first = true;
+    while *budget > 0 && (top || first || !rng.one_in(6)) {
+        first = false;
+        if depth > 0 && rng.one_in(5) {
+            result.push('(');
+            for option in 0..rng.range(2, 3) {
+                if option > 0 {
+                    result.push('|');
+                }
+                route(result, budget, depth - 1, false, rng);
+            }
+            if rng.one_in(3) {
+                result.push('|');
+            }
+            result.push(')');
+        } else {
+            result.push(*rng.choose(&['N', 'E', 'S', 'W']));
+            *budget -= 1;
+        }
+    }
+}
+
+// the first version, one room at a time with a stack for the branches. Only right when
+// whatever follows a branch starts back where the branch did and no room is reached a
+// shorter way after the first time, which holds for the puzzle input where every branch
+// is a detour or a dead end and the rooms form a tree.
+pub fn stack_walk(input: &str) -> (u32, u32) {
+    let mut positions = Vec::new();
+    let mut coord = (0, 0);
+
+    let mut distance_field = HashMap::new();
+    distance_field.insert(coord, 0u32);
+
+    let mut delta_coord = HashMap::new();
+    delta_coord.insert('N', (0, -1));
+    delta_coord.insert('S', (0, 1));
+    delta_coord.insert('E', (-1, 0));
+    delta_coord.insert('W', (1, 0));
+
+    for ch in input.chars() {
+        match ch {
+            '(' => {
+                positions.push(coord);
+            },
+            ')' => {
+                coord = positions.pop().unwrap();
+            },
+            '|' => {
+                coord = *positions.last().unwrap();
+            },
+            'N' | 'E' | 'S' | 'W' => {
+                let mut new_pos = *delta_coord.get(&ch).unwrap();
+                new_pos.0 += coord.0;
+                new_pos.1 += coord.1;
+                let curr_dist = *distance_field.get(&coord).unwrap();
+                if distance_field.contains_key(&new_pos) {
+                    let dist = std::cmp::min(*distance_field.get(&new_pos).unwrap(), curr_dist + 1);
+                    distance_field.insert( new_pos, dist);
+                } else {
+                    distance_field.insert(new_pos, curr_dist + 1);
+                }
+                coord = new_pos;
+            },
+            _ => {}
+        }
+    }
+
+    (*distance_field.values().max().unwrap(), distance_field.values().fold(0, |sum, x| if *x >= 1000 { sum + 1 } else { sum }))
+}
+
+type Room = (i32, i32);
+
+// follows every option of every branch from every room the route can be in, adding the
+// doors passed through. Returns the rooms the options end in.
+fn expand(regex: &[u8], at: &mut usize, starts: &HashSet<Room>, doors: &mut HashMap<Room, HashSet<Room>>) -> HashSet<Room> {
+    let mut ends = HashSet::new();
+    let mut current = starts.clone();
+    while *at < regex.len() {
+        let ch = regex[*at];
+        *at += 1;
+        let delta = match ch {
+            b'N' => (0, -1),
+            b'S' => (0, 1),
+            b'E' => (1, 0),
+            b'W' => (-1, 0),
+            b'(' => {
+                current = expand(regex, at, &current, doors);
+                continue;
+            },
+            b'|' => {
+                ends.extend(current.drain());
+                current = starts.clone();
+                continue;
+            },
+            b')' | b'$' => break,
+            _ => continue
+        };
+        current = current.iter().map(|room| {
+            let next = (room.0 + delta.0, room.1 + delta.1);
+            doors.entry(*room).or_default().insert(next);
+            doors.entry(next).or_default().insert(*room);
+            next
+        }).collect();
+    }
+    ends.extend(current);
+    ends
+}
+
+fn distances(doors: &HashMap<Room, HashSet<Room>>) -> (u32, u32) {
+    let mut distance = HashMap::from([((0, 0), 0u32)]);
+    let mut queue = VecDeque::from([(0, 0)]);
+    while let Some(room) = queue.pop_front() {
+        let next_distance = distance[&room] + 1;
+        for next in doors.get(&room).into_iter().flatten() {
+            if !distance.contains_key(next) {
+                distance.insert(*next, next_distance);
+                queue.push_back(*next);
+            }
+        }
+    }
+    (*distance.values().max().unwrap(), distance.values().filter(|x| **x >= 1000).count() as u32)
+}
+
+// the whole map from the regex then a breadth first search over the doors
+pub fn solution(input: &str) -> (u32, u32) {
+    let mut doors = HashMap::new();
+    expand(input.as_bytes(), &mut 0, &HashSet::from([(0, 0)]), &mut doors);
+    distances(&doors)
+}
+
+// every route the regex matches, written out in full
+fn routes(regex: &[u8], at: &mut usize) -> Vec<String> {
+    let mut options = Vec::new();
+    let mut current = vec![String::new()];
+    while *at < regex.len() {
+        let ch = regex[*at];
+        *at += 1;
+        match ch {
+            b'(' => {
+                let group = routes(regex, at);
+                current = current.iter().flat_map(|a| group.iter().map(move |b| format!("{}{}", a, b))).collect();
+            },
+            b'|' => {
+                options.append(&mut current);
+                current = vec![String::new()];
+            },
+            b')' | b'$' => break,
+            b'N' | b'E' | b'S' | b'W' => current.iter_mut().for_each(|x| x.push(ch as char)),
+            _ => {}
+        }
+    }
+    options.append(&mut current);
+    options
+}
+
+// the reference, walks each route the regex matches one by one
+fn brute_force(input: &str) -> (u32, u32) {
+    let mut doors: HashMap<Room, HashSet<Room>> = HashMap::new();
+    for route in routes(input.as_bytes(), &mut 0) {
+        let mut room = (0, 0);
+        for ch in route.chars() {
+            let next = match ch {
+                'N' => (room.0, room.1 - 1),
+                'S' => (room.0, room.1 + 1),
+                'E' => (room.0 + 1, room.1),
+                _ => (room.0 - 1, room.1)
+            };
+            doors.entry(room).or_default().insert(next);
+            doors.entry(next).or_default().insert(room);
+            room = next;
+        }
+    }
+    distances(&doors)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_part1_ex0() {
+        use super::solution;
+        assert_eq!(solution("^WNE$").0, 3);
+    }
+
+    #[test]
+    fn test_part1_ex1() {
+        use super::solution;
+        assert_eq!(solution("^ENWWW(NEEE|SSE(EE|N))$").0, 10);
+    }
+
+    #[test]
+    fn test_part1_ex2() {
+        use super::solution;
+        assert_eq!(solution("^ENNWSWW(NEWS|)SSSEEN(WNSE|)EE(SWEN|)NNN$").0, 18);
+    }
+
+    #[test]
+    fn test_part1_ex3() {
+        use super::solution;
+        assert_eq!(solution("^ESSWWN(E|NNENN(EESS(WNSE|)SSS|WWWSSSSE(SW|NNNE)))$").0, 23);
+    }
+
+    #[test]
+    fn test_part1_ex4() {
+        use super::solution;
+        assert_eq!(solution("^WSSEESWWWNW(S|NENNEEEENN(ESSSSW(NWSW|SSEN)|WSWWN(E|WWS(E|SS))))$").0, 31);
+    }
+
+    #[test]
+    fn test_stack_walk() {
+        use super::{solution, stack_walk};
+        // fine with detours and dead ends, but after (N|E) the route goes on from the
+        // room above or the one to the right, not from where the branch started
+        assert_eq!(stack_walk("^EE(NEWS|)SS$"), solution("^EE(NEWS|)SS$"));
+        assert_eq!(solution("^(N|E)EEE$").0, 4);
+        assert_eq!(stack_walk("^(N|E)EEE$").0, 3);
+    }
+
+    #[test]
+    fn test_oracles() {
+        use crate::generate::Knobs;
+        use crate::oracle::{disagreements, run};
+        use super::Day20;
+        let cases = run::<Day20>(&Knobs { count: 20, extent: 4 }, 30).unwrap();
+        assert!(disagreements(&cases).is_empty(), "{:?}", disagreements(&cases));
+    }
+}
@@ -0,0 +1,169 @@
+use log::debug;
+
+use crate::elfcode::{self, compiled::Compiled};
+use crate::cycle;
+use crate::diagnostics;
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u32 = 21;
+    type Input = Device;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Device> {
+        load(input)
+    }
+
+    fn part1(input: &Device) -> Result<u64> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Device) -> Result<u64> {
+        part2(input).ok_or_else(|| Error::solve(Day21::DAY, "the checked values never repeat"))
+    }
+}
+
+const NUM_REGS: usize = 6;
+
+pub type Device = elfcode::Device<NUM_REGS>;
+
+fn load(input: &str) -> Result<Device> {
+    Device::load(Day21::DAY, input)
+}
+
+// ip=28 is where register 0 is tested against register 4, eqrr 4 0 2
+const CHECK_IP: u32 = 28;
+
+// runs on to the next time register 0 is checked, or to the end
+fn next_check(program: &Compiled<NUM_REGS>, device: &Device) -> Device {
+    let mut device = device.clone();
+    program.run(&mut device, Some(CHECK_IP), u64::MAX);
+    device
+}
+
+// the program with the divisions by 256 between checks run natively
+fn compile(input: &Device) -> Compiled<NUM_REGS> {
+    let mut device = input.clone();
+    device.optimize();
+    device.compile()
+}
+
+// the value register 0 has to match to halt at the first check
+pub fn part1(input: &Device) -> u64 {
+    next_check(&compile(input), input).registers[4]
+}
+
+// the values checked against cycle, the last new one before the repeat halts after the
+// most instructions
+pub fn part2(input: &Device) -> Option<u64> {
+    let program = compile(input);
+    let step = |device: &Device| next_check(&program, device);
+    let first = step(input);
+    let cycle = cycle::floyd(&first, step, |device| device.registers[4], 100000)?;
+    debug!("cycle start: {} length: {}", cycle.start, cycle.length);
+    diagnostics::record("cycle_start", cycle.start);
+    diagnostics::record("cycle_length", cycle.length);
+    Some(cycle.state_at(&first, step, cycle.start + cycle.length - 1).registers[4])
+}
+
+#[cfg(test)]
+mod tests {
+    // r4 steps through (5 * r4 + 3) & 15 from 1, checking it at ip 28
+    fn program() -> String {
+        let mut program = String::from("#ip 5\nseti 1 0 4\n");
+        program.push_str(&"addi 1 1 1\n".repeat(24));
+        program.push_str("muli 4 5 4\naddi 4 3 4\nbani 4 15 4\neqrr 4 0 2\naddr 2 5 5\nseti 24 0 5\n");
+        program
+    }
+
+    #[test]
+    fn test_part1_ex() {
+        use super::{load, part1};
+        assert_eq!(part1(&load(&program()).unwrap()), 8);
+    }
+
+    #[test]
+    fn test_part2_ex() {
+        use super::{load, part2};
+        // 8 11 10 5 12 15 14 9 0 3 2 13 4 7 6 1 then 8 again, a register 0 the program
+        // can't match keeps it going like the puzzle's 0 does
+        let mut device = load(&program()).unwrap();
+        device.registers[0] = 16;
+        assert_eq!(part2(&device), Some(1));
+    }
+
+    #[test]
+    fn test_divide() {
+        use super::{load, part1, part2};
+        // the puzzle's program, with its divisions by 256 counted out a step at a time
+        // unless they run natively
+        let program = "\
+#ip 5
+seti 123 0 4
+bani 4 456 4
+eqri 4 72 4
+addr 4 5 5
+seti 0 0 5
+seti 0 9 4
+bori 4 65536 3
+seti 1505483 6 4
+bani 3 255 2
+addr 4 2 4
+bani 4 16777215 4
+muli 4 65899 4
+bani 4 16777215 4
+gtir 256 3 2
+addr 2 5 5
+addi 5 1 5
+seti 27 1 5
+seti 0 5 2
+addi 2 1 1
+muli 1 256 1
+gtrr 1 3 1
+addr 1 5 5
+addi 5 1 5
+seti 25 2 5
+addi 2 1 2
+seti 17 8 5
+setr 2 2 3
+seti 7 9 5
+eqrr 4 0 2
+addr 2 5 5
+seti 5 3 5
+";
+        // what the program computes between checks
+        let next = |r4: u64| {
+            let mut r3 = r4 | 65536;
+            let mut r4 = 1505483;
+            loop {
+                r4 = (((r4 + (r3 & 255)) & 16777215) * 65899) & 16777215;
+                if r3 < 256 {
+                    return r4;
+                }
+                r3 /= 256;
+            }
+        };
+        let mut seen = Vec::new();
+        let mut value = next(0);
+        while !seen.contains(&value) {
+            seen.push(value);
+            value = next(value);
+        }
+        let device = load(program).unwrap();
+        assert_eq!(part1(&device), seen[0]);
+        assert_eq!(part2(&device), seen.last().copied());
+    }
+
+    #[test]
+    #[ignore = "needs the original author's puzzle input in inputs/ or AOC_INPUT_DIR"]
+    fn test_part1_input() {
+        use crate::solution::load_input;
+        use super::Day21;
+        use super::part1;
+        assert_eq!(part1(&load_input::<Day21>().unwrap()), 2985446);
+    }
+}
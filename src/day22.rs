@@ -1,7 +1,31 @@
-#![feature(test)]
+use pathfinding::prelude::astar;
 
-extern crate pathfinding;
-use pathfinding::prelude::{absdiff, astar, Matrix};
+use crate::error::Result;
+use crate::format::Format;
+use crate::point::Point;
+use crate::solution::Solution;
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u32 = 22;
+    // cave depth and target coordinate
+    type Input = (usize, (usize, usize));
+    type Answer1 = usize;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_input(input)
+    }
+
+    fn part1(&(depth, target): &Self::Input) -> Result<usize> {
+        Ok(part1(depth, target))
+    }
+
+    fn part2(&(depth, target): &Self::Input) -> Result<u32> {
+        Ok(part2(depth, target, 1024))
+    }
+}
 
 #[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 enum Tool {
@@ -17,29 +41,29 @@ const SWITCH_TIME: u32 = 7;
 
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 struct Region {
-    coord: (usize, usize),
+    coord: Point<2>,
     tool: Tool
 }
 
 impl Region {
-    fn distance(&self, other: &Region) -> u32{
-        (absdiff(self.coord.0 as u32, other.coord.0 as u32) + absdiff(self.coord.1 as u32, other.coord.1 as u32))
+    fn distance(&self, other: &Region) -> u32 {
+        self.coord.manhattan(&other.coord) as u32
     }
 
-    fn add_at(&self, coord: (usize, usize), curr_region: usize, regions: &Vec<Vec<usize>>) -> Vec<(Region, u32)> {
+    fn add_at(&self, coord: Point<2>, curr_region: usize, regions: &Vec<Vec<usize>>) -> Vec<(Region, u32)> {
         let mut result = Vec::new();
-        let next_region = regions[coord.1][coord.0];
+        let next_region = regions[coord[1] as usize][coord[0] as usize];
         match curr_region {
             ROCKY => {
                 assert_ne!(self.tool, Tool::Neither);
                 match next_region {
                     ROCKY => {
                         if self.tool == Tool::Torch {
-                            result.push((Region {coord: coord, tool: Tool::Torch}, 1));
+                            result.push((Region {coord, tool: Tool::Torch}, 1));
                             result.push((Region {coord: self.coord, tool: Tool::Climbing}, SWITCH_TIME));
                         } else {
                             assert_eq!(self.tool, Tool::Climbing);
-                            result.push((Region {coord: coord, tool: Tool::Climbing}, 1));
+                            result.push((Region {coord, tool: Tool::Climbing}, 1));
                             result.push((Region {coord: self.coord, tool: Tool::Torch}, SWITCH_TIME));
                         }
                     },
@@ -48,7 +72,7 @@ impl Region {
                             result.push((Region {coord: self.coord, tool: Tool::Climbing}, SWITCH_TIME));
                         } else {
                             assert_eq!(self.tool, Tool::Climbing);
-                            result.push((Region {coord: coord, tool: Tool::Climbing}, 1));
+                            result.push((Region {coord, tool: Tool::Climbing}, 1));
                         }
                     },
                     NARROW => {
@@ -56,7 +80,7 @@ impl Region {
                             result.push((Region {coord: self.coord, tool: Tool::Torch}, SWITCH_TIME));
                         } else {
                             assert_eq!(self.tool, Tool::Torch);
-                            result.push((Region {coord: coord, tool: Tool::Torch}, 1));
+                            result.push((Region {coord, tool: Tool::Torch}, 1));
                         }
                     },
                     _ => panic!("")
@@ -70,16 +94,16 @@ impl Region {
                             result.push((Region {coord: self.coord, tool: Tool::Climbing}, SWITCH_TIME));
                         } else {
                             assert_eq!(self.tool, Tool::Climbing);
-                            result.push((Region {coord: coord, tool: Tool::Climbing}, 1));
+                            result.push((Region {coord, tool: Tool::Climbing}, 1));
                         }
                     },
                     WET => {
                         if self.tool == Tool::Neither {
-                            result.push((Region {coord: coord, tool: Tool::Neither}, 1));
+                            result.push((Region {coord, tool: Tool::Neither}, 1));
                             result.push((Region {coord: self.coord, tool: Tool::Climbing}, SWITCH_TIME));
                         } else {
                             assert_eq!(self.tool, Tool::Climbing);
-                            result.push((Region {coord: coord, tool: Tool::Climbing}, 1));
+                            result.push((Region {coord, tool: Tool::Climbing}, 1));
                             result.push((Region {coord: self.coord, tool: Tool::Neither}, SWITCH_TIME));
                         }
                     },
@@ -88,7 +112,7 @@ impl Region {
                             result.push((Region {coord: self.coord, tool: Tool::Neither}, SWITCH_TIME));
                         } else {
                             assert_eq!(self.tool, Tool::Neither);
-                            result.push((Region {coord: coord, tool: Tool::Neither}, 1));
+                            result.push((Region {coord, tool: Tool::Neither}, 1));
                         }
                     },
                     _ => panic!("")
@@ -99,7 +123,7 @@ impl Region {
                 match next_region {
                     ROCKY => {
                         if self.tool == Tool::Torch {
-                            result.push((Region {coord: coord, tool: Tool::Torch}, 1));
+                            result.push((Region {coord, tool: Tool::Torch}, 1));
                         } else {
                             assert_eq!(self.tool, Tool::Neither);
                             result.push((Region {coord: self.coord, tool: Tool::Torch}, SWITCH_TIME));
@@ -110,16 +134,16 @@ impl Region {
                             result.push((Region {coord: self.coord, tool: Tool::Neither}, SWITCH_TIME));
                         } else {
                             assert_eq!(self.tool, Tool::Neither);
-                            result.push((Region {coord: coord, tool: Tool::Neither}, 1));
+                            result.push((Region {coord, tool: Tool::Neither}, 1));
                         }
                     },
                     NARROW => {
                         if self.tool == Tool::Torch {
-                            result.push((Region {coord: coord, tool: Tool::Torch}, 1));
+                            result.push((Region {coord, tool: Tool::Torch}, 1));
                             result.push((Region {coord: self.coord, tool: Tool::Neither}, SWITCH_TIME));
                         } else {
                             assert_eq!(self.tool, Tool::Neither);
-                            result.push((Region {coord: coord, tool: Tool::Neither}, 1));
+                            result.push((Region {coord, tool: Tool::Neither}, 1));
                             result.push((Region {coord: self.coord, tool: Tool::Torch}, SWITCH_TIME));
                         }
                     },
@@ -133,21 +157,22 @@ impl Region {
 
     pub fn successors(&self, regions: &Vec<Vec<usize>>) -> Vec<(Region, u32)> {
         let mut result= Vec::new();
-        let curr_region = regions[self.coord.1][self.coord.0];
-        if self.coord.0 > 0 { result.append(&mut self.add_at((self.coord.0-1, self.coord.1), curr_region, regions)); }
-        if self.coord.1 > 0 { result.append(&mut self.add_at((self.coord.0, self.coord.1-1), curr_region, regions)); }
-        result.append(&mut self.add_at((self.coord.0+1, self.coord.1), curr_region, regions));
-        result.append(&mut self.add_at((self.coord.0, self.coord.1+1), curr_region, regions));
+        let curr_region = regions[self.coord[1] as usize][self.coord[0] as usize];
+        for next in self.coord.neighbours() {
+            if next[0] >= 0 && next[1] >= 0 {
+                result.append(&mut self.add_at(next, curr_region, regions));
+            }
+        }
         result
     }
 }
 
 fn create(depth: usize, target: (usize, usize), dim: (usize, usize)) -> Vec<Vec<usize>> {
-    let mut erosion_levels = Vec::with_capacity(dim.1 as usize);
-    let mut regions = Vec::with_capacity(dim.1 as usize);
+    let mut erosion_levels = Vec::with_capacity(dim.1);
+    let mut regions = Vec::with_capacity(dim.1);
 
-    let mut er_row = Vec::with_capacity(dim.0 as usize);
-    let mut region_row = Vec::with_capacity(dim.0 as usize);
+    let mut er_row = Vec::with_capacity(dim.0);
+    let mut region_row = Vec::with_capacity(dim.0);
 
     // y = 0
     for x in 0..=dim.0 {
@@ -181,15 +206,24 @@ fn create(depth: usize, target: (usize, usize), dim: (usize, usize)) -> Vec<Vec<
     regions
 }
 
-fn part1(depth: usize, target: (usize, usize)) -> usize {
+fn read_input(input: &str) -> Result<(usize, (usize, usize))> {
+    let mut lines = input.lines();
+    let line = lines.next().unwrap_or("");
+    let (depth,) = Format::new("depth: {depth}").parse(Day22::DAY, 1, line)?;
+    let line = lines.next().unwrap_or("");
+    let target = Format::new("target: {x},{y}").parse(Day22::DAY, 2, line)?;
+    Ok((depth, target))
+}
+
+pub fn part1(depth: usize, target: (usize, usize)) -> usize {
     let regions = create(depth, target, target);
-    regions.iter().fold(0usize, |sum, x| sum + x.iter().fold(0usize, |sum, y| sum + y))
+    regions.iter().fold(0usize, |sum, x| sum + x.iter().sum::<usize>())
 }
 
-fn part2(depth: usize, target: (usize, usize), expand: usize) -> u32 {
+pub fn part2(depth: usize, target: (usize, usize), expand: usize) -> u32 {
     let regions = create(depth, target, (target.0 + expand, target.1 + expand));
-    let initial = Region { coord: (0, 0), tool: Tool::Torch };
-    let target = Region { coord: target, tool: Tool::Torch };
+    let initial = Region { coord: Point::origin(), tool: Tool::Torch };
+    let target = Region { coord: Point::new([target.0 as i64, target.1 as i64]), tool: Tool::Torch };
     let result = astar(&initial, |p| p.successors(&regions), |p| p.distance(&target),
         |p| *p == target);
     result.unwrap().1
@@ -199,17 +233,19 @@ fn part2(depth: usize, target: (usize, usize), expand: usize) -> u32 {
 mod tests {
     #[test]
     fn test_part1_ex() {
-        use part1;
+        use super::part1;
         assert_eq!(part1(510, (10, 10)), 114);
     }
 
+    #[test]
+    fn test_part2_ex() {
+        use super::part2;
+        assert_eq!(part2(510, (10, 10), 50), 45);
+    }
+
     #[test]
     fn test_part1_input() {
-        use part1;
+        use super::part1;
         assert_eq!(part1(11541, (14, 778)), 11575);
     }
 }
-
-fn main() {
-    println!("result: {}", part2(11541, (14, 778), 1024));
-}
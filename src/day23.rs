@@ -0,0 +1,268 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::bench::Variant;
+use crate::error::{Error, Result};
+use crate::format::Format;
+use crate::generate::{Knobs, Rng};
+use crate::oracle::Oracle;
+use crate::point::{Bounds, Point, Vector};
+use crate::solution::Solution;
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u32 = 23;
+    type Input = Vec<Nanobot>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<Nanobot>> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<Nanobot>) -> Result<u32> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Vec<Nanobot>) -> Result<u32> {
+        Ok(part2(input))
+    }
+
+    const GENERATED: bool = true;
+
+    fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+        generate(knobs, rng)
+    }
+
+    fn variants() -> Vec<Variant<Vec<Nanobot>>> {
+        vec![Variant::new(2, "grid", |input| Ok(grid_search(input).to_string()))]
+    }
+
+    fn oracles() -> Vec<Oracle<Vec<Nanobot>>> {
+        vec![Oracle::new("box search", |input| Ok(part2(input).to_string()), |input| Ok(brute_force(input).to_string()))]
+    }
+}
+
+#[derive(Debug)]
+pub struct Nanobot {
+    pos: Point<3>,
+    r: i64
+}
+
+impl Nanobot {
+    pub fn in_range(&self, other: &Nanobot) -> bool {
+        self.pos.manhattan(&other.pos) <= self.r
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<Nanobot>> {
+    let format = Format::new("pos=<{x},{y},{z}>, r={r}");
+    let mut result = Vec::new();
+    for (n, line) in input.lines().enumerate() {
+        let (x, y, z, r): (i64, i64, i64, u32) = format.parse(Day23::DAY, n + 1, line)?;
+        result.push(Nanobot { pos: Point::new([x, y, z]), r: r as i64 });
+    }
+    if result.is_empty() {
+        return Err(Error::parse(Day23::DAY, 1, "", format.pattern()));
+    }
+    Ok(result)
+}
+
+// knobs.count nanobots within knobs.extent of the origin along each axis, with ranges up
+// to knobs.extent
+pub fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+    let extent = knobs.extent.max(1) as i64;
+    let mut result = String::new();
+    for _ in 0..knobs.count.max(1) {
+        let [x, y, z] = [(); 3].map(|_| rng.range(-extent, extent));
+        result.push_str(&format!("pos=<{},{},{}>, r={}\n", x, y, z, rng.range(1, extent)));
+    }
+    result
+}
+
+pub fn part1(nanobots: &Vec<Nanobot>) -> u32 {
+    let max = nanobots.iter().max_by_key(|x| x.r).unwrap();
+    nanobots.iter().filter(|x| max.in_range(x)).count() as u32
+}
+
+// the box from min to max inclusive
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+struct Cube {
+    min: [i64; 3],
+    max: [i64; 3]
+}
+
+impl Cube {
+    fn distance(&self, point: &Point<3>) -> i64 {
+        (0..3).map(|i| (self.min[i] - point[i]).max(0) + (point[i] - self.max[i]).max(0)).sum()
+    }
+
+    fn in_range(&self, nanobots: &[Nanobot]) -> usize {
+        nanobots.iter().filter(|x| self.distance(&x.pos) <= x.r).count()
+    }
+
+    fn size(&self) -> i64 {
+        (0..3).map(|i| self.max[i] - self.min[i]).max().unwrap()
+    }
+
+    // halves along every axis that is more than one wide
+    fn split(&self) -> Vec<Cube> {
+        let mut result = vec![Cube { min: self.min, max: self.max }];
+        for i in 0..3 {
+            if self.min[i] == self.max[i] {
+                continue;
+            }
+            let mid = (self.min[i] + self.max[i]).div_euclid(2);
+            result = result.into_iter().flat_map(|cube| {
+                let (mut low, mut high) = (cube, cube);
+                low.max[i] = mid;
+                high.min[i] = mid + 1;
+                [low, high]
+            }).collect();
+        }
+        result
+    }
+}
+
+// best first over boxes. A box's count of nanobots reaching any of it is at least the
+// count of any point inside, and its distance to the origin is at most theirs, so the
+// first single point to come off the heap is the answer.
+pub fn part2(nanobots: &Vec<Nanobot>) -> u32 {
+    let origin = Point::origin();
+    let bounds = Bounds::of(nanobots.iter().map(|x| &x.pos).chain([&origin])).unwrap();
+    let all = Cube { min: [bounds.min[0], bounds.min[1], bounds.min[2]], max: [bounds.max[0], bounds.max[1], bounds.max[2]] };
+    let mut heap = BinaryHeap::new();
+    heap.push((all.in_range(nanobots), Reverse(all.distance(&origin)), Reverse(all.size()), all));
+    while let Some((_, Reverse(distance), Reverse(size), cube)) = heap.pop() {
+        if size == 0 {
+            return distance as u32;
+        }
+        for child in cube.split() {
+            heap.push((child.in_range(nanobots), Reverse(child.distance(&origin)), Reverse(child.size()), child));
+        }
+    }
+    unreachable!("the heap only empties after a single point")
+}
+
+// the first version, refining around the best corner of a coarse grid. Right for the
+// puzzle input but it can throw away the region with the best point on others.
+pub fn grid_search(nanobots: &Vec<Nanobot>) -> u32 {
+    let bounds = Bounds::of(nanobots.iter().map(|x| &x.pos)).unwrap();
+    let mut min = bounds.min;
+    let mut max = bounds.max;
+
+    let mut found_best = false;
+    let origin = Point::origin();
+    let mut best_cell = origin;
+    let mut grid_size = max[0] - min[0];
+    while grid_size > 0 {
+        let mut max_count = 0;
+
+        let mut x = min[0];
+        while x <= max[0] {
+            let mut y = min[1];
+            while y <= max[1] {
+                let mut z = min[2];
+                while z <= max[2] {
+                    let cell = Point::new([x, y, z]);
+                    let count = nanobots.iter().filter(|a| a.pos.manhattan(&cell) - a.r < grid_size).count();
+                    if max_count < count {
+                        max_count = count;
+                        best_cell = cell;
+                        found_best = true;
+                    } else if max_count == count
+                        && (!found_best || (origin.manhattan(&cell) < best_cell.manhattan(&origin))) {
+                            best_cell = cell;
+                            found_best = true;
+                        }
+                    z += grid_size;
+                }
+                y += grid_size;
+            }
+            x += grid_size;
+        }
+
+        let step = Vector::new([grid_size; 3]);
+        min = best_cell - step;
+        max = best_cell + step;
+
+        grid_size /= 2;
+    }
+
+    origin.manhattan(&best_cell) as u32
+}
+
+// tries every point in the box around the nanobots and the origin. Pulling any point into
+// that box brings it no further from the bots or the origin, so the answer is in there.
+pub fn brute_force(nanobots: &Vec<Nanobot>) -> u32 {
+    let origin = Point::origin();
+    let bounds = Bounds::of(nanobots.iter().map(|x| &x.pos).chain([&origin])).unwrap();
+    let mut best = (0, 0);
+    for x in bounds.min[0]..=bounds.max[0] {
+        for y in bounds.min[1]..=bounds.max[1] {
+            for z in bounds.min[2]..=bounds.max[2] {
+                let cell = Point::new([x, y, z]);
+                let count = nanobots.iter().filter(|a| a.pos.manhattan(&cell) <= a.r).count();
+                let distance = origin.manhattan(&cell);
+                if count > best.0 || (count == best.0 && distance < best.1) {
+                    best = (count, distance);
+                }
+            }
+        }
+    }
+    best.1 as u32
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_part1_ex() {
+        use super::{parse, part1};
+        let input = "\
+pos=<0,0,0>, r=4
+pos=<1,0,0>, r=1
+pos=<4,0,0>, r=3
+pos=<0,2,0>, r=1
+pos=<0,5,0>, r=3
+pos=<0,0,3>, r=1
+pos=<1,1,1>, r=1
+pos=<1,1,2>, r=1
+pos=<1,3,1>, r=1
+";
+        assert_eq!(part1(&parse(input).unwrap()), 7);
+    }
+
+    #[test]
+    fn test_part2_ex() {
+        use super::{parse, part2};
+        let input = "\
+pos=<10,12,12>, r=2
+pos=<12,14,12>, r=2
+pos=<16,12,12>, r=4
+pos=<14,14,14>, r=6
+pos=<50,50,50>, r=200
+pos=<10,10,10>, r=5
+";
+        assert_eq!(part2(&parse(input).unwrap()), 36);
+    }
+
+    #[test]
+    fn test_oracles() {
+        use crate::generate::Knobs;
+        use crate::oracle::{disagreements, run};
+        use super::Day23;
+        let cases = run::<Day23>(&Knobs { count: 10, extent: 8 }, 30).unwrap();
+        assert!(disagreements(&cases).is_empty(), "{:?}", disagreements(&cases));
+    }
+
+    #[test]
+    fn test_grid_search() {
+        use super::{grid_search, parse, part2};
+        // both bots reach (-1, -1, 0), two from the origin, but the grid settles on the
+        // origin, which neither reaches
+        let bots = parse("pos=<-1,-2,-1>, r=2\npos=<-1,-1,-2>, r=3\n").unwrap();
+        assert_eq!(part2(&bots), 2);
+        assert_eq!(grid_search(&bots), 0);
+    }
+}
@@ -0,0 +1,517 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use log::trace;
+
+use crate::deadline;
+use crate::diagnostics;
+use crate::error::{Error, Result};
+use crate::format::{first_of, Format, Mismatch};
+use crate::generate::{Knobs, Rng};
+use crate::solution::Solution;
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u32 = 24;
+    type Input = Vec<Army>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<Army>> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<Army>) -> Result<u32> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Vec<Army>) -> Result<u32> {
+        part2(input)
+    }
+
+    const GENERATED: bool = true;
+
+    fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+        generate(knobs, rng)
+    }
+}
+
+const IMMUNE_SYSTEM: usize = 0;
+const INFECTION: usize = 1;
+
+#[derive(Clone, Debug)]
+struct Attack {
+    initiative: u32,
+    dmg: u32,
+    dmg_type: String
+}
+
+#[derive(Clone, Debug)]
+pub struct Group {
+    id: u32,
+    army: usize,
+    units: u32,
+    hp: u32,
+    attack: Attack,
+    weak_to: Vec<String>,
+    imm_to: Vec<String>
+}
+
+impl Group {
+    pub fn effective_power(&self) -> u32 {
+        self.units * self.attack.dmg
+    }
+
+    pub fn calc_damage_to(&self, other: &Group) -> u32 {
+        if other.imm_to.contains(&self.attack.dmg_type) {
+            0
+        } else if other.weak_to.contains(&self.attack.dmg_type) {
+            self.effective_power() * 2
+        } else {
+            self.effective_power()
+        }
+    }
+
+    pub fn take_damage(&mut self, damage: u32) -> u32 {
+        let before = self.units;
+        let killed = damage / self.hp;
+        self.units = self.units.saturating_sub(killed);
+        before - self.units 
+    }
+
+    pub fn sort_by_ep(groups: &mut Vec<Group>) {
+        groups.sort_by(|a, b| {
+            let ea = a.effective_power();
+            let eb = b.effective_power();
+            if ea == eb {
+                a.attack.initiative.cmp(&b.attack.initiative).reverse()
+            } else {
+                ea.cmp(&eb).reverse()
+            }
+        });
+    }
+
+    pub fn sort_by_initiative(groups: &mut Vec<Group>) {
+        groups.sort_by(|a, b| a.attack.initiative.cmp(&b.attack.initiative).reverse());
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Army {
+    id: usize,
+    name: String,
+    groups: Vec<Group>
+}
+
+impl Army {
+    // the groups in id order and how many units each has left
+    pub fn render(&self) -> String {
+        let mut result = format!("{}:\n", self.name);
+        if !self.groups.is_empty() {
+            let mut groups: Vec<&Group> = self.groups.iter().collect();
+            groups.sort_by_key(|x| x.id);
+            for group in groups {
+                result.push_str(&format!("Group {} contains {} units\n", group.id, group.units));
+            }
+        } else {
+            result.push_str("No groups remain.\n");
+        }
+        result
+    }
+
+    pub fn unit_count(&self) -> u32 {
+        self.groups.iter().fold(0u32, |sum, x| sum + x.units)
+    }
+
+    pub fn target_selection(&self, enemy_army: &Army, attacks: &mut HashMap<(usize, u32), (u32, u32)>) {
+        let mut assigned = HashSet::new();
+        for group in &self.groups {
+            let mut damages = Vec::with_capacity(enemy_army.groups.len());
+            for other in &enemy_army.groups {
+                if assigned.contains(&other.id) {
+                    continue;
+                }
+                let dmg = group.calc_damage_to(other);
+                if other.units > 0 && dmg > 0 {
+                    trace!("{} group {} would deal defending group {} {} damage", self.name, group.id, other.id, dmg);
+                    damages.push((dmg, other.effective_power(), other.attack.initiative, other.id, other.hp));
+                }
+            }
+
+            damages.sort_by(|a, b| {
+                if a.0 == b.0 {
+                    if a.1 == b.1 {
+                        a.2.cmp(&b.2).reverse()
+                    } else {
+                        a.1.cmp(&b.1).reverse()
+                    }
+                } else {
+                    a.0.cmp(&b.0).reverse()
+                }
+            });
+            trace!("damages: {:?}", damages);
+
+            if !damages.is_empty() {
+                let attack = damages.first().unwrap();
+                attacks.insert((self.id, group.id), (attack.3, attack.0));
+                assigned.insert(attack.3);
+                trace!("selected: {}", attack.3);
+            }
+            trace!("assigned: {:?}", assigned);
+        }
+    }
+
+    pub fn enemy(id: usize) -> usize {
+        if id == IMMUNE_SYSTEM { INFECTION } else { IMMUNE_SYSTEM }
+    }
+
+    pub fn damage_from(&mut self, group_id: u32, from: &Group) -> u32 {
+        for group in self.groups.iter_mut() {
+            if group_id == group.id {
+                let damage = from.calc_damage_to(group);
+                return group.take_damage(damage);
+            }
+        }
+        0
+    }
+
+    pub fn get_group(&self, group_id: u32) -> Option<Group> {
+        for group in &self.groups {
+            if group_id == group.id {
+                return Some(group.clone());
+            }
+        }
+        None
+    }
+
+    pub fn get_group_units(&self, group_id: u32) -> Option<u32> {
+        for group in &self.groups {
+            if group_id == group.id {
+                return Some(group.units);
+            }
+        }
+        None
+    }
+
+    pub fn cleanup(&mut self) {
+        self.groups.retain(|x| x.units > 0);
+    }
+}
+
+fn fight(armies: &mut Vec<Army>, round: u32) -> bool {
+    let mut attacks = HashMap::new();
+
+    Group::sort_by_ep(&mut armies[IMMUNE_SYSTEM].groups);
+    armies[IMMUNE_SYSTEM].target_selection(&armies[INFECTION], &mut attacks);
+
+    Group::sort_by_ep(&mut armies[INFECTION].groups);
+    armies[INFECTION].target_selection(&armies[IMMUNE_SYSTEM], &mut attacks);
+
+    let mut all_groups = Vec::new();
+    for army in armies.iter() {
+        all_groups.append(&mut army.groups.clone());
+    }
+    Group::sort_by_initiative(&mut all_groups);
+
+    let mut total_killed = 0;
+    for group in &all_groups {
+        let tmp = attacks.get(&(group.army, group.id));
+        if tmp.is_some() {
+            let attack = tmp.unwrap();
+            let attacking_group = armies[group.army].get_group(group.id).unwrap();
+            if attacking_group.units == 0 {
+                continue;
+            }
+            let killed = armies[Army::enemy(group.army)].damage_from(attack.0, &attacking_group);
+            total_killed += killed;
+            trace!("round {}: {} group {} attacks defending {} group {}, killing {} of {}", round, armies[group.army].name, group.id, armies[Army::enemy(group.army)].name, attack.0,
+                   killed, armies[Army::enemy(group.army)].get_group_units(attack.0).unwrap() + killed);
+        }
+    }
+
+    for army in armies {
+        army.cleanup();
+    }
+
+    total_killed == 0
+}
+
+// the weaknesses and immunities between the brackets, like `weak to fire; immune to cold, slashing`
+fn parse_traits(traits: &str, group: &mut Group) -> std::result::Result<(), Mismatch> {
+    let weak = Format::new("weak to {list}");
+    let immune = Format::new("immune to {list}");
+    let mut column = 0;
+    for part in traits.split("; ") {
+        let (which, fields) = first_of(&[&weak, &immune], part).map_err(|e| e.within(column))?;
+        let list = fields.str("list").split(", ").map(String::from);
+        if which == 0 {
+            group.weak_to.extend(list);
+        } else {
+            group.imm_to.extend(list);
+        }
+        column += part.len() + 2;
+    }
+    Ok(())
+}
+
+fn parse(input: &str) -> Result<Vec<Army>> {
+    let plain = Format::new("{units} units each with {hp} hit points with an attack that does {damage} {type} damage at initiative {initiative}");
+    let with_traits = Format::new("{units} units each with {hp} hit points ({traits}) with an attack that does {damage} {type} damage at initiative {initiative}");
+
+    let mut result = vec![
+        Army{ id: IMMUNE_SYSTEM, name: String::from("Immune System"), groups: Vec::new() },
+        Army{ id: INFECTION, name: String::from("Infection"), groups: Vec::new() },
+    ];
+
+    let mut army = IMMUNE_SYSTEM;
+    let mut group_id = 1u32;
+
+    for (n, line) in input.lines().enumerate() {
+        match line {
+            "Immune System:" => { army = IMMUNE_SYSTEM; group_id = 1; },
+            "Infection:" => { army = INFECTION; group_id = 1; },
+            _ => {
+                if line.is_empty() { continue; }
+
+                let mut group = Group {id: group_id, army, units: 0, hp: 0, attack: Attack {initiative:0, dmg:0, dmg_type:String::from("")}, weak_to: Vec::new(), imm_to: Vec::new()};
+                let mut scan = || {
+                    let (which, fields) = first_of(&[&plain, &with_traits], line)?;
+                    group.units = fields.get("units")?;
+                    group.hp = fields.get("hp")?;
+                    group.attack.dmg = fields.get("damage")?;
+                    group.attack.dmg_type = fields.str("type").to_string();
+                    group.attack.initiative = fields.get("initiative")?;
+                    if which == 1 {
+                        parse_traits(fields.str("traits"), &mut group).map_err(|e| e.within(fields.column("traits")))?;
+                    }
+                    Ok(())
+                };
+                scan().map_err(|e: Mismatch| e.error(Day24::DAY, n + 1, line))?;
+                if group.hp == 0 || group.units == 0 {
+                    return Err(Error::parse(Day24::DAY, n + 1, line, "groups with some units and hit points"));
+                }
+                trace!("{} units each with {} hit points weak_to:{:?} imm_to:{:?} with an attack that does {} {} damage at initiative {}",
+                       group.units, group.hp, group.weak_to, group.imm_to, group.attack.dmg, group.attack.dmg_type, group.attack.initiative);
+                result[army].groups.push(group);
+                group_id += 1;
+            }
+        }
+    }
+    if result.iter().any(|army| army.groups.is_empty()) {
+        return Err(Error::parse_input(Day24::DAY, input, "groups in both armies"));
+    }
+    Ok(result)
+}
+
+// knobs.count groups in each army, at least one, with up to knobs.extent units. Every group has its own
+// initiative, and some weaknesses and immunities out of the five attack types.
+pub fn generate(knobs: &Knobs, rng: &mut Rng) -> String {
+    let types = ["bludgeoning", "cold", "fire", "radiation", "slashing"];
+    let extent = knobs.extent.max(1) as i64;
+    let count = knobs.count.max(1);
+    let mut initiatives: Vec<usize> = (1..=2 * count).collect();
+    rng.shuffle(&mut initiatives);
+    let mut initiatives = initiatives.into_iter();
+
+    let mut result = String::new();
+    for name in ["Immune System", "Infection"] {
+        if !result.is_empty() {
+            result.push('\n');
+        }
+        result.push_str(&format!("{}:\n", name));
+        for _ in 0..count {
+            let mut shuffled = types;
+            rng.shuffle(&mut shuffled);
+            let weak = rng.below(3);
+            let immune = rng.below(2);
+            let mut traits = Vec::new();
+            if weak > 0 {
+                traits.push(format!("weak to {}", shuffled[..weak].join(", ")));
+            }
+            if immune > 0 {
+                traits.push(format!("immune to {}", shuffled[weak..weak + immune].join(", ")));
+            }
+            rng.shuffle(&mut traits);
+            let traits = if traits.is_empty() { String::new() } else { format!(" ({})", traits.join("; ")) };
+            result.push_str(&format!("{} units each with {} hit points{} with an attack that does {} {} damage at initiative {}\n",
+                                     rng.range(1, extent), rng.range(1, 10 * extent), traits, rng.range(1, 5 * extent),
+                                     rng.choose(&types), initiatives.next().unwrap()));
+        }
+    }
+    result
+}
+
+pub fn part1(input: &Vec<Army>) -> u32 {
+    let mut armies = input.clone();
+    let mut round = 1;
+    loop {
+        trace!("{}{}", armies[IMMUNE_SYSTEM].render(), armies[INFECTION].render());
+
+        let immune_count = armies[IMMUNE_SYSTEM].unit_count();
+        let infection_count = armies[INFECTION].unit_count();
+        if immune_count == 0 && infection_count == 0 {
+            // tie
+            return 0;
+        } else if immune_count == 0 {
+            return infection_count;
+        } else if infection_count == 0 {
+            return immune_count;
+        }
+
+        if fight(&mut armies, round) {
+            // stalemate
+            return 0;
+        }
+
+        round += 1;
+    }
+}
+
+fn boosted(armies: &mut Vec<Army>, boost: u32) -> Option<u32> {
+    for group in armies[IMMUNE_SYSTEM].groups.iter_mut() {
+        group.attack.dmg += boost;
+    }
+
+    loop {
+        if deadline::expired() {
+            return None;
+        }
+        let immune_count = armies[IMMUNE_SYSTEM].unit_count();
+        let infection_count = armies[INFECTION].unit_count();
+        if immune_count == 0 && infection_count == 0 {
+            // tie
+            return None;
+        } else if immune_count == 0 {
+            return None;
+        } else if infection_count == 0 {
+            return Some(immune_count);
+        }
+
+        if fight(armies, 0) {
+            // stalemate
+            return None;
+        }
+    }
+}
+
+// the smallest boost the immune system wins with. The boost doubles until one wins, then
+// every boost up to that one is tried in order, since a smaller one can win where a bigger
+// one loses. It gives up at the deadline, or once an immune system group's effective power
+// or the boost itself would no longer fit in a u32.
+pub fn part2(armies: &Vec<Army>) -> Result<u32> {
+    let fits = |boost: u32| armies[IMMUNE_SYSTEM].groups.iter().all(|x| x.units as u64 * (x.attack.dmg as u64 + boost as u64) * 2 <= u32::MAX as u64);
+    let gave_up = || Error::solve(Day24::DAY, "ran out of time looking for a boost");
+    let mut top = 1u32;
+    loop {
+        trace!("boost: {}", top);
+        if deadline::expired() {
+            return Err(gave_up());
+        }
+        if !fits(top) {
+            return Err(Error::solve(Day24::DAY, &format!("no boost below {} lets the immune system win", top)));
+        }
+        if boosted(&mut armies.clone(), top).is_some() {
+            break;
+        }
+        top = top.checked_mul(2).ok_or_else(|| Error::solve(Day24::DAY, "no boost that fits in a u32 lets the immune system win"))?;
+    }
+    for boost in 1..=top {
+        trace!("boost: {}", boost);
+        if deadline::expired() {
+            return Err(gave_up());
+        }
+        if let Some(units) = boosted(&mut armies.clone(), boost) {
+            diagnostics::record("boost", boost);
+            return Ok(units);
+        }
+    }
+    unreachable!("the boost of {} won before", top)
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = "\
+Immune System:
+17 units each with 5390 hit points (weak to radiation, bludgeoning) with an attack that does 4507 fire damage at initiative 2
+989 units each with 1274 hit points (immune to fire; weak to bludgeoning, slashing) with an attack that does 25 slashing damage at initiative 3
+
+Infection:
+801 units each with 4706 hit points (weak to radiation) with an attack that does 116 bludgeoning damage at initiative 1
+4485 units each with 2961 hit points (immune to radiation; weak to fire, cold) with an attack that does 12 slashing damage at initiative 4
+";
+
+    #[test]
+    fn test_parse_traits() {
+        use super::parse;
+        let input = "\
+Immune System:
+10 units each with 20 hit points (weak to fire, cold, radiation; immune to slashing) with an attack that does 5 cold damage at initiative 1
+
+Infection:
+3 units each with 7 hit points with an attack that does 2 fire damage at initiative 2
+";
+        let armies = parse(input).unwrap();
+        let group = &armies[0].groups[0];
+        assert_eq!(group.weak_to, vec!["fire", "cold", "radiation"]);
+        assert_eq!(group.imm_to, vec!["slashing"]);
+
+        let err = parse("10 units each with 20 hit points (weak fire) with an attack that does 5 cold damage at initiative 1").err().unwrap();
+        assert_eq!(err.to_string(), "day 24 line 1 column 40: expected `to`, found `fire) with an attack that does 5 cold damage at initiative 1`");
+        // both armies need a group with some units in it
+        assert_eq!(parse("Immune System:\n\nInfection:\n").err().unwrap().to_string(), "day 24 line 1: expected `groups in both armies`, found `Immune System:`");
+        assert_eq!(parse(&input.replace("10 units", "0 units")).err().unwrap().to_string(), "day 24 line 2: expected `groups with some units and hit points`, found `0 units each with 20 hit points (weak to fire, cold, radiation; immune to slashing) with an attack that does 5 cold damage at initiative 1`");
+        assert!(parse(&input[..input.find("\nInfection").unwrap()]).is_err());
+    }
+
+    #[test]
+    fn test_part1_ex() {
+        use super::{parse, part1};
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 5216);
+    }
+
+    #[test]
+    #[ignore = "needs the original author's puzzle input in inputs/ or AOC_INPUT_DIR"]
+    fn test_part1_input() {
+        use crate::solution::load_input;
+        use super::Day24;
+        use super::part1;
+        assert_eq!(part1(&load_input::<Day24>().unwrap()), 10538);
+    }
+
+    #[test]
+    fn test_part2_ex() {
+        use super::{parse, part2};
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()).unwrap(), 51);
+    }
+
+    #[test]
+    fn test_part2_bound() {
+        use super::{boosted, parse, part2};
+        // the infection can't kill the immune system's one unit, and it takes a boost of
+        // 999 to kill one of the infection's
+        let slow = "\
+Immune System:
+1 units each with 1000000 hit points with an attack that does 1 fire damage at initiative 2
+
+Infection:
+1000 units each with 1000 hit points with an attack that does 1 cold damage at initiative 1
+";
+        let armies = parse(slow).unwrap();
+        assert_eq!((boosted(&mut armies.clone(), 998), boosted(&mut armies.clone(), 999)), (None, Some(1)));
+        assert_eq!(part2(&armies).unwrap(), 1);
+        // no boost gets past an immunity, and it stops before the damage overflows
+        let immune = parse(&slow.replace("1000 hit points", "1000 hit points (immune to fire)")).unwrap();
+        assert_eq!(part2(&immune).unwrap_err().to_string(), "day 24: no boost below 2147483648 lets the immune system win");
+    }
+
+    #[test]
+    fn test_deadline() {
+        use super::{parse, part2};
+        use crate::deadline::within;
+        use std::time::Duration;
+        let armies = parse(EXAMPLE).unwrap();
+        assert_eq!(within(Duration::ZERO, || part2(&armies).ok()), (None, true));
+        assert_eq!(within(Duration::from_secs(60), || part2(&armies).ok()), (Some(51), false));
+    }
+}
@@ -0,0 +1,100 @@
+use crate::error::Result;
+use crate::format::Format;
+use crate::point::Point;
+use crate::solution::Solution;
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u32 = 25;
+    const PART2: bool = false;
+    type Input = Vec<Point<4>>;
+    type Answer1 = usize;
+    type Answer2 = &'static str;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize> {
+        Ok(part1(input))
+    }
+
+    fn part2(_input: &Self::Input) -> Result<&'static str> {
+        unreachable!("day 25 has no part 2")
+    }
+}
+
+// points this close or closer are in the same constellation
+const JOIN_DISTANCE: i64 = 3;
+
+fn parse(input: &str) -> Result<Vec<Point<4>>> {
+    let format = Format::new("{x},{y},{z},{t}");
+    let mut result = Vec::new();
+    for (index, line) in input.lines().enumerate() {
+        let (x, y, z, t) = format.parse(Day25::DAY, index + 1, line)?;
+        result.push(Point::new([x, y, z, t]));
+    }
+    Ok(result)
+}
+
+// flood fills from each point not yet in a constellation
+pub fn part1(points: &Vec<Point<4>>) -> usize {
+    let mut assigned = vec![false; points.len()];
+    let mut constellations = 0;
+    for first in 0..points.len() {
+        if assigned[first] {
+            continue;
+        }
+
+        constellations += 1;
+        assigned[first] = true;
+        let mut todo = vec![first];
+        while let Some(current) = todo.pop() {
+            for next in 0..points.len() {
+                if !assigned[next] && points[current].manhattan(&points[next]) <= JOIN_DISTANCE {
+                    assigned[next] = true;
+                    todo.push(next);
+                }
+            }
+        }
+    }
+    constellations
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_part1_ex() {
+        use super::{parse, part1};
+        let input = "\
+0,0,0,0
+3,0,0,0
+0,3,0,0
+0,0,3,0
+0,0,0,3
+0,0,0,6
+9,0,0,0
+12,0,0,0
+";
+        assert_eq!(part1(&parse(input).unwrap()), 2);
+    }
+
+    #[test]
+    fn test_part1_ex_negative() {
+        use super::{parse, part1};
+        let input = "\
+-1,2,2,0
+0,0,2,-2
+0,0,0,-2
+-1,2,0,0
+-2,-2,-2,2
+3,0,2,-1
+-1,3,2,2
+-1,0,-1,0
+0,2,1,-2
+3,0,0,0
+";
+        assert_eq!(part1(&parse(input).unwrap()), 4);
+    }
+}
@@ -0,0 +1,54 @@
+//! A time limit for the part running on this thread. `aoc all` stops waiting for a part
+//! at its deadline and reports it as timed out, but a thread can't be stopped from
+//! outside, so the part goes on running until the process exits. Days 14, 15 and 24
+//! poll `expired` and give up early, which frees their thread for the next part.
+
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+// false outside of within, so the days run to the end when called directly
+pub fn expired() -> bool {
+    DEADLINE.with(|deadline| deadline.get().is_some_and(|deadline| Instant::now() >= deadline))
+}
+
+// runs f with limit to go, and says whether it finished after the limit. A within
+// inside f can't push the deadline later.
+pub fn within<T>(limit: Duration, f: impl FnOnce() -> T) -> (T, bool) {
+    let deadline = Instant::now() + limit;
+    let outer = DEADLINE.with(|x| x.replace(Some(x.get().map_or(deadline, |outer| outer.min(deadline)))));
+    let result = f();
+    let expired = expired();
+    DEADLINE.with(|x| x.set(outer));
+    (result, expired)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_within() {
+        use super::{expired, within};
+        use std::time::Duration;
+        assert!(!expired());
+        let (polls, late) = within(Duration::from_millis(20), || {
+            let mut polls = 0;
+            while !expired() {
+                polls += 1;
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            polls
+        });
+        assert!(late);
+        assert!(polls > 0);
+        assert!(!expired());
+
+        let (inner, late) = within(Duration::from_secs(60), || within(Duration::from_secs(3600), expired).1);
+        assert!(!inner && !late);
+        // the inner limit is cut down to the outer one
+        let (inner, _) = within(Duration::ZERO, || within(Duration::from_secs(3600), || ()).1);
+        assert!(inner);
+    }
+}
@@ -0,0 +1,52 @@
+//! Extra numbers a day can report along with its answer, like how many rounds a battle
+//! took or the period of the cycle it found. The runner collects them for its JSON
+//! output and outside of `collect` recording one does nothing.
+//!
+//! The collector belongs to the thread running the part, values recorded from inside a
+//! rayon closure are dropped.
+
+use std::cell::RefCell;
+
+use serde_json::{Map, Value};
+
+thread_local! {
+    static EXTRAS: RefCell<Option<Map<String, Value>>> = const { RefCell::new(None) };
+}
+
+// a later value for the same key replaces the earlier one, so a loop can record as it goes
+pub fn record(key: &str, value: impl Into<Value>) {
+    EXTRAS.with(|extras| {
+        if let Some(extras) = extras.borrow_mut().as_mut() {
+            extras.insert(key.to_string(), value.into());
+        }
+    });
+}
+
+// runs f and returns what it recorded, a collect inside f keeps its own values
+pub fn collect<T>(f: impl FnOnce() -> T) -> (T, Map<String, Value>) {
+    let outer = EXTRAS.with(|extras| extras.replace(Some(Map::new())));
+    let result = f();
+    let extras = EXTRAS.with(|extras| extras.replace(outer)).unwrap_or_default();
+    (result, extras)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_collect() {
+        use super::{collect, record};
+        use serde_json::json;
+        record("dropped", 1);
+        let (answer, extras) = collect(|| {
+            record("rounds", 46);
+            record("hp", 590);
+            record("rounds", 47);
+            let (_, inner) = collect(|| record("inner", true));
+            assert_eq!(inner.get("inner"), Some(&json!(true)));
+            27730
+        });
+        assert_eq!(answer, 27730);
+        assert_eq!(serde_json::Value::Object(extras), json!({ "rounds": 47, "hp": 590 }));
+        assert!(collect(|| ()).1.is_empty());
+    }
+}
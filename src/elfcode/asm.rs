@@ -0,0 +1,495 @@
+//! An assembler for the ElfCode device, putting out the `#ip N` text `Device::load` reads.
+//! On top of the sixteen instructions it has:
+//!
+//! ```text
+//! #ip 5                  the instruction pointer register, needed for any jump
+//! ; comment              to the end of the line
+//! loop:                  a label, its value is the address of what follows
+//! .reg n r4              n names r4, as does ip the #ip register
+//! .const TARGET 836      a number by name
+//! .scratch r3            the register the jumps below keep their comparison in
+//! .macro inc x           a macro, its parameters replaced by the words it's given.
+//!     addi x 1 x         Labels in it are renamed for each use.
+//! .end
+//! ```
+//!
+//! and these, lowered to instructions that set ip:
+//!
+//! ```text
+//! jmp label              mov dst, src            halt
+//! jz reg, label          jnz reg, label
+//! jeq reg, x, label      jne reg, x, label       x a register or a number
+//! jgt reg, x, label      jle reg, x, label
+//! jlt reg, x, label      jge reg, x, label
+//! ```
+//!
+//! Operands are separated by spaces or commas. A number can be written as a constant or
+//! a label, plus or minus a number, like `loop-1`. Since ip moves on one after every
+//! instruction, even one that jumps, nothing can jump to 0.
+
+use std::collections::BTreeMap;
+
+use super::{instruction_set, Operands};
+
+use crate::error::{Error, Result};
+
+// where a line came from, for errors
+struct Line {
+    number: usize,
+    text: String
+}
+
+struct Macro {
+    params: Vec<String>,
+    body: Vec<String>
+}
+
+// how many instructions each built in macro takes
+fn size(name: &str) -> Option<usize> {
+    match name {
+        "jmp" | "mov" | "halt" => Some(1),
+        "jz" | "jnz" | "jne" | "jle" | "jge" => Some(3),
+        "jeq" | "jgt" | "jlt" => Some(4),
+        _ => None
+    }
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| c.is_whitespace() || c == ',').filter(|x| !x.is_empty()).map(String::from).collect()
+}
+
+fn is_name(word: &str) -> bool {
+    word.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && word.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '.')
+}
+
+// replaces whole words only, so a parameter x leaves max alone
+fn replace_word(text: &str, word: &str, with: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(i) = rest.find(word) {
+        let before = rest[..i].chars().next_back();
+        let after = rest[i + word.len()..].chars().next();
+        let part = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '.');
+        out.push_str(&rest[..i]);
+        out.push_str(if part(before) || part(after) { word } else { with });
+        rest = &rest[i + word.len()..];
+    }
+    out.push_str(rest);
+    out
+}
+
+struct Assembler<'a, const REGS: usize> {
+    path: &'a str,
+    bound: Option<u32>,
+    scratch: Option<String>,
+    aliases: BTreeMap<String, u32>,
+    numbers: BTreeMap<String, u32>
+}
+
+impl<const REGS: usize> Assembler<'_, REGS> {
+    fn error(&self, line: &Line, expected: &str) -> Error {
+        Error::format(self.path, line.number, line.text.trim(), expected)
+    }
+
+    fn register(&self, line: &Line, word: &str) -> Result<u32> {
+        let reg = match word {
+            "ip" => self.bound,
+            _ => self.aliases.get(word).copied()
+                .or_else(|| word.strip_prefix('r').unwrap_or(word).parse::<u32>().ok())
+        };
+        reg.filter(|r| (*r as usize) < REGS).ok_or_else(|| self.error(line, &format!("a register, r0 to r{}, ip or a .reg name, for {}", REGS - 1, word)))
+    }
+
+    fn number(&self, line: &Line, word: &str) -> Result<u32> {
+        let expected = || self.error(line, &format!("a number, constant or label, for {}", word));
+        // label+1 or label-1, by char so a first character of more than one byte is fine
+        let (name, offset) = match word.char_indices().skip(1).find(|(_, c)| matches!(c, '+' | '-')) {
+            Some((i, _)) => {
+                let (name, offset) = word.split_at(i);
+                let value = offset[1..].parse::<i64>().map_err(|_| expected())?;
+                (name, if offset.starts_with('-') { -value } else { value })
+            },
+            None => (word, 0)
+        };
+        let base = match name.parse::<u32>() {
+            Ok(n) => n,
+            Err(_) => *self.numbers.get(name).ok_or_else(expected)?
+        };
+        u32::try_from(base as i64 + offset).map_err(|_| expected())
+    }
+
+    // the register or number x is, for the macros that take either
+    fn either(&self, line: &Line, word: &str) -> Result<(bool, u32)> {
+        match self.register(line, word) {
+            Ok(reg) if !word.chars().all(|c| c.is_ascii_digit()) => Ok((true, reg)),
+            _ => self.number(line, word).map(|n| (false, n))
+        }
+    }
+
+    // what ip has to be set to for the next instruction to be target
+    fn target(&self, line: &Line, word: &str) -> Result<u32> {
+        match self.number(line, word)? {
+            0 => Err(self.error(line, "a jump target after 0, ip moves on one after a jump")),
+            target => Ok(target - 1)
+        }
+    }
+
+    fn ip(&self, line: &Line) -> Result<u32> {
+        self.bound.ok_or_else(|| self.error(line, "#ip N before the first jump"))
+    }
+
+    fn scratch(&self, line: &Line) -> Result<u32> {
+        let name = self.scratch.as_deref().ok_or_else(|| self.error(line, ".scratch naming a register for the comparison"))?;
+        self.register(line, name)
+    }
+
+    // a built in macro as instructions
+    fn lower(&self, line: &Line, name: &str, args: &[String], len: usize) -> Result<Vec<(&'static str, [u32; 3])>> {
+        let arity = match name {
+            "halt" => 0,
+            "jmp" => 1,
+            "mov" | "jz" | "jnz" => 2,
+            _ => 3
+        };
+        if args.len() != arity {
+            return Err(self.error(line, &format!("{} operands for {}", arity, name)));
+        }
+        if name == "mov" {
+            let dst = self.register(line, &args[0])?;
+            return Ok(vec![match self.either(line, &args[1])? {
+                (true, src) => ("setr", [src, 0, dst]),
+                (false, n) => ("seti", [n, 0, dst])
+            }]);
+        }
+        let ip = self.ip(line)?;
+        if name == "halt" {
+            // past the end of the program
+            return Ok(vec![("seti", [len as u32 - 1, 0, ip])]);
+        }
+        let target = self.target(line, args.last().unwrap())?;
+        if name == "jmp" {
+            return Ok(vec![("seti", [target, 0, ip])]);
+        }
+
+        let flag = self.scratch(line)?;
+        let a = self.register(line, &args[0])?;
+        // the comparison, and whether to jump when it holds or when it doesn't
+        let (compare, when) = match name {
+            "jz" => (("gtri", [a, 0, flag]), false),
+            "jnz" => (("eqri", [a, 0, flag]), false),
+            _ => {
+                let b = self.either(line, &args[1])?;
+                let (compare, when) = match name {
+                    "jeq" => ("eq", true),
+                    "jne" => ("eq", false),
+                    "jgt" => ("gt", true),
+                    "jle" => ("gt", false),
+                    "jlt" => ("lt", true),
+                    _ => ("lt", false)
+                };
+                let instruction = match (compare, b) {
+                    ("eq", (true, b)) => ("eqrr", [a, b, flag]),
+                    ("eq", (false, b)) => ("eqri", [a, b, flag]),
+                    ("gt", (true, b)) => ("gtrr", [a, b, flag]),
+                    ("gt", (false, b)) => ("gtri", [a, b, flag]),
+                    // a < b is b > a
+                    (_, (true, b)) => ("gtrr", [b, a, flag]),
+                    (_, (false, b)) => ("gtir", [b, a, flag])
+                };
+                (instruction, when)
+            }
+        };
+        // the flag skips the next instruction when it is 1
+        let mut out = vec![compare, ("addr", [flag, ip, ip])];
+        if when {
+            out.push(("addi", [ip, 1, ip]));
+        }
+        out.push(("seti", [target, 0, ip]));
+        Ok(out)
+    }
+}
+
+// expands the .macro definitions, each use of one becomes its body
+fn expand(path: &str, source: &str) -> Result<Vec<Line>> {
+    let mut macros: BTreeMap<String, Macro> = BTreeMap::new();
+    let mut lines = Vec::new();
+    let mut defining: Option<(usize, String, Macro)> = None;
+    for (n, raw) in source.lines().enumerate() {
+        let text = raw.split(';').next().unwrap().trim();
+        let line = Line { number: n + 1, text: text.to_string() };
+        let first = words(text).into_iter().next().unwrap_or_default();
+        match (&mut defining, first.as_str()) {
+            (Some(_), ".end") => {
+                let (_, name, body) = defining.take().unwrap();
+                macros.insert(name, body);
+            },
+            (Some(_), ".macro") => return Err(Error::format(path, n + 1, text, ".end before another .macro")),
+            (Some((_, _, body)), _) => body.body.push(text.to_string()),
+            (None, ".macro") => {
+                let words = words(text);
+                if words.len() < 2 || !is_name(&words[1]) || size(&words[1]).is_some() {
+                    return Err(Error::format(path, n + 1, text, ".macro <name> [params]..., not a built in name"));
+                }
+                defining = Some((n + 1, words[1].clone(), Macro { params: words[2..].to_vec(), body: Vec::new() }));
+            },
+            (None, ".end") => return Err(Error::format(path, n + 1, text, ".macro before .end")),
+            (None, _) => lines.push(line)
+        }
+    }
+    if let Some((start, _, _)) = defining {
+        return Err(Error::format(path, start, source.lines().nth(start - 1).unwrap_or(""), ".end for this .macro"));
+    }
+
+    // uses inside macros are expanded too, as long as they don't go round forever
+    let mut uses = 0;
+    let mut out = Vec::new();
+    let mut pending: Vec<Line> = lines.into_iter().rev().collect();
+    while let Some(line) = pending.pop() {
+        let mut words = words(&line.text);
+        let label = match words.first() {
+            Some(word) if word.ends_with(':') => Some(words.remove(0)),
+            _ => None
+        };
+        let Some(definition) = words.first().and_then(|name| macros.get(name)) else {
+            out.push(line);
+            continue;
+        };
+        let args = &words[1..];
+        if args.len() != definition.params.len() {
+            return Err(Error::format(path, line.number, &line.text, &format!("{} operands for {}", definition.params.len(), words[0])));
+        }
+        uses += 1;
+        if uses > 10000 {
+            return Err(Error::format(path, line.number, &line.text, "macros that stop using each other"));
+        }
+        if let Some(label) = label {
+            out.push(Line { number: line.number, text: label });
+        }
+        let locals: Vec<String> = definition.body.iter()
+            .filter_map(|text| text.split_whitespace().next()?.strip_suffix(':').map(String::from))
+            .collect();
+        let mut body = Vec::new();
+        for text in &definition.body {
+            let mut text = text.clone();
+            for (param, arg) in definition.params.iter().zip(args) {
+                text = replace_word(&text, param, arg);
+            }
+            for local in &locals {
+                text = replace_word(&text, local, &format!("{}.{}", local, uses));
+            }
+            body.push(Line { number: line.number, text });
+        }
+        pending.extend(body.into_iter().rev());
+    }
+    Ok(out)
+}
+
+// assembles source, errors say which line of path they're on
+pub fn assemble<const REGS: usize>(path: &str, source: &str) -> Result<String> {
+    let lines = expand(path, source)?;
+    let set = instruction_set();
+    let mut asm = Assembler::<REGS> { path, bound: None, scratch: None, aliases: BTreeMap::new(), numbers: BTreeMap::new() };
+
+    // the directives and labels, and where every instruction goes
+    let mut items: Vec<(&Line, Vec<String>)> = Vec::new();
+    let mut len = 0;
+    let defined = |asm: &mut Assembler<REGS>, line: &Line, name: &str, value: Option<u32>, alias: Option<u32>| {
+        if !is_name(name) || name == "ip" || asm.numbers.contains_key(name) || asm.aliases.contains_key(name) {
+            return Err(asm.error(line, &format!("a name not already used, for {}", name)));
+        }
+        match (value, alias) {
+            (Some(value), _) => asm.numbers.insert(name.to_string(), value),
+            (_, Some(reg)) => asm.aliases.insert(name.to_string(), reg),
+            _ => None
+        };
+        Ok(())
+    };
+    for line in &lines {
+        let mut words = words(&line.text);
+        while words.first().is_some_and(|word| word.ends_with(':')) {
+            let label = words.remove(0);
+            defined(&mut asm, line, label.trim_end_matches(':'), Some(len as u32), None)?;
+        }
+        match words.first().map(|x| x.as_str()) {
+            None => {},
+            Some("#ip") => match words[..] {
+                [_, ref reg] if asm.bound.is_none() && len == 0 => asm.bound = Some(asm.register(line, reg)?),
+                _ => return Err(asm.error(line, "one #ip N before the first instruction"))
+            },
+            Some(".reg") => match words[..] {
+                [_, ref name, ref reg] => {
+                    let reg = asm.register(line, reg)?;
+                    defined(&mut asm, line, name, None, Some(reg))?;
+                },
+                _ => return Err(asm.error(line, ".reg <name> <register>"))
+            },
+            Some(".const") => match words[..] {
+                [_, ref name, ref value] => {
+                    let value = asm.number(line, value)?;
+                    defined(&mut asm, line, name, Some(value), None)?;
+                },
+                _ => return Err(asm.error(line, ".const <name> <number>"))
+            },
+            Some(".scratch") => match words[..] {
+                [_, ref reg] => asm.scratch = Some(reg.clone()),
+                _ => return Err(asm.error(line, ".scratch <register>"))
+            },
+            Some(name) => {
+                len += match size(name) {
+                    Some(size) => size,
+                    None if set.iter().any(|inst| inst.mnemonic == name) => 1,
+                    None => return Err(asm.error(line, "an instruction, macro or directive"))
+                };
+                items.push((line, words));
+            }
+        }
+    }
+    let bound = asm.bound.ok_or_else(|| Error::format(path, 1, source.lines().next().unwrap_or(""), "#ip N"))?;
+    if len == 0 {
+        return Err(Error::format(path, 1, source.lines().next().unwrap_or(""), "at least one instruction"));
+    }
+
+    let mut out = format!("#ip {}\n", bound);
+    for (line, words) in items {
+        let name = words[0].as_str();
+        let args = &words[1..];
+        let instructions = match set.iter().find(|inst| inst.mnemonic == name) {
+            Some(inst) => {
+                if args.len() != 3 {
+                    return Err(asm.error(line, &format!("3 operands for {}", name)));
+                }
+                // set only reads a, b can be anything or _
+                let ignored = |word: &str| if word == "_" { Ok(0) } else { asm.number(line, word).or_else(|_| asm.register(line, word)) };
+                let (a, b) = match (inst.ops, &inst.mnemonic[..3]) {
+                    (Operands::RegReg, "set") => (asm.register(line, &args[0])?, ignored(&args[1])?),
+                    (Operands::ImmReg, "set") => (asm.number(line, &args[0])?, ignored(&args[1])?),
+                    (Operands::RegReg, _) => (asm.register(line, &args[0])?, asm.register(line, &args[1])?),
+                    (Operands::RegImm, _) => (asm.register(line, &args[0])?, asm.number(line, &args[1])?),
+                    (Operands::ImmReg, _) => (asm.number(line, &args[0])?, asm.register(line, &args[1])?)
+                };
+                vec![(inst.mnemonic, [a, b, asm.register(line, &args[2])?])]
+            },
+            None => asm.lower(line, name, args, len)?
+        };
+        for (mnemonic, [a, b, c]) in instructions {
+            out.push_str(&format!("{} {} {} {}\n", mnemonic, a, b, c));
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    // day 19's program, sums the divisors of n
+    const DIVISORS: &str = "\
+#ip 2
+.reg n r5
+.reg i r1
+.reg j r3
+.scratch r4
+.const N 836
+
+        jmp setup
+start:  mov i, 1
+outer:  mov j, 1
+inner:  mulr i j r4
+        jne r4, n, skip
+        addr i r0 r0        ; a divisor
+skip:   addi j 1 j
+        jle j, n, inner
+        addi i 1 i
+        jle i, n, outer
+        halt
+setup:  mov n, N
+        jmp start
+";
+
+    #[test]
+    fn test_assemble() {
+        use super::assemble;
+        use crate::elfcode::Device;
+        let program = assemble::<6>("divisors.asm", DIVISORS).unwrap();
+        assert!(program.starts_with("#ip 2\nseti 16 0 2\nseti 1 0 1\nseti 1 0 3\nmulr 1 3 4\neqrr 4 5 4\naddr 4 2 2\nseti 7 0 2\n"), "{}", program);
+        let mut device = Device::<6>::load(19, &program).unwrap();
+        device.run();
+        // 836 = 4 * 11 * 19
+        assert_eq!(device.registers[0], 7 * 12 * 20);
+    }
+
+    #[test]
+    fn test_raw() {
+        use super::assemble;
+        // the plain format goes through as it is
+        let raw = "#ip 0\nseti 5 0 1\nseti 6 0 2\naddi 0 1 0\naddr 1 2 3\nsetr 1 0 0\nseti 8 0 4\nseti 9 0 5\n";
+        assert_eq!(assemble::<6>("raw.txt", raw).unwrap(), raw);
+    }
+
+    #[test]
+    fn test_jumps() {
+        use super::assemble;
+        use crate::elfcode::Device;
+        // sets a bit of r5 for each jump taken, comparing r0 against r1 and 7
+        let mut source = String::from("#ip 4\n.scratch r3\n");
+        let jumps = ["jz r0", "jnz r0", "jeq r0, r1", "jne r0, r1", "jgt r0, r1", "jle r0, r1", "jlt r0, r1", "jge r0, r1", "jeq r0, 7", "jgt r0, 7", "jlt r0, 7"];
+        for (i, jump) in jumps.iter().enumerate() {
+            source.push_str(&format!("{jump}, yes{i}\njmp next{i}\nyes{i}: bori r5 {} r5\nnext{i}:\n", 1 << i));
+        }
+        source.push_str("halt\n");
+        let program = assemble::<6>("jumps.asm", &source).unwrap();
+        for (a, b) in [(0, 0), (0, 3), (3, 0), (7, 7), (7, 9), (9, 2)] {
+            let mut device = Device::<6>::load(21, &program).unwrap();
+            device.registers[0] = a;
+            device.registers[1] = b;
+            device.run();
+            let expected = [a == 0, a != 0, a == b, a != b, a > b, a <= b, a < b, a >= b, a == 7, a > 7, a < 7];
+            let taken: Vec<bool> = (0..jumps.len()).map(|i| device.registers[5] & (1 << i) != 0).collect();
+            assert_eq!(taken, expected, "r0 {} r1 {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_macros() {
+        use super::assemble;
+        use crate::elfcode::Device;
+        // 1 + 2 + ... + n, used twice so its labels have to come out separate
+        let source = "\
+#ip 1
+.scratch r2
+.macro triangle n total c
+        mov c, 0
+again:  jge c, n, done
+        addi c 1 c
+        addr total c total
+        jmp again
+done:
+.end
+        seti 3 0 r3
+        triangle r3 r0 r4
+        seti 5 0 r3
+        triangle r3 r5 r4
+";
+        let program = assemble::<6>("macros.asm", source).unwrap();
+        let mut device = Device::<6>::load(19, &program).unwrap();
+        device.run();
+        assert_eq!(device.registers[0], 6);
+        assert_eq!(device.registers[5], 15);
+    }
+
+    #[test]
+    fn test_errors() {
+        use super::assemble;
+        let error = |source: &str| assemble::<6>("bad.asm", source).unwrap_err().to_string();
+        assert_eq!(error("seti 1 0 1\n"), "bad.asm line 1: expected `#ip N`, found `seti 1 0 1`");
+        assert_eq!(error("#ip 0\njmp nowhere\n"), "bad.asm line 2: expected `a number, constant or label, for nowhere`, found `jmp nowhere`");
+        assert_eq!(error("#ip 0\nx: seti 1 0 1\nx: seti 1 0 1\n"), "bad.asm line 3: expected `a name not already used, for x`, found `x: seti 1 0 1`");
+        assert_eq!(error("#ip 0\naddi 1 r2 1\n"), "bad.asm line 2: expected `a number, constant or label, for r2`, found `addi 1 r2 1`");
+        assert_eq!(error("#ip 0\naddi r7 1 1\n"), "bad.asm line 2: expected `a register, r0 to r5, ip or a .reg name, for r7`, found `addi r7 1 1`");
+        assert_eq!(error("#ip 0\nseti 0 0 1\nx: jz r1, x\n"), "bad.asm line 3: expected `.scratch naming a register for the comparison`, found `x: jz r1, x`");
+        assert_eq!(error("#ip 0\nstart: jmp start\n"), "bad.asm line 2: expected `a jump target after 0, ip moves on one after a jump`, found `start: jmp start`");
+        assert_eq!(error("#ip 0\n.macro twice x\naddi x 1 x\n"), "bad.asm line 2: expected `.end for this .macro`, found `.macro twice x`");
+        assert_eq!(error("#ip 0\njmp é\n"), "bad.asm line 2: expected `a number, constant or label, for é`, found `jmp é`");
+        assert_eq!(error("#ip 0\njmp é+1\n"), "bad.asm line 2: expected `a number, constant or label, for é+1`, found `jmp é+1`");
+        assert_eq!(error("#ip 0\nfoo r1 r2 r3\n"), "bad.asm line 2: expected `an instruction, macro or directive`, found `foo r1 r2 r3`");
+    }
+}
@@ -0,0 +1,298 @@
+//! A program decoded once into steps a single match runs. The registers and ip are kept
+//! in locals and written back when it stops, and nothing is allocated per instruction.
+//! With data breakpoints to check or elfcode=trace logging on it goes through
+//! `Device::execute` instead, which stays as the reference it's tested against.
+//!
+//! Each instruction knows its own address, so reads of the #ip register are compiled to
+//! that number and the register is only written for the instructions that jump, and
+//! when it stops.
+
+use std::time::Instant;
+
+use log::{log_enabled, Level};
+
+use super::optimize::Shortcut;
+use super::{instruction_set, Device, Operands};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+    Addr, Addi, Mulr, Muli, Banr, Bani, Borr, Bori,
+    Setr, Seti, Gtir, Gtri, Gtrr, Eqir, Eqri, Eqrr
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Step {
+    kind: Kind,
+    a: u64,
+    b: u64,
+    c: usize,
+    // writes the #ip register
+    jump: bool
+}
+
+#[derive(Clone, Debug)]
+pub struct Compiled<const REGS: usize> {
+    steps: Vec<Step>,
+    bound: Option<usize>,
+    // the device's shortcuts by the ip they start at
+    shortcuts: Vec<Option<Shortcut>>
+}
+
+#[derive(Clone, Copy)]
+enum Src {
+    Reg(u64),
+    Imm(u64)
+}
+
+impl<const REGS: usize> Compiled<REGS> {
+    pub fn new(device: &Device<REGS>) -> Compiled<REGS> {
+        let set = instruction_set();
+        let steps = device.program.iter().enumerate().map(|(ip, code)| {
+            let inst = &set[code[0] as usize];
+            // registers the device doesn't have read 0, like Device::reg
+            let src = |r: u32| match r as usize {
+                r if Some(r) == device.bound => Src::Imm(ip as u64),
+                r if r < REGS => Src::Reg(r as u64),
+                _ => Src::Imm(0)
+            };
+            let (a, b) = match inst.ops {
+                Operands::RegReg => (src(code[1]), src(code[2])),
+                Operands::RegImm => (src(code[1]), Src::Imm(code[2] as u64)),
+                Operands::ImmReg => (Src::Imm(code[1] as u64), src(code[2]))
+            };
+            // the mnemonic without the letters for where a and b come from
+            let name = match &inst.mnemonic[..2] {
+                "gt" | "eq" => &inst.mnemonic[..2],
+                _ => &inst.mnemonic[..3]
+            };
+            let (kind, a, b) = match (name, a, b) {
+                // no registers left to read, the answer is known
+                ("set", Src::Imm(a), _) => (Kind::Seti, a, 0),
+                (_, Src::Imm(a), Src::Imm(b)) => {
+                    let mut value = 0;
+                    (inst.op)(a, b, &mut value);
+                    (Kind::Seti, value, 0)
+                },
+                ("set", Src::Reg(a), _) => (Kind::Setr, a, 0),
+                // these don't mind which way round they are
+                ("add" | "mul" | "ban" | "bor", Src::Imm(a), Src::Reg(b)) => (kind(name, "ri"), b, a),
+                (_, Src::Reg(a), Src::Reg(b)) => (kind(name, "rr"), a, b),
+                (_, Src::Reg(a), Src::Imm(b)) => (kind(name, "ri"), a, b),
+                (_, Src::Imm(a), Src::Reg(b)) => (kind(name, "ir"), a, b)
+            };
+            Step { kind, a, b, c: code[3] as usize, jump: Some(code[3] as usize) == device.bound }
+        }).collect();
+        let mut shortcuts = vec![None; device.program.len()];
+        for shortcut in &device.shortcuts {
+            shortcuts[shortcut.start] = Some(*shortcut);
+        }
+        Compiled { steps, bound: device.bound, shortcuts }
+    }
+
+    // runs device from its ip until ip leaves the program, gets to stop or budget
+    // instructions have run, and says how many did. At least one runs, so stop can be
+    // where it starts. A shortcut counts as one.
+    pub fn run(&self, device: &mut Device<REGS>, stop: Option<u32>, budget: u64) -> u64 {
+        if !device.breakpoints.is_empty() || log_enabled!(Level::Trace) {
+            let mut count = 0;
+            while count < budget && (device.ip as usize) < device.program.len() {
+                device.execute_ip();
+                count += 1;
+                if stop == Some(device.ip) {
+                    break;
+                }
+            }
+            return count;
+        }
+        let mut r = device.registers;
+        let mut ip = device.ip;
+        let mut count = 0;
+        let bound = self.bound;
+        // whether the #ip register is behind, holding less than the last ip run
+        let mut behind = false;
+        while count < budget && (ip as usize) < self.steps.len() {
+            let at = ip as usize;
+            count += 1;
+            let shortcut = match (&self.shortcuts[at], bound) {
+                (Some(shortcut), Some(bound)) => shortcut.apply(&mut r, bound),
+                _ => None
+            };
+            if let Some(next) = shortcut {
+                ip = next;
+                behind = false;
+            } else {
+                let Step { kind, a, b, c, jump } = self.steps[at];
+                let value = match kind {
+                    Kind::Addr => r[a as usize].wrapping_add(r[b as usize]),
+                    Kind::Addi => r[a as usize].wrapping_add(b),
+                    Kind::Mulr => r[a as usize].wrapping_mul(r[b as usize]),
+                    Kind::Muli => r[a as usize].wrapping_mul(b),
+                    Kind::Banr => r[a as usize] & r[b as usize],
+                    Kind::Bani => r[a as usize] & b,
+                    Kind::Borr => r[a as usize] | r[b as usize],
+                    Kind::Bori => r[a as usize] | b,
+                    Kind::Setr => r[a as usize],
+                    Kind::Seti => a,
+                    Kind::Gtir => (a > r[b as usize]) as u64,
+                    Kind::Gtri => (r[a as usize] > b) as u64,
+                    Kind::Gtrr => (r[a as usize] > r[b as usize]) as u64,
+                    Kind::Eqir => (a == r[b as usize]) as u64,
+                    Kind::Eqri => (r[a as usize] == b) as u64,
+                    Kind::Eqrr => (r[a as usize] == r[b as usize]) as u64
+                };
+                r[c] = value;
+                // like execute, a jump past u32 stops at the last ip rather than going round
+                let next = if jump { u32::try_from(value).unwrap_or(u32::MAX) } else { ip };
+                ip = next.saturating_add(1);
+                behind = !jump;
+            }
+            if stop == Some(ip) {
+                break;
+            }
+        }
+        if let (Some(bound), true) = (bound, behind) {
+            r[bound] = ip as u64 - 1;
+        }
+        device.registers = r;
+        device.ip = ip;
+        count
+    }
+}
+
+fn kind(name: &str, ops: &str) -> Kind {
+    match (name, ops) {
+        ("add", "rr") => Kind::Addr,
+        ("add", _) => Kind::Addi,
+        ("mul", "rr") => Kind::Mulr,
+        ("mul", _) => Kind::Muli,
+        ("ban", "rr") => Kind::Banr,
+        ("ban", _) => Kind::Bani,
+        ("bor", "rr") => Kind::Borr,
+        ("bor", _) => Kind::Bori,
+        ("gt", "rr") => Kind::Gtrr,
+        ("gt", "ri") => Kind::Gtri,
+        ("gt", _) => Kind::Gtir,
+        ("eq", "rr") => Kind::Eqrr,
+        ("eq", "ri") => Kind::Eqri,
+        _ => Kind::Eqir
+    }
+}
+
+// instructions a second running device's program from where it is, for up to budget
+// instructions and without shortcuts: first a step at a time through Device::execute_ip,
+// then through Compiled::run
+pub fn speed<const REGS: usize>(device: &Device<REGS>, budget: u64) -> (f64, f64) {
+    let mut device = device.clone();
+    device.shortcuts.clear();
+    let mut stepped = device.clone();
+    let start = Instant::now();
+    let mut count = 0;
+    while count < budget && (stepped.ip as usize) < stepped.program.len() {
+        stepped.execute_ip();
+        count += 1;
+    }
+    let interpreted = count as f64 / start.elapsed().as_secs_f64();
+
+    let program = device.compile();
+    let start = Instant::now();
+    let count = program.run(&mut device, None, budget);
+    let compiled = count as f64 / start.elapsed().as_secs_f64();
+    (interpreted, compiled)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_same_as_execute() {
+        use crate::day19::generate;
+        use crate::elfcode::Device;
+        use crate::generate::{Knobs, Rng};
+        // every register and ip after every number of instructions, with and without
+        // the day 19 shortcut
+        let mut rng = Rng::new(5);
+        for _ in 0..5 {
+            let mut slow = Device::<6>::load(19, &generate(&Knobs { count: 10, extent: 2 }, &mut rng)).unwrap();
+            slow.registers[0] = 1;
+            let program = slow.compile();
+            let mut fast = slow.clone();
+            loop {
+                let running = slow.execute_ip();
+                assert_eq!(program.run(&mut fast, None, 1), 1);
+                assert_eq!((fast.registers, fast.ip), (slow.registers, slow.ip));
+                if !running {
+                    break;
+                }
+            }
+            assert_eq!(program.run(&mut fast, None, 1), 0);
+        }
+        for _ in 0..5 {
+            let mut slow = Device::<6>::load(19, &generate(&Knobs { count: 30, extent: 5 }, &mut rng)).unwrap();
+            slow.optimize();
+            let mut fast = slow.clone();
+            slow.run_interpreted();
+            fast.compile().run(&mut fast, None, u64::MAX);
+            assert_eq!((fast.registers, fast.ip), (slow.registers, slow.ip));
+        }
+    }
+
+    #[test]
+    fn test_random() {
+        use crate::elfcode::{instruction_set, Device};
+        use crate::generate::Rng;
+        // any instruction, reading ip and every register, step by step
+        let set = instruction_set();
+        let mut rng = Rng::new(3);
+        for _ in 0..200 {
+            let mut program = String::from("#ip 3\n");
+            for _ in 0..12 {
+                let inst = &set[rng.below(set.len())];
+                program.push_str(&format!("{} {} {} {}\n", inst.mnemonic, rng.below(6), rng.below(6), rng.below(6)));
+            }
+            let mut slow = Device::<6>::load(21, &program).unwrap();
+            for reg in slow.registers.iter_mut() {
+                *reg = rng.below(12) as u64;
+            }
+            let compiled = slow.compile();
+            let mut fast = slow.clone();
+            for _ in 0..50 {
+                let running = slow.execute_ip();
+                compiled.run(&mut fast, None, 1);
+                assert_eq!((fast.registers, fast.ip), (slow.registers, slow.ip), "{}", program);
+                if !running {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_jump_past_end() {
+        use crate::elfcode::Device;
+        let mut slow = Device::<6>::load(19, "#ip 0\nseti 4294967295 0 0\n").unwrap();
+        let mut fast = slow.clone();
+        slow.run_interpreted();
+        assert_eq!(fast.compile().run(&mut fast, None, u64::MAX), 1);
+        assert_eq!((fast.registers, fast.ip), (slow.registers, slow.ip));
+    }
+
+    #[test]
+    fn test_stop() {
+        use crate::elfcode::{DataBreakpoint, Device};
+        // counts r1 up from 0, around ip 1 to 3 until it passes 5
+        let mut device = Device::<6>::load(21, "#ip 5\nseti 0 0 1\naddi 1 1 1\ngtri 1 5 2\naddr 2 5 5\nseti 0 0 5\nseti 9 0 3\n").unwrap();
+        let program = device.compile();
+        // stopping where it starts still runs the one there
+        device.ip = 1;
+        assert_eq!(program.run(&mut device, Some(1), u64::MAX), 4);
+        assert_eq!((device.registers[1], device.ip), (1, 1));
+        assert_eq!(program.run(&mut device, None, 10), 10);
+        assert_eq!(program.run(&mut device, None, u64::MAX), 10);
+        assert_eq!((device.registers, device.ip), ([0, 6, 1, 9, 0, 5], 6));
+
+        // with a data breakpoint it goes through execute, which checks it
+        device.ip = 0;
+        device.breakpoints.push(DataBreakpoint { reg: 1, ip: 2, data: 3, write_reg: 1, write_val: 9, run_count: 1 });
+        device.run();
+        assert_eq!(device.registers[1], 9);
+    }
+}
@@ -0,0 +1,493 @@
+//! A debugger for the ElfCode device, driven by commands typed at a prompt or read from
+//! a script, one per line:
+//!
+//! ```text
+//! step [n]         s   run n instructions, 1 by default
+//! next             n   run until the instruction after this one, past any loop back
+//! continue         c   run to a breakpoint, a watched write, the end or the budget
+//! run [n]              run to the end ignoring breakpoints, within n instructions
+//! break <ip> [if <cond>] [then <command>; ...]
+//!                      stop before running ip when cond holds, or with then run the
+//!                      commands and carry on
+//! watch <reg>          stop after an instruction writes reg
+//! delete <n>           remove breakpoint or watchpoint n
+//! info                 list the breakpoints and watchpoints
+//! set <reg> <value>    set a register, or ip
+//! print [reg]      p   show the registers, or one of them
+//! list                 the instructions around ip
+//! budget <n>           the most instructions one command runs
+//! quit             q
+//! ```
+//!
+//! Registers are r0, r1, ... and ip. A condition compares registers and numbers, joined
+//! with &&, like `r5 == 10551355 && r0 > 0`. Breakpoints with commands replace a
+//! `DataBreakpoint`: `break 34 if r5 == 10551355 then set r5 10` is day 19's.
+
+use super::Device;
+
+use crate::error::{Error, Result};
+
+// about a second of instructions in a release build
+const BUDGET: u64 = 100_000_000;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Operand {
+    Reg(usize),
+    Ip,
+    Num(u64)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Cmp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Point {
+    Break { ip: u32, cond: Vec<(Operand, Cmp, Operand)>, text: String, commands: Vec<String> },
+    Watch(usize)
+}
+
+// why running stopped
+#[derive(Clone, Debug, PartialEq)]
+enum Stop {
+    Done,
+    Breakpoint(usize),
+    Watch { number: usize, reg: usize, old: u64, new: u64 },
+    Halted,
+    // ip is as far as it goes, so there's no instruction after it to stop at
+    Overflow,
+    Budget
+}
+
+pub struct Debugger<const REGS: usize> {
+    device: Device<REGS>,
+    // numbered from 1 in the order they're made, deleted ones leave a gap
+    points: Vec<Option<Point>>,
+    budget: u64,
+    // instructions run since loading
+    executed: u64,
+    // whether the breakpoints at ip have been looked at, so continuing from one
+    // doesn't stop at it again or run its commands twice
+    arrived: bool,
+    // what breakpoint commands printed while running
+    printed: String
+}
+
+const USAGE: &[(&str, &str)] = &[
+    ("step", "step [n]"),
+    ("next", "next"),
+    ("continue", "continue"),
+    ("run", "run [n]"),
+    ("break", "break <ip> [if <cond>] [then <command>; ...]"),
+    ("watch", "watch <reg>"),
+    ("delete", "delete <n>"),
+    ("set", "set <reg> <value>"),
+    ("print", "print [reg]"),
+    ("budget", "budget <n>")
+];
+
+fn usage(command: &str) -> String {
+    USAGE.iter().find(|(name, _)| *name == command).map(|(_, usage)| usage.to_string()).unwrap_or_else(|| String::from("step, next, continue, run, break, watch, delete, info, set, print, list, budget or quit"))
+}
+
+impl<const REGS: usize> Debugger<REGS> {
+    pub fn new(device: Device<REGS>) -> Debugger<REGS> {
+        Debugger { device, points: Vec::new(), budget: BUDGET, executed: 0, arrived: false, printed: String::new() }
+    }
+
+    pub fn device(&self) -> &Device<REGS> {
+        &self.device
+    }
+
+    fn operand(&self, word: &str) -> Option<Operand> {
+        match word {
+            "ip" => Some(Operand::Ip),
+            _ => match word.strip_prefix('r') {
+                Some(reg) => reg.parse().ok().filter(|reg| *reg < REGS).map(Operand::Reg),
+                None => word.parse().ok().map(Operand::Num)
+            }
+        }
+    }
+
+    fn value(&self, operand: Operand) -> u64 {
+        match operand {
+            Operand::Reg(reg) => self.device.registers[reg],
+            Operand::Ip => self.device.ip as u64,
+            Operand::Num(n) => n
+        }
+    }
+
+    fn condition(&self, text: &str) -> Option<Vec<(Operand, Cmp, Operand)>> {
+        text.split("&&").map(|part| {
+            let words: Vec<&str> = part.split_whitespace().collect();
+            let [a, cmp, b] = words[..] else { return None };
+            let cmp = match cmp {
+                "==" => Cmp::Eq,
+                "!=" => Cmp::Ne,
+                "<" => Cmp::Lt,
+                "<=" => Cmp::Le,
+                ">" => Cmp::Gt,
+                ">=" => Cmp::Ge,
+                _ => return None
+            };
+            Some((self.operand(a)?, cmp, self.operand(b)?))
+        }).collect()
+    }
+
+    fn holds(&self, cond: &[(Operand, Cmp, Operand)]) -> bool {
+        cond.iter().all(|&(a, cmp, b)| {
+            let (a, b) = (self.value(a), self.value(b));
+            match cmp {
+                Cmp::Eq => a == b,
+                Cmp::Ne => a != b,
+                Cmp::Lt => a < b,
+                Cmp::Le => a <= b,
+                Cmp::Gt => a > b,
+                Cmp::Ge => a >= b
+            }
+        })
+    }
+
+    fn halted(&self) -> bool {
+        self.device.ip as usize >= self.device.program.len()
+    }
+
+    // the registers and the instruction about to run
+    fn status(&self) -> String {
+        match self.device.program.get(self.device.ip as usize) {
+            Some(code) => {
+                let inst = &self.device.instructions[code[0] as usize];
+                format!("ip={} {:?} {} {} {} {}", self.device.ip, self.device.registers, inst.mnemonic, code[1], code[2], code[3])
+            },
+            None => format!("ip={} {:?} halted", self.device.ip, self.device.registers)
+        }
+    }
+
+    // runs until stop says to, or something else stops it. Stopping breakpoints and
+    // watchpoints only count with stops, the commands of the others always run.
+    fn advance(&mut self, stops: bool, until: impl Fn(&Device<REGS>, u64) -> bool) -> Result<Stop> {
+        let mut count = 0;
+        loop {
+            if self.halted() {
+                return Ok(Stop::Halted);
+            }
+            if !self.arrived {
+                self.arrived = true;
+                let ip = self.device.ip;
+                let hits: Vec<(usize, Vec<String>)> = self.points.iter().enumerate()
+                    .filter_map(|(i, point)| match point {
+                        Some(Point::Break { ip: at, cond, commands, .. }) if *at == ip && self.holds(cond) => Some((i + 1, commands.clone())),
+                        _ => None
+                    })
+                    .collect();
+                for (number, commands) in hits {
+                    if commands.is_empty() {
+                        if stops {
+                            return Ok(Stop::Breakpoint(number));
+                        }
+                        continue;
+                    }
+                    for command in commands {
+                        let output = self.command(&command)?.unwrap_or_default();
+                        self.printed.push_str(&output);
+                    }
+                }
+            }
+            if count == self.budget {
+                return Ok(Stop::Budget);
+            }
+            if count > 0 && until(&self.device, count) {
+                return Ok(Stop::Done);
+            }
+
+            let code = self.device.program[self.device.ip as usize];
+            let before = self.device.registers;
+            self.device.execute_ip();
+            self.executed += 1;
+            self.arrived = false;
+            count += 1;
+            let reg = code[3] as usize;
+            if stops {
+                let watched = self.points.iter().position(|point| *point == Some(Point::Watch(reg)));
+                if let Some(i) = watched {
+                    return Ok(Stop::Watch { number: i + 1, reg, old: before[reg], new: self.device.registers[reg] });
+                }
+            }
+        }
+    }
+
+    fn report(&mut self, stop: Stop) -> String {
+        let reason = match stop {
+            Stop::Done => String::new(),
+            Stop::Breakpoint(n) => format!("breakpoint {}\n", n),
+            Stop::Watch { number, reg, old, new } => format!("watchpoint {}: r{} {} -> {}\n", number, reg, old, new),
+            Stop::Halted => format!("halted after {} instructions\n", self.executed),
+            Stop::Overflow => format!("halted, there's no ip after {}\n", self.device.ip),
+            Stop::Budget => format!("stopped after the budget of {} instructions\n", self.budget)
+        };
+        format!("{}{}{}\n", std::mem::take(&mut self.printed), reason, self.status())
+    }
+
+    // runs one command and returns what it prints, or None to quit. A command that
+    // doesn't parse is an error with its usage as what was expected.
+    pub fn command(&mut self, line: &str) -> Result<Option<String>> {
+        let line = line.trim();
+        let (word, rest) = line.split_once(char::is_whitespace).map(|(w, r)| (w, r.trim())).unwrap_or((line, ""));
+        let args: Vec<&str> = rest.split_whitespace().collect();
+        let expected = |name: &str| Error::format("debugger", 0, line, &usage(name));
+        let count = |name: &str| match args[..] {
+            [] => Ok(1),
+            [n] => n.parse::<u64>().map_err(|_| expected(name)),
+            _ => Err(expected(name))
+        };
+
+        let output = match word {
+            "" => String::new(),
+            "quit" | "q" => return Ok(None),
+            "step" | "s" => {
+                let n = count("step")?;
+                let stop = self.advance(true, |_, done| done == n)?;
+                self.report(stop)
+            },
+            "next" | "n" if args.is_empty() => {
+                let stop = match self.device.ip.checked_add(1) {
+                    Some(after) => self.advance(true, |device, _| device.ip == after)?,
+                    None => Stop::Overflow
+                };
+                self.report(stop)
+            },
+            "continue" | "c" if args.is_empty() => {
+                let stop = self.advance(true, |_, _| false)?;
+                self.report(stop)
+            },
+            "run" => {
+                let budget = self.budget;
+                if !args.is_empty() {
+                    self.budget = count("run")?;
+                }
+                let stop = self.advance(false, |_, _| false);
+                let output = stop.map(|stop| self.report(stop));
+                self.budget = budget;
+                output?
+            },
+            "break" => {
+                let (rest, commands) = match rest.split_once(" then ") {
+                    Some((rest, commands)) => (rest, commands.split(';').map(|x| x.trim().to_string()).filter(|x| !x.is_empty()).collect()),
+                    None => (rest, Vec::new())
+                };
+                let (at, cond) = match rest.split_once(" if ") {
+                    Some((at, cond)) => (at, self.condition(cond).ok_or_else(|| expected("break"))?),
+                    None => (rest, Vec::new())
+                };
+                let ip = at.trim().parse::<u32>().map_err(|_| expected("break"))?;
+                // commands that run the device from inside a breakpoint would never end
+                if commands.iter().any(|x: &String| !x.starts_with("set ") && !x.starts_with("print") && x != "p" && !x.starts_with("p ")) {
+                    return Err(Error::format("debugger", 0, line, "then set or print commands"));
+                }
+                self.points.push(Some(Point::Break { ip, cond, text: rest.trim().to_string(), commands }));
+                format!("breakpoint {} at ip {}\n", self.points.len(), ip)
+            },
+            "watch" => match args[..] {
+                [reg] => match self.operand(reg) {
+                    Some(Operand::Reg(reg)) => {
+                        self.points.push(Some(Point::Watch(reg)));
+                        format!("watchpoint {} on r{}\n", self.points.len(), reg)
+                    },
+                    _ => return Err(expected("watch"))
+                },
+                _ => return Err(expected("watch"))
+            },
+            "delete" => {
+                let n = match args[..] {
+                    [n] => n.parse::<usize>().ok().filter(|n| (1..=self.points.len()).contains(n)).ok_or_else(|| expected("delete"))?,
+                    _ => return Err(expected("delete"))
+                };
+                self.points[n - 1] = None;
+                String::new()
+            },
+            "info" => {
+                let mut out = String::new();
+                for (i, point) in self.points.iter().enumerate() {
+                    match point {
+                        Some(Point::Break { text, commands, .. }) if commands.is_empty() => out.push_str(&format!("{}: break {}\n", i + 1, text)),
+                        Some(Point::Break { text, commands, .. }) => out.push_str(&format!("{}: break {} then {}\n", i + 1, text, commands.join("; "))),
+                        Some(Point::Watch(reg)) => out.push_str(&format!("{}: watch r{}\n", i + 1, reg)),
+                        None => {}
+                    }
+                }
+                out
+            },
+            "set" => match args[..] {
+                [target, value] => {
+                    let value = value.parse::<u64>().map_err(|_| expected("set"))?;
+                    match self.operand(target) {
+                        Some(Operand::Reg(reg)) => self.device.registers[reg] = value,
+                        Some(Operand::Ip) => {
+                            self.device.ip = u32::try_from(value).map_err(|_| expected("set"))?;
+                            self.arrived = false;
+                        },
+                        _ => return Err(expected("set"))
+                    }
+                    String::new()
+                },
+                _ => return Err(expected("set"))
+            },
+            "print" | "p" => match args[..] {
+                [] => format!("{}\n", self.status()),
+                [target] => match self.operand(target) {
+                    Some(operand @ (Operand::Reg(_) | Operand::Ip)) => format!("{} = {}\n", target, self.value(operand)),
+                    _ => return Err(expected("print"))
+                },
+                _ => return Err(expected("print"))
+            },
+            "list" => {
+                let ip = self.device.ip as usize;
+                let mut out = String::new();
+                for pc in ip.saturating_sub(3)..(ip + 4).min(self.device.program.len()) {
+                    let code = self.device.program[pc];
+                    let mark = if pc == ip { "=>" } else { "  " };
+                    out.push_str(&format!("{} {:>3}  {} {} {} {}\n", mark, pc, self.device.instructions[code[0] as usize].mnemonic, code[1], code[2], code[3]));
+                }
+                out
+            },
+            "budget" => match args[..] {
+                [n] => {
+                    self.budget = n.parse::<u64>().map_err(|_| expected("budget"))?;
+                    String::new()
+                },
+                _ => return Err(expected("budget"))
+            },
+            other => return Err(expected(other))
+        };
+        Ok(Some(output))
+    }
+
+    // runs the commands in text, stopping at quit or the first one that doesn't parse,
+    // which is reported against path and its line
+    pub fn script(&mut self, path: &str, text: &str) -> Result<(String, bool)> {
+        let mut out = String::new();
+        for (n, line) in text.lines().enumerate() {
+            if line.trim().starts_with('#') {
+                continue;
+            }
+            out.push_str(&format!("> {}\n", line.trim()));
+            match self.command(line) {
+                Ok(Some(output)) => out.push_str(&output),
+                Ok(None) => return Ok((out, true)),
+                Err(Error::Format { text, expected, .. }) => return Err(Error::format(path, n + 1, &text, &expected)),
+                Err(e) => return Err(e)
+            }
+        }
+        Ok((out, false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // sums the divisors of r5, 836 or with r0 set 846, like day 19's program
+    const DIVISORS: &str = "\
+#ip 2
+addi 2 16 2
+seti 1 0 1
+seti 1 8 3
+mulr 1 3 4
+eqrr 4 5 4
+addr 4 2 2
+addi 2 1 2
+addr 1 0 0
+addi 3 1 3
+gtrr 3 5 4
+addr 2 4 2
+seti 2 4 2
+addi 1 1 1
+gtrr 1 5 4
+addr 4 2 2
+seti 1 7 2
+mulr 2 2 2
+addi 5 2 5
+mulr 5 5 5
+mulr 2 5 5
+muli 5 11 5
+addr 2 0 2
+seti 0 0 2
+setr 2 3 4
+addi 5 10 5
+seti 0 0 2
+";
+
+    fn debugger() -> super::Debugger<6> {
+        super::Debugger::new(crate::elfcode::Device::load(19, DIVISORS).unwrap())
+    }
+
+    #[test]
+    fn test_stepping() {
+        let mut debugger = debugger();
+        assert_eq!(debugger.command("step").unwrap().unwrap(), "ip=17 [0, 0, 16, 0, 0, 0] addi 5 2 5\n");
+        assert_eq!(debugger.command("s 3").unwrap().unwrap(), "ip=20 [0, 0, 19, 0, 0, 76] muli 5 11 5\n");
+        assert_eq!(debugger.command("p r5").unwrap().unwrap(), "r5 = 76\n");
+        // over the inner loop, from its last instruction
+        debugger.command("set r5 6").unwrap();
+        debugger.command("set ip 11").unwrap();
+        debugger.command("set r1 2").unwrap();
+        assert_eq!(debugger.command("next").unwrap().unwrap(), "ip=12 [2, 2, 11, 7, 1, 6] addi 1 1 1\n");
+        assert_eq!(debugger.command("print ip").unwrap().unwrap(), "ip = 12\n");
+        debugger.command("set ip 4294967295").unwrap();
+        assert_eq!(debugger.command("next").unwrap().unwrap(), "halted, there's no ip after 4294967295\nip=4294967295 [2, 2, 11, 7, 1, 6] halted\n");
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        let mut debugger = debugger();
+        assert_eq!(debugger.command("break 7 if r1 == 2").unwrap().unwrap(), "breakpoint 1 at ip 7\n");
+        assert_eq!(debugger.command("watch r0").unwrap().unwrap(), "watchpoint 2 on r0\n");
+        assert_eq!(debugger.command("c").unwrap().unwrap(), "watchpoint 2: r0 0 -> 1\nip=8 [1, 1, 7, 836, 1, 836] addi 3 1 3\n");
+        assert_eq!(debugger.command("c").unwrap().unwrap(), "breakpoint 1\nip=7 [1, 2, 6, 418, 1, 836] addr 1 0 0\n");
+        assert_eq!(debugger.command("c").unwrap().unwrap(), "watchpoint 2: r0 1 -> 3\nip=8 [3, 2, 7, 418, 1, 836] addi 3 1 3\n");
+        debugger.command("delete 1").unwrap();
+        assert_eq!(debugger.command("info").unwrap().unwrap(), "2: watch r0\n");
+        debugger.command("delete 2").unwrap();
+        // the rest of the divisors of 10 from 3 on
+        debugger.command("set r5 10").unwrap();
+        assert!(debugger.command("c").unwrap().unwrap().starts_with("halted after "));
+        assert_eq!(debugger.device().registers[0], 3 + 5 + 10);
+    }
+
+    #[test]
+    fn test_budget() {
+        let mut debugger = debugger();
+        debugger.command("budget 1000").unwrap();
+        assert!(debugger.command("c").unwrap().unwrap().starts_with("stopped after the budget of 1000 instructions\nip="));
+        assert!(debugger.command("run 10").unwrap().unwrap().starts_with("stopped after the budget of 10 instructions\n"));
+        assert!(debugger.command("c").unwrap().unwrap().starts_with("stopped after the budget of 1000 instructions\n"));
+    }
+
+    #[test]
+    fn test_script() {
+        // day 19's trick without recompiling, the target made small before the loop starts
+        let script = "\
+# shrink the target
+set r0 1
+break 1 if r5 == 846 then set r5 10; print
+run
+quit
+step
+";
+        let mut debugger = debugger();
+        let (out, quit) = debugger.script("stomp.txt", script).unwrap();
+        assert!(quit);
+        assert!(out.starts_with("> set r0 1\n> break 1 if r5 == 846 then set r5 10; print\nbreakpoint 1 at ip 1\n> run\nip=1 [1, 0, 0, 0, 23, 10] seti 1 0 1\nhalted after "), "{}", out);
+        // 1 to start with, and 1 + 2 + 5 + 10
+        assert_eq!(debugger.device().registers[0], 19);
+
+        let error = debugger.script("stomp.txt", "set r0 1\nbreak 1 then continue\n").unwrap_err();
+        assert_eq!(error.to_string(), "stomp.txt line 2: expected `then set or print commands`, found `break 1 then continue`");
+        let error = debugger.script("stomp.txt", "set r9 1\n").unwrap_err();
+        assert_eq!(error.to_string(), "stomp.txt line 1: expected `set <reg> <value>`, found `set r9 1`");
+    }
+}
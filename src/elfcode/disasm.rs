@@ -0,0 +1,931 @@
+//! Reads an `#ip N` program back as something closer to the code it was compiled from.
+//! Writes to the bound register are jumps, the program is split into basic blocks at
+//! their targets, and the blocks are put back together as loops and ifs where the
+//! control flow nests, with gotos for what doesn't. There are three views of the result:
+//! an annotated listing, pseudo-code, and the control-flow graph as Graphviz DOT.
+//!
+//! `addr ip rN ip` is taken to skip the next instruction when rN is 1. rN is nearly
+//! always the result of a comparison; when it isn't, the listing says so.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{Code, Device, Operands};
+
+// past every block, for a region that runs to the end of the program
+const END: usize = usize::MAX;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Bin {
+    Mul,
+    Add,
+    And,
+    Or,
+    Gt,
+    Lt,
+    Ge,
+    Le,
+    Eq,
+    Ne
+}
+
+impl Bin {
+    fn symbol(self) -> &'static str {
+        match self {
+            Bin::Mul => "*",
+            Bin::Add => "+",
+            Bin::And => "&",
+            Bin::Or => "|",
+            Bin::Gt => ">",
+            Bin::Lt => "<",
+            Bin::Ge => ">=",
+            Bin::Le => "<=",
+            Bin::Eq => "==",
+            Bin::Ne => "!="
+        }
+    }
+
+    // as in Rust, comparisons bind loosest
+    fn precedence(self) -> u32 {
+        match self {
+            Bin::Mul => 5,
+            Bin::Add => 4,
+            Bin::And => 3,
+            Bin::Or => 2,
+            _ => 1
+        }
+    }
+
+    fn compares(self) -> bool {
+        self.precedence() == 1
+    }
+
+    fn negate(self) -> Bin {
+        match self {
+            Bin::Gt => Bin::Le,
+            Bin::Lt => Bin::Ge,
+            Bin::Ge => Bin::Lt,
+            Bin::Le => Bin::Gt,
+            Bin::Eq => Bin::Ne,
+            Bin::Ne => Bin::Eq,
+            op => op
+        }
+    }
+
+    // the same comparison with its operands swapped
+    fn flip(self) -> Bin {
+        match self {
+            Bin::Gt => Bin::Lt,
+            Bin::Lt => Bin::Gt,
+            Bin::Ge => Bin::Le,
+            Bin::Le => Bin::Ge,
+            op => op
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Expr {
+    Reg(usize),
+    Imm(u64),
+    Bin(Bin, Box<Expr>, Box<Expr>)
+}
+
+impl Expr {
+    // reads better with the register first, 256 > r3 is r3 < 256
+    fn bin(op: Bin, a: Expr, b: Expr) -> Expr {
+        match (&a, &b) {
+            (Expr::Imm(_), Expr::Reg(_) | Expr::Bin(..)) => Expr::Bin(op.flip(), Box::new(b), Box::new(a)),
+            _ => Expr::Bin(op, Box::new(a), Box::new(b))
+        }
+    }
+
+    fn reads(&self) -> u64 {
+        match self {
+            Expr::Reg(r) => 1 << r,
+            Expr::Imm(_) => 0,
+            Expr::Bin(_, a, b) => a.reads() | b.reads()
+        }
+    }
+
+    fn count(&self, reg: usize) -> usize {
+        match self {
+            Expr::Reg(r) => (*r == reg) as usize,
+            Expr::Imm(_) => 0,
+            Expr::Bin(_, a, b) => a.count(reg) + b.count(reg)
+        }
+    }
+
+    fn substitute(&self, reg: usize, with: &Expr) -> Expr {
+        match self {
+            Expr::Reg(r) if *r == reg => with.clone(),
+            Expr::Bin(op, a, b) => Expr::Bin(*op, Box::new(a.substitute(reg, with)), Box::new(b.substitute(reg, with))),
+            other => other.clone()
+        }
+    }
+
+    // as a condition, anything but 0 is true
+    fn truth(&self) -> Expr {
+        match self {
+            Expr::Bin(op, ..) if op.compares() => self.clone(),
+            _ => Expr::Bin(Bin::Ne, Box::new(self.clone()), Box::new(Expr::Imm(0)))
+        }
+    }
+
+    fn negate(&self) -> Expr {
+        match self.truth() {
+            Expr::Bin(op, a, b) => Expr::Bin(op.negate(), a, b),
+            other => other
+        }
+    }
+
+    fn render(&self, names: &[String]) -> String {
+        match self {
+            Expr::Reg(r) => names[*r].clone(),
+            Expr::Imm(v) => v.to_string(),
+            Expr::Bin(op, a, b) => {
+                // comparisons don't chain, and C puts & and | below them, so mixing those with
+                // anything gets brackets
+                let side = |e: &Expr, right: bool| match e {
+                    Expr::Bin(inner, ..) if inner.precedence() < op.precedence()
+                        || (inner.precedence() == op.precedence() && (right || op.compares()))
+                        || (op.compares() && matches!(inner, Bin::And | Bin::Or))
+                        || (matches!(op, Bin::And | Bin::Or) && inner != op) => format!("({})", e.render(names)),
+                    _ => e.render(names)
+                };
+                format!("{} {} {}", side(a, false), op.symbol(), side(b, true))
+            }
+        }
+    }
+}
+
+// what one instruction does, with reads of the bound register already the constant pc
+#[derive(Clone, Debug, PartialEq)]
+enum Effect {
+    Assign(usize, Expr),
+    // ip is set to the target, past the end halts
+    Goto(usize),
+    // skips the next instruction when the register is 1
+    Skip(usize),
+    // ip is set from the registers, to the value + 1
+    Jump(Expr)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Exit {
+    // a target past the end halts
+    Goto(usize),
+    // to the first target when the condition holds, else the second
+    Branch(Expr, usize, usize),
+    Jump(Expr)
+}
+
+impl Exit {
+    fn targets(&self) -> Vec<usize> {
+        match self {
+            Exit::Goto(t) => vec![*t],
+            Exit::Branch(_, then, other) => vec![*then, *other],
+            Exit::Jump(_) => Vec::new()
+        }
+    }
+
+    fn reads(&self) -> u64 {
+        match self {
+            Exit::Goto(_) => 0,
+            Exit::Branch(c, ..) | Exit::Jump(c) => c.reads()
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Block {
+    start: usize,
+    end: usize,
+    stmts: Vec<(usize, Expr)>,
+    exit: Exit
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Stmt {
+    Label(usize),
+    Assign(usize, Expr),
+    If(Expr, Vec<Stmt>, Vec<Stmt>),
+    Loop(Vec<Stmt>),
+    While(Expr, Vec<Stmt>),
+    DoWhile(Vec<Stmt>, Expr),
+    Break,
+    Continue,
+    Goto(usize),
+    Halt,
+    Jump(Expr)
+}
+
+pub struct Disassembly {
+    bound: Option<usize>,
+    names: Vec<String>,
+    code: Vec<(&'static str, Code)>,
+    effects: Vec<Effect>,
+    blocks: BTreeMap<usize, Block>
+}
+
+pub fn disassemble<const REGS: usize>(device: &Device<REGS>) -> Disassembly {
+    let bound = device.bound;
+    let len = device.program.len();
+    let mut code = Vec::new();
+    let mut effects = Vec::new();
+    for (pc, instruction) in device.program.iter().enumerate() {
+        let inst = &device.instructions[instruction[0] as usize];
+        code.push((inst.mnemonic, *instruction));
+        effects.push(effect(inst.mnemonic, inst.ops, inst.op, instruction, pc, bound));
+    }
+
+    let mut leaders = BTreeSet::from([0]);
+    for (pc, effect) in effects.iter().enumerate() {
+        match effect {
+            Effect::Assign(..) => continue,
+            Effect::Goto(t) => { leaders.insert(*t); },
+            Effect::Skip(_) => { leaders.insert(pc + 2); },
+            Effect::Jump(_) => {}
+        }
+        leaders.insert(pc + 1);
+    }
+    let leaders: Vec<usize> = leaders.into_iter().filter(|x| *x < len).collect();
+
+    let mut blocks = BTreeMap::new();
+    for (i, &start) in leaders.iter().enumerate() {
+        let end = leaders.get(i + 1).copied().unwrap_or(len);
+        let stmts = (start..end).filter_map(|pc| match &effects[pc] {
+            Effect::Assign(r, e) => Some((*r, e.clone())),
+            _ => None
+        }).collect();
+        let exit = match &effects[end - 1] {
+            Effect::Assign(..) => Exit::Goto(end),
+            Effect::Goto(t) => Exit::Goto(*t),
+            Effect::Skip(r) => Exit::Branch(Expr::Reg(*r), end + 1, end),
+            Effect::Jump(e) => Exit::Jump(e.clone())
+        };
+        blocks.insert(start, Block { start, end, stmts, exit });
+    }
+
+    let names = (0..REGS).map(|r| if Some(r) == bound { String::from("ip") } else { format!("r{}", r) }).collect();
+    Disassembly { bound, names, code, effects, blocks }
+}
+
+fn effect(mnemonic: &str, ops: Operands, op: super::Op, code: &Code, pc: usize, bound: Option<usize>) -> Effect {
+    let reg = |r: u32| if Some(r as usize) == bound { Expr::Imm(pc as u64) } else { Expr::Reg(r as usize) };
+    let (a, b) = match ops {
+        Operands::RegReg => (reg(code[1]), reg(code[2])),
+        Operands::RegImm => (reg(code[1]), Expr::Imm(code[2] as u64)),
+        Operands::ImmReg => (Expr::Imm(code[1] as u64), reg(code[2]))
+    };
+    let value = match (&mnemonic[..2], a, b) {
+        ("se", a, _) => a,
+        (_, Expr::Imm(x), Expr::Imm(y)) => {
+            let mut result = 0;
+            op(x, y, &mut result);
+            Expr::Imm(result)
+        },
+        ("ad", a, b) => Expr::bin(Bin::Add, a, b),
+        ("mu", a, b) => Expr::bin(Bin::Mul, a, b),
+        ("ba", a, b) => Expr::bin(Bin::And, a, b),
+        ("bo", a, b) => Expr::bin(Bin::Or, a, b),
+        ("gt", a, b) => Expr::bin(Bin::Gt, a, b),
+        (_, a, b) => Expr::bin(Bin::Eq, a, b)
+    };
+
+    let dest = code[3] as usize;
+    if Some(dest) != bound {
+        return Effect::Assign(dest, value);
+    }
+    let ip = bound.unwrap() as u32;
+    match value {
+        Expr::Imm(v) => Effect::Goto(v as usize + 1),
+        _ if mnemonic == "addr" && code[1] == ip => Effect::Skip(code[2] as usize),
+        _ if mnemonic == "addr" && code[2] == ip => Effect::Skip(code[1] as usize),
+        value => Effect::Jump(value)
+    }
+}
+
+impl Disassembly {
+    pub fn registers(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn rename(&mut self, reg: usize, name: &str) {
+        self.names[reg] = name.to_string();
+    }
+
+    fn assign(&self, reg: usize, value: &Expr) -> String {
+        let name = &self.names[reg];
+        match value {
+            Expr::Bin(op, a, b) if !op.compares() && **a == Expr::Reg(reg) => format!("{} {}= {}", name, op.symbol(), b.render(&self.names)),
+            Expr::Bin(op, a, b) if !op.compares() && **b == Expr::Reg(reg) => format!("{} {}= {}", name, op.symbol(), a.render(&self.names)),
+            _ => format!("{} = {}", name, value.render(&self.names))
+        }
+    }
+
+    fn target(&self, t: usize) -> String {
+        if t < self.code.len() { format!("goto {}", t) } else { format!("halt, ip {} is past the end", t) }
+    }
+
+    // where each block is jumped to or falls in from, by the pc of the instruction
+    fn predecessors(&self, blocks: &BTreeMap<usize, Block>) -> BTreeMap<usize, Vec<usize>> {
+        let mut preds: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for block in blocks.values() {
+            for t in block.exit.targets() {
+                if blocks.contains_key(&t) {
+                    preds.entry(t).or_default().push(block.start);
+                }
+            }
+        }
+        preds
+    }
+
+    pub fn listing(&self) -> String {
+        let preds = self.predecessors(&self.blocks);
+        let mut out = String::new();
+        if let Some(bound) = self.bound {
+            out.push_str(&format!("#ip {}\n", bound));
+        }
+        for block in self.blocks.values() {
+            let from: Vec<String> = preds.get(&block.start).into_iter().flatten().map(|p| (self.blocks[p].end - 1).to_string()).collect();
+            match (block.start, from.is_empty()) {
+                (0, _) => out.push_str("L0: entry\n"),
+                (start, true) => out.push_str(&format!("L{}: not jumped to\n", start)),
+                (start, false) => out.push_str(&format!("L{}: from {}\n", start, from.join(", ")))
+            }
+            for pc in block.start..block.end {
+                let (mnemonic, code) = &self.code[pc];
+                let text = match &self.effects[pc] {
+                    Effect::Assign(r, e) => self.assign(*r, e),
+                    Effect::Goto(t) => self.target(*t),
+                    Effect::Skip(r) => {
+                        let flag = pc > block.start && matches!(&self.effects[pc - 1], Effect::Assign(x, Expr::Bin(op, ..)) if x == r && op.compares());
+                        let note = if flag { "" } else { ", if it is 0 or 1" };
+                        format!("if {} {} else {}{}", self.names[*r], self.target(pc + 2), pc + 1, note)
+                    },
+                    Effect::Jump(e) => format!("goto {} + 1", e.render(&self.names))
+                };
+                let instruction = format!("{} {} {} {}", mnemonic, code[1], code[2], code[3]);
+                out.push_str(&format!("{:>4}  {:<18}{}\n", pc, instruction, text));
+            }
+        }
+        out
+    }
+
+    pub fn dot(&self) -> String {
+        let len = self.code.len();
+        let mut out = String::from("digraph elfcode {\n    node [shape=box, fontname=\"monospace\"];\n");
+        let (mut halts, mut jumps) = (false, false);
+        for block in self.blocks.values() {
+            let mut label = format!("L{}\\l", block.start);
+            for pc in block.start..block.end {
+                let (mnemonic, code) = &self.code[pc];
+                label.push_str(&format!("{:>4}  {} {} {} {}\\l", pc, mnemonic, code[1], code[2], code[3]));
+            }
+            out.push_str(&format!("    b{} [label=\"{}\"];\n", block.start, label));
+            let node = |t: usize, halts: &mut bool| if t < len { format!("b{}", t) } else { *halts = true; String::from("halt") };
+            match &block.exit {
+                Exit::Goto(t) => out.push_str(&format!("    b{} -> {};\n", block.start, node(*t, &mut halts))),
+                Exit::Branch(c, then, other) => {
+                    out.push_str(&format!("    b{} -> {} [label=\"{}\"];\n", block.start, node(*then, &mut halts), c.truth().render(&self.names)));
+                    out.push_str(&format!("    b{} -> {} [label=\"else\"];\n", block.start, node(*other, &mut halts)));
+                },
+                Exit::Jump(e) => {
+                    jumps = true;
+                    out.push_str(&format!("    b{} -> jump [label=\"{} + 1\", style=dashed];\n", block.start, e.render(&self.names)));
+                }
+            }
+        }
+        if halts {
+            out.push_str("    halt [shape=oval];\n");
+        }
+        if jumps {
+            out.push_str("    jump [shape=oval, label=\"computed jump\"];\n");
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    pub fn pseudocode(&self) -> String {
+        let blocks = self.fold(self.thread());
+        let mut structurer = Structurer::new(blocks, self.code.len());
+        let end = END;
+        let stmts = structurer.emit(0, end, end, None);
+        let mut out = String::new();
+        self.render(&stmts, 0, &structurer.gotos, &mut out);
+        out
+    }
+
+    // sends jumps to a block that is just a goto on to where it goes. Without computed
+    // jumps nothing can reach those blocks any more, and they're dropped.
+    fn thread(&self) -> BTreeMap<usize, Block> {
+        let len = self.code.len();
+        let trampoline = |t: usize| -> Option<usize> {
+            match self.blocks.get(&t) {
+                Some(block) if t != 0 && block.stmts.is_empty() => match block.exit {
+                    Exit::Goto(next) if next < len => Some(next),
+                    _ => None
+                },
+                _ => None
+            }
+        };
+        let resolve = |t: usize| {
+            let mut seen = BTreeSet::new();
+            let mut t = t;
+            while let Some(next) = trampoline(t) {
+                if !seen.insert(t) {
+                    break;
+                }
+                t = next;
+            }
+            t
+        };
+
+        let mut blocks = self.blocks.clone();
+        for block in blocks.values_mut() {
+            block.exit = match &block.exit {
+                Exit::Goto(t) => Exit::Goto(resolve(*t)),
+                Exit::Branch(c, then, other) => Exit::Branch(c.clone(), resolve(*then), resolve(*other)),
+                exit => exit.clone()
+            };
+        }
+        if blocks.values().any(|block| matches!(block.exit, Exit::Jump(_))) {
+            return blocks;
+        }
+        let targeted: BTreeSet<usize> = blocks.values()
+            .filter(|block| trampoline(block.start).is_none())
+            .flat_map(|block| block.exit.targets())
+            .collect();
+        blocks.retain(|&start, _| trampoline(start).is_none() || targeted.contains(&start));
+        blocks
+    }
+
+    // moves a value into the one place that reads it, when nothing else does, so a flag
+    // computed for a jump becomes the jump's condition
+    fn fold(&self, mut blocks: BTreeMap<usize, Block>) -> BTreeMap<usize, Block> {
+        let len = self.code.len();
+        let all = u64::MAX >> (64 - self.names.len());
+        // r0 is the answer to both puzzles, what's left in the others at the end isn't
+        let halt = 1;
+        let mut live_in: BTreeMap<usize, u64> = blocks.keys().map(|&start| (start, 0)).collect();
+        let live_out = |block: &Block, live_in: &BTreeMap<usize, u64>| -> u64 {
+            match &block.exit {
+                Exit::Jump(_) => all,
+                exit => exit.targets().iter().map(|t| if *t < len { live_in.get(t).copied().unwrap_or(all) } else { halt }).fold(0, |a, b| a | b)
+            }
+        };
+        loop {
+            let mut changed = false;
+            for block in blocks.values() {
+                let mut live = live_out(block, &live_in) | block.exit.reads();
+                for (r, e) in block.stmts.iter().rev() {
+                    live = (live & !(1 << r)) | e.reads();
+                }
+                if live_in[&block.start] != live {
+                    live_in.insert(block.start, live);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let outs: BTreeMap<usize, u64> = blocks.values().map(|block| (block.start, live_out(block, &live_in))).collect();
+        for block in blocks.values_mut() {
+            let out = outs[&block.start];
+            // whether reg is read after statement i before it's written again
+            let live_after = |block: &Block, i: usize, reg: usize| {
+                for (r, e) in &block.stmts[i + 1..] {
+                    if e.count(reg) > 0 {
+                        return true;
+                    }
+                    if *r == reg {
+                        return false;
+                    }
+                }
+                block.exit.reads() & (1 << reg) != 0 || out & (1 << reg) != 0
+            };
+            let mut i = 0;
+            while i < block.stmts.len() {
+                let (reg, value) = block.stmts[i].clone();
+                if i + 1 < block.stmts.len() {
+                    let (next, ref uses) = block.stmts[i + 1];
+                    if uses.count(reg) == 1 && (next == reg || !live_after(block, i + 1, reg)) {
+                        block.stmts[i + 1].1 = uses.substitute(reg, &value);
+                        block.stmts.remove(i);
+                        i = i.saturating_sub(1);
+                        continue;
+                    }
+                } else if out & (1 << reg) == 0 {
+                    let exit = match &block.exit {
+                        Exit::Branch(c, then, other) if c.count(reg) == 1 => Some(Exit::Branch(c.substitute(reg, &value), *then, *other)),
+                        Exit::Jump(e) if e.count(reg) == 1 => Some(Exit::Jump(e.substitute(reg, &value))),
+                        _ => None
+                    };
+                    if let Some(exit) = exit {
+                        block.exit = exit;
+                        block.stmts.remove(i);
+                        i = i.saturating_sub(1);
+                        continue;
+                    }
+                }
+                i += 1;
+            }
+        }
+        blocks
+    }
+
+    fn render(&self, stmts: &[Stmt], depth: usize, gotos: &BTreeSet<usize>, out: &mut String) {
+        let indent = "    ".repeat(depth);
+        for stmt in stmts {
+            match stmt {
+                Stmt::Label(pc) if gotos.contains(pc) => out.push_str(&format!("{}L{}:\n", indent, pc)),
+                Stmt::Label(_) => {},
+                Stmt::If(c, then, other) if other.is_empty() && then.len() == 1 && self.jump(&then[0]).is_some() => {
+                    out.push_str(&format!("{}if ({}) {}\n", indent, c.render(&self.names), self.jump(&then[0]).unwrap()));
+                },
+                Stmt::If(c, then, other) => {
+                    out.push_str(&format!("{}if ({}) {{\n", indent, c.render(&self.names)));
+                    self.render(then, depth + 1, gotos, out);
+                    if !other.is_empty() {
+                        out.push_str(&format!("{}}} else {{\n", indent));
+                        self.render(other, depth + 1, gotos, out);
+                    }
+                    out.push_str(&format!("{}}}\n", indent));
+                },
+                Stmt::Loop(body) => {
+                    out.push_str(&format!("{}loop {{\n", indent));
+                    self.render(body, depth + 1, gotos, out);
+                    out.push_str(&format!("{}}}\n", indent));
+                },
+                Stmt::While(c, body) => {
+                    out.push_str(&format!("{}while ({}) {{\n", indent, c.render(&self.names)));
+                    self.render(body, depth + 1, gotos, out);
+                    out.push_str(&format!("{}}}\n", indent));
+                },
+                Stmt::DoWhile(body, c) => {
+                    out.push_str(&format!("{}do {{\n", indent));
+                    self.render(body, depth + 1, gotos, out);
+                    out.push_str(&format!("{}}} while ({})\n", indent, c.render(&self.names)));
+                },
+                Stmt::Assign(r, e) => out.push_str(&format!("{}{}\n", indent, self.assign(*r, e))),
+                jump => out.push_str(&format!("{}{}\n", indent, self.jump(jump).unwrap()))
+            }
+        }
+    }
+
+    fn jump(&self, stmt: &Stmt) -> Option<String> {
+        match stmt {
+            Stmt::Break => Some(String::from("break")),
+            Stmt::Continue => Some(String::from("continue")),
+            Stmt::Goto(t) => Some(format!("goto L{}", t)),
+            Stmt::Halt => Some(String::from("halt")),
+            Stmt::Jump(e) => Some(format!("goto {} + 1", e.render(&self.names))),
+            _ => None
+        }
+    }
+}
+
+struct Structurer {
+    blocks: BTreeMap<usize, Block>,
+    len: usize,
+    preds: BTreeMap<usize, Vec<usize>>,
+    // the blocks that dominate each block, reachable ones only
+    dominators: BTreeMap<usize, BTreeSet<usize>>,
+    gotos: BTreeSet<usize>
+}
+
+impl Structurer {
+    fn new(blocks: BTreeMap<usize, Block>, len: usize) -> Structurer {
+        let mut preds: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for block in blocks.values() {
+            for t in block.exit.targets() {
+                if blocks.contains_key(&t) {
+                    preds.entry(t).or_default().push(block.start);
+                }
+            }
+        }
+
+        let mut reachable = BTreeSet::new();
+        let mut stack = vec![0];
+        while let Some(start) = stack.pop() {
+            if reachable.insert(start) {
+                stack.extend(blocks[&start].exit.targets().into_iter().filter(|t| blocks.contains_key(t)));
+            }
+        }
+        let mut dominators: BTreeMap<usize, BTreeSet<usize>> = reachable.iter().map(|&b| (b, reachable.clone())).collect();
+        dominators.insert(0, BTreeSet::from([0]));
+        loop {
+            let mut changed = false;
+            for &b in reachable.iter().filter(|&&b| b != 0) {
+                let mut common: Option<BTreeSet<usize>> = None;
+                for p in preds.get(&b).into_iter().flatten().filter(|p| reachable.contains(p)) {
+                    common = Some(match common {
+                        None => dominators[p].clone(),
+                        Some(common) => common.intersection(&dominators[p]).copied().collect()
+                    });
+                }
+                let mut doms = common.unwrap_or_default();
+                doms.insert(b);
+                if doms != dominators[&b] {
+                    dominators.insert(b, doms);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        // a block nothing jumps to is only reached by a computed jump, if at all
+        let gotos = blocks.keys().filter(|&&b| b != 0 && !preds.contains_key(&b)).copied().collect();
+        Structurer { blocks, len, preds, dominators, gotos }
+    }
+
+    fn next(&self, start: usize) -> usize {
+        self.blocks.range(start + 1..).next().map(|(&b, _)| b).unwrap_or(END)
+    }
+
+    // where the loop headed by start carries on after it, when start heads one that is a
+    // run of blocks in order
+    fn loop_follow(&self, start: usize) -> Option<usize> {
+        let latch = self.preds.get(&start)?.iter()
+            .filter(|&&p| p >= start && self.dominators.get(&p).is_some_and(|doms| doms.contains(&start)))
+            .max()?;
+        Some(self.next(*latch))
+    }
+
+    // the statement for going to t from the end of a block, none when it just carries on
+    fn jump(&mut self, t: usize, next: usize, end: usize, join: usize, lp: Option<(usize, usize)>) -> Option<Stmt> {
+        if t >= self.len {
+            return Some(Stmt::Halt);
+        }
+        match lp {
+            Some((header, _)) if t == header => return Some(Stmt::Continue),
+            Some((_, follow)) if t == follow => return Some(Stmt::Break),
+            _ => {}
+        }
+        if t == next || t == end || t == join {
+            return None;
+        }
+        self.gotos.insert(t);
+        Some(Stmt::Goto(t))
+    }
+
+    // the blocks from start up to end in order. A jump to join, or falling off the end,
+    // carries on after the region, and lp is the innermost loop as (header, follow).
+    fn emit(&mut self, start: usize, end: usize, join: usize, lp: Option<(usize, usize)>) -> Vec<Stmt> {
+        let mut out = Vec::new();
+        // falling out of a loop's body goes round again, it has to break to leave
+        let loop_end = lp.is_some_and(|(_, follow)| follow == end);
+        let mut cur = start;
+        while cur < end {
+            let header = cur == start && lp.is_some_and(|(header, _)| header == cur);
+            if !header {
+                if let Some(follow) = self.loop_follow(cur).filter(|&follow| follow <= end) {
+                    let body = self.emit(cur, follow, follow, Some((cur, follow)));
+                    out.push(Stmt::Label(cur));
+                    out.push(close_loop(body));
+                    if follow == end && loop_end {
+                        out.push(Stmt::Break);
+                    }
+                    cur = follow;
+                    continue;
+                }
+                out.push(Stmt::Label(cur));
+            }
+
+            let block = self.blocks[&cur].clone();
+            out.extend(block.stmts.iter().map(|(r, e)| Stmt::Assign(*r, e.clone())));
+            let next = self.next(cur);
+            match block.exit {
+                Exit::Goto(t) => out.extend(self.jump(t, next, end, join, lp)),
+                Exit::Jump(e) => out.push(Stmt::Jump(e)),
+                Exit::Branch(c, then, other) => {
+                    // the blocks up to the far target run when the branch falls through
+                    let body = if then == next && other != next {
+                        Some((c.truth(), other))
+                    } else if other == next && then != next {
+                        Some((c.negate(), then))
+                    } else {
+                        None
+                    };
+                    let inside = |t: usize| t < end || (t == end && !loop_end);
+                    if let Some((cond, far)) = body.filter(|&(_, far)| next < end && far > next && far < self.len && inside(far)) {
+                        // the then part jumping over what follows it is an else
+                        let last = *self.blocks.range(..far).next_back().unwrap().0;
+                        let join = match self.blocks[&last].exit {
+                            Exit::Goto(u) if u > far && u < self.len && inside(u) => u,
+                            _ => far
+                        };
+                        let then = self.emit(next, far, join, lp);
+                        let other = if join > far { self.emit(far, join, join, lp) } else { Vec::new() };
+                        out.push(Stmt::If(cond, then, other));
+                        cur = join;
+                        continue;
+                    }
+                    match (self.jump(then, next, end, join, lp), self.jump(other, next, end, join, lp)) {
+                        (Some(a), Some(b)) => {
+                            out.push(Stmt::If(c.truth(), vec![a], Vec::new()));
+                            out.push(b);
+                        },
+                        (Some(a), None) => out.push(Stmt::If(c.truth(), vec![a], Vec::new())),
+                        (None, Some(b)) => out.push(Stmt::If(c.negate(), vec![b], Vec::new())),
+                        (None, None) => {}
+                    }
+                }
+            }
+            cur = next;
+        }
+        out
+    }
+}
+
+// a continue that isn't inside an inner loop
+fn continues(stmts: &[Stmt]) -> bool {
+    stmts.iter().any(|stmt| match stmt {
+        Stmt::Continue => true,
+        Stmt::If(_, then, other) => continues(then) || continues(other),
+        _ => false
+    })
+}
+
+// a loop whose test is at the top or bottom is a while or do while, as long as nothing
+// in it continues
+fn close_loop(mut body: Vec<Stmt>) -> Stmt {
+    let n = body.len();
+    if n >= 2 && body[n - 1] == Stmt::Break && !continues(&body[..n - 2]) {
+        if let Stmt::If(c, then, other) = &body[n - 2] {
+            if then[..] == [Stmt::Continue] && other.is_empty() {
+                let c = c.clone();
+                body.truncate(n - 2);
+                return Stmt::DoWhile(body, c);
+            }
+        }
+    }
+    if body.last() == Some(&Stmt::Continue) {
+        body.pop();
+    }
+    if let Some(Stmt::If(c, then, other)) = body.last() {
+        if then[..] == [Stmt::Break] && other.is_empty() && !continues(&body[..body.len() - 1]) {
+            let c = c.negate();
+            body.pop();
+            return Stmt::DoWhile(body, c);
+        }
+    }
+    if let Some(Stmt::If(c, then, other)) = body.first() {
+        if then[..] == [Stmt::Break] && other.is_empty() && !continues(&body[1..]) {
+            let c = c.negate();
+            body.remove(0);
+            return Stmt::While(c, body);
+        }
+    }
+    Stmt::Loop(body)
+}
+
+#[cfg(test)]
+mod tests {
+    // the shape of day 19's program, summing the divisors of r5
+    const DIVISORS: &str = "\
+#ip 2
+addi 2 16 2
+seti 1 0 1
+seti 1 8 3
+mulr 1 3 4
+eqrr 4 5 4
+addr 4 2 2
+addi 2 1 2
+addr 1 0 0
+addi 3 1 3
+gtrr 3 5 4
+addr 2 4 2
+seti 2 4 2
+addi 1 1 1
+gtrr 1 5 4
+addr 4 2 2
+seti 1 7 2
+mulr 2 2 2
+addi 5 2 5
+mulr 5 5 5
+mulr 2 5 5
+muli 5 11 5
+addr 2 0 2
+seti 0 0 2
+setr 2 3 4
+addi 5 10 5
+seti 0 0 2
+";
+
+    #[test]
+    fn test_pseudocode() {
+        use super::disassemble;
+        use crate::elfcode::Device;
+        let mut disassembly = disassemble(&Device::<6>::load(19, DIVISORS).unwrap());
+        disassembly.rename(5, "n");
+        assert_eq!(disassembly.pseudocode(), "\
+goto L17
+L1:
+r1 = 1
+do {
+    r3 = 1
+    do {
+        if (r1 * r3 == n) {
+            r0 += r1
+        }
+        r3 += 1
+    } while (r3 <= n)
+    r1 += 1
+} while (r1 <= n)
+halt
+L17:
+n += 2
+n = n * n * 19 * 11
+if (r0 == 0) goto L1
+r4 = 23
+n += 10
+goto L1
+");
+    }
+
+    #[test]
+    fn test_listing() {
+        use super::disassemble;
+        use crate::elfcode::Device;
+        let listing = disassemble(&Device::<6>::load(19, DIVISORS).unwrap()).listing();
+        let lines: Vec<&str> = listing.lines().collect();
+        assert_eq!(lines[0], "#ip 2");
+        assert_eq!(lines[1], "L0: entry");
+        assert_eq!(lines[2], "   0  addi 2 16 2       goto 17");
+        assert!(lines.contains(&"L1: from 22, 25"));
+        assert!(lines.contains(&"   5  addr 4 2 2        if r4 goto 7 else 6"));
+        assert!(lines.contains(&"  16  mulr 2 2 2        halt, ip 257 is past the end"));
+        assert!(lines.contains(&"  21  addr 2 0 2        if r0 goto 23 else 22, if it is 0 or 1"));
+    }
+
+    #[test]
+    fn test_loops() {
+        use super::disassemble;
+        use crate::elfcode::Device;
+        // day 21's check that bitwise and works, then its divide by 256
+        let program = "\
+#ip 1
+seti 123 0 4
+bani 4 456 4
+eqri 4 72 4
+addr 4 1 1
+seti 0 0 1
+seti 0 2 5
+addi 5 1 2
+muli 2 256 2
+gtrr 2 3 2
+addr 2 1 1
+addi 1 1 1
+seti 13 3 1
+addi 5 1 5
+seti 5 0 1
+setr 5 6 3
+";
+        assert_eq!(disassemble(&Device::<6>::load(21, program).unwrap()).pseudocode(), "\
+r4 = 123
+do {
+    r4 = (r4 & 456) == 72
+} while (r4 == 0)
+r5 = 0
+while ((r5 + 1) * 256 <= r3) {
+    r5 += 1
+}
+r3 = r5
+halt
+");
+    }
+
+    #[test]
+    fn test_generated() {
+        use super::disassemble;
+        use crate::day19::{generate, Device};
+        use crate::generate::{Knobs, Rng};
+        // whichever registers the program uses, both loops come back
+        for seed in 0..20 {
+            let program = generate(&Knobs { count: 20, extent: 3 }, &mut Rng::new(seed));
+            let pseudocode = disassemble(&Device::load(19, &program).unwrap()).pseudocode();
+            assert_eq!(pseudocode.matches("} while (").count(), 2, "seed {}:\n{}", seed, pseudocode);
+        }
+    }
+
+    #[test]
+    fn test_dot() {
+        use super::disassemble;
+        use crate::elfcode::Device;
+        let dot = disassemble(&Device::<6>::load(19, DIVISORS).unwrap()).dot();
+        assert!(dot.starts_with("digraph elfcode {\n"));
+        assert!(dot.contains("    b3 -> b7 [label=\"r4 != 0\"];\n"));
+        assert!(dot.contains("    b3 -> b6 [label=\"else\"];\n"));
+        assert!(dot.contains("    b16 -> halt;\n"));
+        assert!(dot.ends_with("    halt [shape=oval];\n}\n"));
+
+        let computed = disassemble(&Device::<6>::load(19, "#ip 0\naddi 1 2 1\nsetr 1 0 0\n").unwrap());
+        assert!(computed.dot().contains("    b0 -> jump [label=\"r1 + 1\", style=dashed];\n"));
+        assert_eq!(computed.pseudocode(), "r1 += 2\ngoto r1 + 1\n");
+    }
+}
@@ -0,0 +1,335 @@
+//! The ElfCode virtual machine shared by days 16, 19 and 21: sixteen opcodes over a
+//! small register file, optionally with the instruction pointer bound to a register.
+
+pub mod asm;
+pub mod compiled;
+pub mod debugger;
+pub mod disasm;
+pub mod optimize;
+
+use log::{debug, log_enabled, trace, Level};
+
+use crate::error::{Error, Result};
+use crate::format::Format;
+
+pub type Op = fn(u64, u64, &mut u64);
+
+// an instruction in a program, opcode a b c
+pub type Code = [u32; 4];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Operands {
+    RegReg,
+    RegImm,
+    ImmReg,
+}
+
+#[derive(Clone, Debug)]
+pub struct Instruction {
+    pub mnemonic: &'static str,
+    pub opcode: u32,
+    pub op: Op,
+    pub ops: Operands
+}
+
+// add and mul wrap rather than panic. Registers are 64 bits so the puzzles' own numbers
+// never get that far: day 21 masks to 24 bits after each multiply, and day 19's
+// i * j stays below n²
+fn add(a: u64, b: u64, c: &mut u64) {
+    *c = a.wrapping_add(b);
+}
+
+fn mul(a: u64, b: u64, c: &mut u64) {
+    *c = a.wrapping_mul(b);
+}
+
+fn and(a: u64, b: u64, c: &mut u64) {
+    *c = a & b;
+}
+
+fn or(a: u64, b: u64, c: &mut u64) {
+    *c = a | b;
+}
+
+fn set(a: u64, _b: u64, c: &mut u64) {
+    *c = a;
+}
+
+fn gt(a: u64, b: u64, c: &mut u64) {
+    if a > b { *c = 1; } else { *c = 0 }
+}
+
+fn eq(a: u64, b: u64, c: &mut u64) {
+    if a == b { *c = 1; } else { *c = 0 }
+}
+
+// every instruction, sorted by the opcodes worked out on day 16
+pub fn instruction_set() -> Vec<Instruction> {
+    let mut instructions = vec![
+        Instruction { mnemonic: "addr", opcode: 13, op: add, ops: Operands::RegReg },
+        Instruction { mnemonic: "addi", opcode: 10, op: add, ops: Operands::RegImm },
+        Instruction { mnemonic: "mulr", opcode: 14, op: mul, ops: Operands::RegReg },
+        Instruction { mnemonic: "muli", opcode: 5, op: mul, ops: Operands::RegImm },
+        Instruction { mnemonic: "banr", opcode: 0, op: and, ops: Operands::RegReg },
+        Instruction { mnemonic: "bani", opcode: 6, op: and, ops: Operands::RegImm },
+        Instruction { mnemonic: "borr", opcode: 7, op: or, ops: Operands::RegReg },
+        Instruction { mnemonic: "bori", opcode: 4, op: or, ops: Operands::RegImm },
+        Instruction { mnemonic: "setr", opcode: 2, op: set, ops: Operands::RegReg },
+        Instruction { mnemonic: "seti", opcode: 15, op: set, ops: Operands::ImmReg },
+        Instruction { mnemonic: "gtir", opcode: 8, op: gt, ops: Operands::ImmReg },
+        Instruction { mnemonic: "gtri", opcode: 11, op: gt, ops: Operands::RegImm },
+        Instruction { mnemonic: "gtrr", opcode: 9, op: gt, ops: Operands::RegReg },
+        Instruction { mnemonic: "eqir", opcode: 3, op: eq, ops: Operands::ImmReg },
+        Instruction { mnemonic: "eqri", opcode: 12, op: eq, ops: Operands::RegImm },
+        Instruction { mnemonic: "eqrr", opcode: 1, op: eq, ops: Operands::RegReg },
+    ];
+    instructions.sort_by_key(|a| a.opcode);
+    instructions
+}
+
+// data breakpoint w/write
+// substitutes for actual debugger twiddling since i don't have one with Intellij rust plugin
+#[derive(Clone, Debug)]
+pub struct DataBreakpoint {
+    pub reg: u32,
+    pub ip: u32,
+    pub data: u64,
+    pub write_reg: u32,
+    pub write_val: u64,
+    // negative runs forever
+    pub run_count: i32
+}
+
+#[derive(Clone, Debug)]
+pub struct Device<const REGS: usize> {
+    pub registers: [u64; REGS],
+    // indexed by opcode
+    pub instructions: Vec<Instruction>,
+    pub ip: u32,
+    // the register the instruction pointer is bound to by #ip
+    pub bound: Option<usize>,
+    pub program: Vec<Code>,
+    pub breakpoints: Vec<DataBreakpoint>,
+    // loops run natively, see optimize
+    pub shortcuts: Vec<optimize::Shortcut>
+}
+
+impl<const REGS: usize> Default for Device<REGS> {
+    fn default() -> Self {
+        Device {
+            registers: [0u64; REGS],
+            instructions: instruction_set(),
+            ip: 0,
+            bound: None,
+            program: Vec::new(),
+            breakpoints: Vec::new(),
+            shortcuts: Vec::new()
+        }
+    }
+}
+
+impl<const REGS: usize> Device<REGS> {
+    // loads a program in the #ip N format, errors are reported against day
+    pub fn load(day: u32, input: &str) -> Result<Device<REGS>> {
+        let mut device = Device::default();
+        let bind = Format::new("#ip {reg}");
+        let instruction = Format::new("{op} {a} {b} {c}");
+
+        for (lc, line) in input.lines().enumerate() {
+            if lc == 0 {
+                let (reg,) = bind.parse(day, 1, line)?;
+                if reg >= REGS {
+                    return Err(Error::parse(day, 1, line, &format!("a register below {}", REGS)));
+                }
+                device.bound = Some(reg);
+            } else {
+                let (op, b, c, d): (String, u32, u32, u32) = instruction.parse(day, lc + 1, line)?;
+                let opcode = device.get_opcode(&op).ok_or_else(|| Error::parse_at(day, lc + 1, line, 0, "an opcode"))?;
+                let code = [opcode, b, c, d];
+                if let Some(n) = device.registers_read(&code).into_iter().chain([3]).find(|&n| code[n] as usize >= REGS) {
+                    // the column of the nth word
+                    let column = line.split(' ').take(n).map(|word| word.len() + 1).sum();
+                    return Err(Error::parse_at(day, lc + 1, line, column, &format!("a register below {}", REGS)));
+                }
+                device.program.push(code);
+            }
+        }
+        if device.program.is_empty() {
+            return Err(Error::parse_input(day, input, "#ip N followed by a program"));
+        }
+        Ok(device)
+    }
+
+    pub fn get_opcode(&self, mnemonic: &str) -> Option<u32> {
+        self.instructions.iter().find(|inst| inst.mnemonic == mnemonic).map(|inst| inst.opcode)
+    }
+
+    // which of code's operands, 1 for a and 2 for b, are registers it reads. setr and
+    // seti ignore b, which needn't be a register
+    fn registers_read(&self, code: &Code) -> Vec<usize> {
+        let inst = &self.instructions[code[0] as usize];
+        match (inst.ops, inst.mnemonic) {
+            (_, "setr") | (Operands::RegImm, _) => vec![1],
+            (_, "seti") => vec![],
+            (Operands::RegReg, _) => vec![1, 2],
+            (Operands::ImmReg, _) => vec![2]
+        }
+    }
+
+    // day 16 tries every opcode on its samples, where a register can be out of range
+    fn reg(&self, r: u32) -> u64 {
+        self.registers.get(r as usize).copied().unwrap_or(0)
+    }
+
+    // runs one instruction against the registers, ignoring the instruction pointer
+    pub fn apply(&mut self, inst: &Instruction, code: &Code) {
+        let (a, b) = match inst.ops {
+            Operands::RegReg => (self.reg(code[1]), self.reg(code[2])),
+            Operands::RegImm => (self.reg(code[1]), code[2] as u64),
+            Operands::ImmReg => (code[1] as u64, self.reg(code[2])),
+        };
+        (inst.op)(a, b, &mut self.registers[code[3] as usize]);
+    }
+
+    pub fn execute(&mut self, code: &Code) {
+        // data breakpoints before execute
+        for breakpoint in &mut self.breakpoints {
+            if breakpoint.run_count != 0 && breakpoint.ip == self.ip && breakpoint.data == self.registers[breakpoint.reg as usize] {
+                debug!("hit breakpoint on ip: {} reg: {} data: {} write_reg: {}", breakpoint.ip, breakpoint.reg, breakpoint.data, breakpoint.write_reg);
+                self.registers[breakpoint.write_reg as usize] = breakpoint.write_val;
+                if breakpoint.run_count > 0 {
+                    breakpoint.run_count -= 1;
+                }
+            }
+        }
+
+        if let Some(bound) = self.bound {
+            self.registers[bound] = self.ip as u64;
+        }
+        let before = self.registers;
+        let inst = self.instructions[code[0] as usize].clone();
+        self.apply(&inst, code);
+        // every instruction as it executes, with the registers before and after
+        if log_enabled!(Level::Trace) {
+            trace!("ip={} {:?} {} {} {} {} {:?}", self.ip, before, inst.mnemonic, code[1], code[2], code[3], self.registers);
+        }
+        if let Some(bound) = self.bound {
+            // no program is long enough to get to an ip past u32, it stops there like past
+            // the end of one that isn't
+            self.ip = u32::try_from(self.registers[bound]).unwrap_or(u32::MAX);
+        }
+        self.ip = self.ip.saturating_add(1);
+    }
+
+    // finds the loops it can run natively, only with the ip bound to a register
+    pub fn optimize(&mut self) {
+        if let Some(bound) = self.bound {
+            self.shortcuts = optimize::find::<REGS>(&self.program, bound);
+            debug!("shortcuts: {:?}", self.shortcuts);
+        }
+    }
+
+    // runs the loop starting at ip natively if there is one, and no data breakpoint
+    // inside it that would be skipped
+    fn shortcut(&mut self) -> bool {
+        let ip = self.ip as usize;
+        let Some(shortcut) = self.shortcuts.iter().find(|s| s.start == ip) else {
+            return false;
+        };
+        if self.breakpoints.iter().any(|b| (ip..ip + shortcut.len).contains(&(b.ip as usize))) {
+            return false;
+        }
+        let before = self.registers;
+        match shortcut.apply(&mut self.registers, self.bound.unwrap()) {
+            Some(next) => {
+                trace!("ip={} {:?} {:?} {:?}", ip, before, shortcut.idiom, self.registers);
+                self.ip = next;
+                true
+            },
+            None => false
+        }
+    }
+
+    // executes the instruction at ip, false once ip has left the program
+    pub fn execute_ip(&mut self) -> bool {
+        if !self.shortcuts.is_empty() && self.shortcut() {
+            return (self.ip as usize) < self.program.len();
+        }
+        match self.program.get(self.ip as usize) {
+            Some(&code) => {
+                self.execute(&code);
+                (self.ip as usize) < self.program.len()
+            },
+            None => false
+        }
+    }
+
+    // decodes the program for Compiled::run, along with the shortcuts found so far
+    pub fn compile(&self) -> compiled::Compiled<REGS> {
+        compiled::Compiled::new(self)
+    }
+
+    pub fn run(&mut self) {
+        self.compile().run(self, None, u64::MAX);
+    }
+
+    // a step at a time through execute
+    pub fn run_interpreted(&mut self) {
+        while self.execute_ip() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = "\
+#ip 0
+seti 5 0 1
+seti 6 0 2
+addi 0 1 0
+addr 1 2 3
+setr 1 0 0
+seti 8 0 4
+seti 9 0 5
+";
+
+    #[test]
+    fn test_run() {
+        use super::Device;
+        let mut device = Device::<6>::load(19, EXAMPLE).unwrap();
+        device.run();
+        assert_eq!(device.registers, [6, 5, 6, 0, 0, 9]);
+        assert_eq!(device.ip, 7);
+    }
+
+    #[test]
+    fn test_unbound() {
+        use super::Device;
+        let mut device = Device::<4>::default();
+        let addi = device.get_opcode("addi").unwrap();
+        let mulr = device.get_opcode("mulr").unwrap();
+        device.program = vec![[addi, 0, 7, 1], [mulr, 1, 1, 2]];
+        device.run();
+        assert_eq!(device.registers, [0, 7, 49, 0]);
+    }
+
+    #[test]
+    fn test_jump_past_end() {
+        use super::Device;
+        let mut device = Device::<6>::load(19, "#ip 0\nseti 4294967295 0 0\n").unwrap();
+        device.run_interpreted();
+        assert_eq!(device.ip, u32::MAX);
+    }
+
+    #[test]
+    fn test_load_errors() {
+        use super::Device;
+        assert_eq!(Device::<6>::load(19, "#ip 6\nseti 5 0 1\n").unwrap_err().to_string(), "day 19 line 1: expected `a register below 6`, found `#ip 6`");
+        assert_eq!(Device::<6>::load(19, "#ip 1\nseti 5 0\n").unwrap_err().to_string(), "day 19 line 2 column 9: expected `{c}`, found ``");
+        assert_eq!(Device::<6>::load(21, "#ip 0\nnope 5 0 1\n").unwrap_err().to_string(), "day 21 line 2 column 1: expected `an opcode`, found `nope 5 0 1`");
+        // registers are checked for the operands each instruction reads, and c
+        assert_eq!(Device::<6>::load(19, "#ip 0\nseti 5 0 6\n").unwrap_err().to_string(), "day 19 line 2 column 10: expected `a register below 6`, found `6`");
+        assert_eq!(Device::<6>::load(19, "#ip 0\naddr 1 12 0\n").unwrap_err().to_string(), "day 19 line 2 column 8: expected `a register below 6`, found `12 0`");
+        assert_eq!(Device::<6>::load(19, "#ip 0\ngtir 7 9 0\n").unwrap_err().to_string(), "day 19 line 2 column 8: expected `a register below 6`, found `9 0`");
+        assert!(Device::<6>::load(19, "#ip 0\nseti 99 99 0\nsetr 1 99 0\naddi 1 99 0\ngtir 99 1 0\n").is_ok());
+    }
+}
@@ -0,0 +1,331 @@
+//! A peephole pass that finds the loops days 19 and 21 spend their time in and runs them
+//! natively. Each idiom is a pattern over the instructions, matched whatever registers
+//! the input uses:
+//!
+//! - the nested counting loops day 19 sums the divisors of n with, O(n²) instructions
+//! - day 21's division by 256, counting q up until (q + 1) * 256 passes x
+//!
+//! A shortcut starts where the loop's setup does and only fires when ip gets there, so
+//! jumping into the middle of one runs the instructions as usual. It leaves every
+//! register as the loop would have, ip included.
+
+use super::{instruction_set, Code};
+
+// pattern words: _ anything, =N the number N, @N the address of the pattern's start plus
+// N, a lowercase name a register, an uppercase name a number. p is the #ip register.
+const DIVISOR_SUM: [&str; 15] = [
+    "seti F _ i",
+    "seti =1 _ j",
+    "mulr i j t",
+    "eqrr t n t",
+    "addr t p p",
+    "addi p =1 p",
+    "addr i s s",
+    "addi j =1 j",
+    "gtrr j n t",
+    "addr p t p",
+    "seti @1 _ p",
+    "addi i =1 i",
+    "gtrr i n t",
+    "addr t p p",
+    "seti @0 _ p",
+];
+
+const DIVIDE: [&str; 9] = [
+    "seti =0 _ q",
+    "addi q =1 t",
+    "muli t D t",
+    "gtrr t x t",
+    "addr t p p",
+    "addi p =1 p",
+    "seti @8 _ p",
+    "addi q =1 q",
+    "seti @0 _ p",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Idiom {
+    // s += the divisors of n from first up, leaving i and j past n and t set
+    DivisorSum { n: usize, i: usize, j: usize, t: usize, s: usize, first: u32 },
+    // q = x / divisor, leaving t set
+    Divide { x: usize, q: usize, t: usize, divisor: u32 }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Shortcut {
+    pub start: usize,
+    pub len: usize,
+    pub idiom: Idiom
+}
+
+// what the pattern's names stand for so far
+type Bindings = Vec<(char, u32)>;
+
+fn bind(bindings: &mut Bindings, name: char, value: u32) -> bool {
+    match bindings.iter().find(|(n, _)| *n == name) {
+        Some((_, bound)) => *bound == value,
+        None => {
+            bindings.push((name, value));
+            true
+        }
+    }
+}
+
+fn operand(word: &str, value: u32, start: usize, bindings: &mut Bindings) -> bool {
+    let name = word.chars().next().unwrap();
+    match name {
+        '_' => true,
+        '=' => word[1..].parse() == Ok(value),
+        '@' => word[1..].parse::<usize>().is_ok_and(|n| start + n == value as usize),
+        _ => bind(bindings, name, value)
+    }
+}
+
+// the bindings for pattern at start, trying both orders of the operands of the
+// instructions where order doesn't matter
+fn matches(program: &[Code], start: usize, pattern: &[&str], n: usize, mnemonics: &[&str], bindings: Bindings) -> Option<Bindings> {
+    let Some(line) = pattern.get(n) else {
+        return Some(bindings);
+    };
+    let code = program.get(start + n)?;
+    let words: Vec<&str> = line.split(' ').collect();
+    if mnemonics[code[0] as usize] != words[0] {
+        return None;
+    }
+    let commutes = matches!(words[0], "addr" | "mulr" | "banr" | "borr" | "eqrr");
+    let orders: &[[usize; 3]] = if commutes { &[[1, 2, 3], [2, 1, 3]] } else { &[[1, 2, 3]] };
+    orders.iter().find_map(|order| {
+        let mut bindings = bindings.clone();
+        let ok = words[1..].iter().zip(order).all(|(word, &i)| operand(word, code[i], start, &mut bindings));
+        if ok { matches(program, start, pattern, n + 1, mnemonics, bindings) } else { None }
+    })
+}
+
+// the registers named in a match, as long as they're all different registers and p is
+// the #ip one
+fn registers<const N: usize, const REGS: usize>(bindings: &Bindings, bound: usize, names: [char; N]) -> Option<[usize; N]> {
+    let regs: Vec<usize> = bindings.iter().filter(|(name, _)| name.is_lowercase()).map(|(_, reg)| *reg as usize).collect();
+    let distinct = regs.iter().enumerate().all(|(i, reg)| *reg < REGS && !regs[..i].contains(reg));
+    let get = |name: char| bindings.iter().find(|(n, _)| *n == name).map(|(_, value)| *value as usize);
+    if !distinct || get('p') != Some(bound) {
+        return None;
+    }
+    let mut out = [0; N];
+    for (reg, name) in out.iter_mut().zip(names) {
+        *reg = get(name)?;
+    }
+    Some(out)
+}
+
+// the loops in program it knows how to run natively
+pub fn find<const REGS: usize>(program: &[Code], bound: usize) -> Vec<Shortcut> {
+    let mnemonics: Vec<&str> = instruction_set().iter().map(|inst| inst.mnemonic).collect();
+    let mut shortcuts = Vec::new();
+    let mut start = 0;
+    while start < program.len() {
+        let divisor_sum = matches(program, start, &DIVISOR_SUM, 0, &mnemonics, Vec::new())
+            .and_then(|b| {
+                let first = b.iter().find(|(name, _)| *name == 'F')?.1;
+                registers::<5, REGS>(&b, bound, ['n', 'i', 'j', 't', 's']).map(|[n, i, j, t, s]| Idiom::DivisorSum { n, i, j, t, s, first })
+            })
+            .map(|idiom| (DIVISOR_SUM.len(), idiom));
+        let divide = || matches(program, start, &DIVIDE, 0, &mnemonics, Vec::new())
+            .and_then(|b| {
+                let divisor = b.iter().find(|(name, _)| *name == 'D')?.1;
+                registers::<3, REGS>(&b, bound, ['x', 'q', 't']).map(|[x, q, t]| Idiom::Divide { x, q, t, divisor })
+            })
+            .map(|idiom| (DIVIDE.len(), idiom));
+        match divisor_sum.or_else(divide) {
+            Some((len, idiom)) => {
+                shortcuts.push(Shortcut { start, len, idiom });
+                start += len;
+            },
+            None => start += 1
+        }
+    }
+    shortcuts
+}
+
+impl Shortcut {
+    // runs the loop on registers, giving the ip it leaves at, or None when it has to be
+    // left to the instructions (it would never finish, or the device would wrap where
+    // this doesn't)
+    pub fn apply(&self, registers: &mut [u64], bound: usize) -> Option<u32> {
+        let end = (self.start + self.len) as u32;
+        match self.idiom {
+            Idiom::DivisorSum { n, i, j, t, s, first } => {
+                let (sum, last) = divisor_sum(first as u64, registers[n])?;
+                registers[s] = registers[s].wrapping_add(sum);
+                registers[i] = last + 1;
+                registers[j] = registers[n].max(1) + 1;
+                registers[t] = 1;
+                registers[bound] = end as u64 - 1;
+            },
+            Idiom::Divide { x, q, t, divisor } => {
+                let value = registers[x];
+                let divisor = divisor as u64;
+                // no products that wrap, and one that gets past x
+                if divisor == 0 || value.checked_add(divisor).is_none() {
+                    return None;
+                }
+                registers[q] = value / divisor;
+                registers[t] = 1;
+                registers[bound] = end as u64 - 1;
+            }
+        }
+        Some(end)
+    }
+}
+
+// what the divisor sum loop adds to s for n, and the last i it runs, or None when an
+// i * j could wrap around to n, which the loop would take forever over anyway. Like the
+// loop, i runs at least once and j at least once for each i.
+fn divisor_sum(first: u64, n: u64) -> Option<(u64, u64)> {
+    let last = first.max(n);
+    let most = n.max(1);
+    if last as u128 * most as u128 > u64::MAX as u128 {
+        return None;
+    }
+    let mut sum = 0u64;
+    let mut d = 1;
+    while d * d <= n {
+        if n.is_multiple_of(d) {
+            let pair = [d, n / d];
+            let count = if d * d == n { 1 } else { 2 };
+            for divisor in pair[..count].iter().filter(|x| (first..=last).contains(x)) {
+                sum = sum.wrapping_add(*divisor);
+            }
+        }
+        d += 1;
+    }
+    Some((sum, last))
+}
+
+#[cfg(test)]
+mod tests {
+    // day 21's division, with its registers, after something to jump over
+    const DIVIDE: &str = "\
+#ip 1
+seti 5 0 2
+seti 0 5 4
+addi 4 1 5
+muli 5 256 5
+gtrr 5 2 5
+addr 5 1 1
+addi 1 1 1
+seti 9 2 1
+addi 4 1 4
+seti 1 8 1
+setr 4 2 2
+";
+
+    #[test]
+    fn test_find() {
+        use super::{find, Idiom, Shortcut};
+        use crate::day19::generate;
+        use crate::elfcode::Device;
+        use crate::generate::{Knobs, Rng};
+        let device = Device::<6>::load(21, DIVIDE).unwrap();
+        assert_eq!(find::<6>(&device.program, 1), vec![Shortcut { start: 1, len: 9, idiom: Idiom::Divide { x: 2, q: 4, t: 5, divisor: 256 } }]);
+        // bound to another register it's not the same loop
+        assert!(find::<6>(&device.program, 3).is_empty());
+        let mut rng = Rng::new(7);
+        for _ in 0..10 {
+            let device = Device::<6>::load(19, &generate(&Knobs { count: 20, extent: 3 }, &mut rng)).unwrap();
+            let shortcuts = find::<6>(&device.program, device.bound.unwrap());
+            assert_eq!(shortcuts.len(), 1);
+            assert!(matches!(shortcuts[0], Shortcut { start: 1, len: 15, idiom: Idiom::DivisorSum { .. } }));
+        }
+    }
+
+    #[test]
+    fn test_same_registers() {
+        use crate::day19::generate;
+        use crate::elfcode::Device;
+        use crate::generate::{Knobs, Rng};
+        // with and without the shortcuts, every register ends up the same
+        let mut rng = Rng::new(11);
+        for _ in 0..10 {
+            let program = generate(&Knobs { count: 30, extent: 5 }, &mut rng);
+            for reg0 in [0, 1] {
+                let mut slow = Device::<6>::load(19, &program).unwrap();
+                slow.registers[0] = reg0;
+                let mut fast = slow.clone();
+                fast.optimize();
+                slow.run();
+                fast.run();
+                assert_eq!((fast.registers, fast.ip), (slow.registers, slow.ip), "{}", program);
+            }
+        }
+        for x in [0, 5, 255, 256, 257, 1000, 65535, 16777215] {
+            let program = DIVIDE.replace("seti 5 0 2", &format!("seti {} 0 2", x));
+            let mut slow = Device::<6>::load(21, &program).unwrap();
+            let mut fast = slow.clone();
+            fast.optimize();
+            slow.run();
+            fast.run();
+            assert_eq!((fast.registers, fast.ip), (slow.registers, slow.ip), "{}", x);
+            assert_eq!(fast.registers[2], x / 256);
+        }
+    }
+
+    #[test]
+    fn test_large() {
+        use crate::elfcode::Device;
+        // day 19's number and a division the device would have to count 2^24 times for
+        let mut device = Device::<6>::load(21, &DIVIDE.replace("seti 5 0 2", "seti 4294967039 0 2")).unwrap();
+        device.optimize();
+        device.run();
+        assert_eq!(device.registers[2], 16777214);
+    }
+
+    // the divisor sum loop with i starting at first, the way the device runs it
+    fn brute_force(first: u64, n: u64) -> (u64, u64) {
+        let (mut sum, mut i) = (0u64, first);
+        loop {
+            let mut j = 1u64;
+            loop {
+                if i.wrapping_mul(j) == n {
+                    sum = sum.wrapping_add(i);
+                }
+                j += 1;
+                if j > n {
+                    break;
+                }
+            }
+            i += 1;
+            if i > n {
+                return (sum, i - 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_divisor_sum() {
+        use super::divisor_sum;
+        for (first, n) in [(1, 0), (0, 0), (1, 1), (1, 12), (5, 12), (20, 12), (1, 5040), (65500, 65535), (65530, 65536), (66040, 66054), (65890, 65928), (131000, 131073)] {
+            assert_eq!(divisor_sum(first, n), Some(brute_force(first, n)), "{} {}", first, n);
+        }
+        // the largest n where i * j can't get to 2^64
+        assert_eq!(divisor_sum(1, u32::MAX as u64), Some((7304603328, u32::MAX as u64)));
+        assert_eq!(divisor_sum(1, 1 << 32), None);
+        assert_eq!(divisor_sum(1, u64::MAX), None);
+    }
+
+    #[test]
+    fn test_no_wrapping() {
+        use crate::elfcode::Device;
+        // 66050 * 65027 is 66054 mod 2^32, which registers as wide as 32 bits would have
+        // counted though 66050 doesn't divide it. i starts near n so the device can get
+        // through it too.
+        let sum = "#ip 2\nseti 16 0 2\nseti 66050 0 1\nseti 1 0 3\nmulr 1 3 4\neqrr 4 5 4\naddr 4 2 2\naddi 2 1 2\naddr 1 0 0\naddi 3 1 3\ngtrr 3 5 4\naddr 2 4 2\nseti 2 0 2\naddi 1 1 1\ngtrr 1 5 4\naddr 4 2 2\nseti 1 0 2\nmulr 2 2 2\nseti 66054 0 5\nseti 0 0 2\n";
+        let mut slow = Device::<6>::load(19, sum).unwrap();
+        let mut fast = slow.clone();
+        fast.optimize();
+        assert_eq!(fast.shortcuts.len(), 1);
+        slow.run();
+        fast.run();
+        assert_eq!((fast.registers, fast.ip), (slow.registers, slow.ip));
+        assert_eq!(fast.registers[0], 66054);
+    }
+}
@@ -0,0 +1,153 @@
+//! Error type shared by every day's parser and solver, so the runner can report
+//! bad input instead of panicking.
+
+use std::fmt;
+use std::io;
+use std::time::Duration;
+
+#[derive(Debug)]
+pub enum Error {
+    // the input couldn't be read at all
+    Io { path: String, source: io::Error },
+    // no input file was found for the day in any of the searched places
+    NotFound { day: u32, searched: Vec<String> },
+    // a line of the input didn't match what the day expects, line is 1 based and
+    // column, when known, is the byte offset into the line where it went wrong
+    Parse { day: u32, line: usize, column: Option<usize>, text: String, expected: String },
+    // the input parsed but the solver couldn't find an answer
+    Solve { day: u32, reason: String },
+    // the solver ran past the runner's time limit for a part
+    Timeout { day: u32, limit: Duration },
+    // a line of one of our own data files, like answers.txt, is malformed
+    Format { path: String, line: usize, text: String, expected: String },
+    // a stored input doesn't look like what was downloaded, cut short or with its line
+    // endings changed
+    Corrupt { path: String, problem: String },
+    // the puzzle site couldn't be reached or turned the request down
+    Http { url: String, reason: String },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn io(path: &str, source: io::Error) -> Error {
+        Error::Io { path: path.to_string(), source }
+    }
+
+    pub fn not_found(day: u32, searched: Vec<String>) -> Error {
+        Error::NotFound { day, searched }
+    }
+
+    pub fn parse(day: u32, line: usize, text: &str, expected: &str) -> Error {
+        Error::Parse { day, line, column: None, text: text.to_string(), expected: expected.to_string() }
+    }
+
+    pub fn parse_at(day: u32, line: usize, text: &str, column: usize, expected: &str) -> Error {
+        Error::Parse { day, line, column: Some(column), text: text.to_string(), expected: expected.to_string() }
+    }
+
+    pub fn solve(day: u32, reason: &str) -> Error {
+        Error::Solve { day, reason: reason.to_string() }
+    }
+
+    pub fn timeout(day: u32, limit: Duration) -> Error {
+        Error::Timeout { day, limit }
+    }
+
+    pub fn format(path: &str, line: usize, text: &str, expected: &str) -> Error {
+        Error::Format { path: path.to_string(), line, text: text.to_string(), expected: expected.to_string() }
+    }
+
+    pub fn corrupt(path: &str, problem: &str) -> Error {
+        Error::Corrupt { path: path.to_string(), problem: problem.to_string() }
+    }
+
+    pub fn http(url: &str, reason: &str) -> Error {
+        Error::Http { url: url.to_string(), reason: reason.to_string() }
+    }
+
+    // for input that is a single value or line, the error is reported against line 1
+    pub fn parse_input(day: u32, text: &str, expected: &str) -> Error {
+        Error::parse(day, 1, text.lines().next().unwrap_or(""), expected)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(f, "couldn't read {}: {}", path, source),
+            Error::NotFound { day, searched } => {
+                write!(f, "no input for day {}, searched:", day)?;
+                for place in searched {
+                    write!(f, "\n  {}", place)?;
+                }
+                Ok(())
+            },
+            Error::Parse { day, line, column: None, text, expected } =>
+                write!(f, "day {} line {}: expected `{}`, found `{}`", day, line, expected, text),
+            // columns are shown 1 based like lines, and only the text from there on
+            Error::Parse { day, line, column: Some(column), text, expected } => {
+                let rest = text.get(*column..).unwrap_or("");
+                write!(f, "day {} line {} column {}: expected `{}`, found `{}`", day, line, column + 1, expected, rest)
+            },
+            Error::Solve { day, reason } => write!(f, "day {}: {}", day, reason),
+            Error::Timeout { day, limit } => write!(f, "day {}: gave up after {:?}", day, limit),
+            Error::Format { path, line, text, expected } =>
+                write!(f, "{} line {}: expected `{}`, found `{}`", path, line, expected, text),
+            Error::Corrupt { path, problem } => write!(f, "{} {}", path, problem),
+            Error::Http { url, reason } => write!(f, "couldn't fetch {}: {}", url, reason),
+        }
+    }
+}
+
+// io::Error can't be cloned, the copy keeps its kind and message
+impl Clone for Error {
+    fn clone(&self) -> Error {
+        match self {
+            Error::Io { path, source } => Error::Io { path: path.clone(), source: io::Error::new(source.kind(), source.to_string()) },
+            Error::NotFound { day, searched } => Error::NotFound { day: *day, searched: searched.clone() },
+            Error::Parse { day, line, column, text, expected } =>
+                Error::Parse { day: *day, line: *line, column: *column, text: text.clone(), expected: expected.clone() },
+            Error::Solve { day, reason } => Error::Solve { day: *day, reason: reason.clone() },
+            Error::Timeout { day, limit } => Error::Timeout { day: *day, limit: *limit },
+            Error::Format { path, line, text, expected } =>
+                Error::Format { path: path.clone(), line: *line, text: text.clone(), expected: expected.clone() },
+            Error::Corrupt { path, problem } => Error::Corrupt { path: path.clone(), problem: problem.clone() },
+            Error::Http { url, reason } => Error::Http { url: url.clone(), reason: reason.clone() },
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_display() {
+        use super::Error;
+        assert_eq!(Error::parse(6, 3, "1,", "X, Y").to_string(), "day 6 line 3: expected `X, Y`, found `1,`");
+        assert_eq!(Error::parse_at(3, 1, "#1 @ 3,a: 5x4", 7, "Y").to_string(), "day 3 line 1 column 8: expected `Y`, found `a: 5x4`");
+        assert_eq!(Error::not_found(1, vec![String::from("inputs/day01.txt"), String::from("examples/day01_*.txt")]).to_string(),
+                   "no input for day 1, searched:\n  inputs/day01.txt\n  examples/day01_*.txt");
+        assert_eq!(Error::solve(15, "no attack power lets the elves win").to_string(), "day 15: no attack power lets the elves win");
+        assert_eq!(Error::format("answers.txt", 4, "19 x", "DAY PART INPUT ANSWER").to_string(),
+                   "answers.txt line 4: expected `DAY PART INPUT ANSWER`, found `19 x`");
+        assert_eq!(Error::timeout(24, std::time::Duration::from_secs(30)).to_string(), "day 24: gave up after 30s");
+    }
+
+    #[test]
+    fn test_clone() {
+        use super::Error;
+        use std::io;
+        let error = Error::io("inputs/day01.txt", io::Error::new(io::ErrorKind::PermissionDenied, "denied"));
+        assert_eq!(error.clone().to_string(), error.to_string());
+        assert!(matches!(error.clone(), Error::Io { source, .. } if source.kind() == io::ErrorKind::PermissionDenied));
+    }
+}
@@ -0,0 +1,166 @@
+//! Downloads puzzle inputs from the Advent of Code site, or from whatever AOC_BASE_URL
+//! points at. Tests point it at `mock`, a server on localhost, so nothing but the
+//! command line ever talks to the real site.
+//!
+//! Inputs differ by user, so every request carries the session cookie from the site,
+//! taken from AOC_SESSION.
+
+use std::env;
+use std::time::Duration;
+
+use ureq::Agent;
+
+use crate::error::{Error, Result};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+// the site asks automated tools to say what they are
+const USER_AGENT: &str = concat!("adventofcode2018/", env!("CARGO_PKG_VERSION"), " (aoc fetch)");
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: Agent
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Client {
+        let config = Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .user_agent(USER_AGENT)
+            .build();
+        Client { base_url: base_url.trim_end_matches('/').to_string(), session: session.to_string(), agent: config.into() }
+    }
+
+    pub fn from_env() -> Result<Client> {
+        let session = env::var(SESSION_VAR)
+            .map_err(|_| Error::http(BASE_URL, &format!("set {} to the session cookie from the site", SESSION_VAR)))?;
+        let base_url = env::var(BASE_URL_VAR).unwrap_or_else(|_| String::from(BASE_URL));
+        Ok(Client::new(&base_url, session.trim()))
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    // GET or, with a form, POST to path on the site. Returns the status and the body.
+    pub fn request(&self, path: &str, form: Option<&str>) -> Result<(u16, String)> {
+        let url = self.url(path);
+        let cookie = format!("session={}", self.session);
+        let response = match form {
+            Some(form) => self.agent.post(&url)
+                .header("Cookie", &cookie)
+                .header("Content-Type", "application/x-www-form-urlencoded")
+                .send(form),
+            None => self.agent.get(&url).header("Cookie", &cookie).call()
+        };
+        let mut response = response.map_err(|e| Error::http(&url, &e.to_string()))?;
+        let status = response.status().as_u16();
+        let body = response.body_mut().read_to_string().map_err(|e| Error::http(&url, &e.to_string()))?;
+        Ok((status, body))
+    }
+
+    pub fn input(&self, year: u32, day: u32) -> Result<String> {
+        let path = format!("/{}/day/{}/input", year, day);
+        let (status, body) = self.request(&path, None)?;
+        let reason = match status {
+            200 if body.is_empty() => String::from("the input was empty"),
+            200 => return Ok(body),
+            // the site answers 400 when the cookie is missing or has expired
+            400 | 401 | 403 => String::from("the session cookie was turned down, it may have expired"),
+            404 => String::from("no such puzzle, or it isn't unlocked yet"),
+            _ => format!("the server answered {}", status)
+        };
+        Err(Error::http(&self.url(&path), &reason))
+    }
+}
+
+// a stand in for the site on localhost, for tests
+#[cfg(test)]
+pub mod mock {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    // one request as the server saw it
+    #[derive(Debug)]
+    pub struct Request {
+        pub method: String,
+        pub path: String,
+        pub cookie: Option<String>,
+        pub body: String
+    }
+
+    // answers one connection per response, in order, then stops and hands back the
+    // requests it got
+    pub fn serve(responses: Vec<(u16, &str)>) -> (String, JoinHandle<Vec<Request>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let responses: Vec<(u16, String)> = responses.into_iter().map(|(status, body)| (status, body.to_string())).collect();
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let mut parts = line.split_whitespace();
+                let (method, path) = (parts.next().unwrap().to_string(), parts.next().unwrap().to_string());
+                let (mut cookie, mut length) = (None, 0);
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    let header = header.trim_end();
+                    if header.is_empty() {
+                        break;
+                    }
+                    let (name, value) = header.split_once(':').unwrap();
+                    match name.to_ascii_lowercase().as_str() {
+                        "cookie" => cookie = Some(value.trim().to_string()),
+                        "content-length" => length = value.trim().parse().unwrap(),
+                        _ => {}
+                    }
+                }
+                let mut request_body = vec![0; length];
+                reader.read_exact(&mut request_body).unwrap();
+                requests.push(Request { method, path, cookie, body: String::from_utf8(request_body).unwrap() });
+                write!(stream, "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, body.len(), body).unwrap();
+            }
+            requests
+        });
+        (base_url, handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_input() {
+        use super::mock::serve;
+        use super::Client;
+        let (base_url, server) = serve(vec![(200, "+1\n-2\n"), (404, "Not Found"), (400, "Puzzle inputs differ by user."), (200, "")]);
+        let client = Client::new(&format!("{}/", base_url), "abc123");
+        assert_eq!(client.input(2018, 1).unwrap(), "+1\n-2\n");
+        assert!(client.input(2018, 26).unwrap_err().to_string().ends_with("/2018/day/26/input: no such puzzle, or it isn't unlocked yet"));
+        assert!(client.input(2018, 2).unwrap_err().to_string().contains("session cookie"));
+        assert!(client.input(2018, 3).unwrap_err().to_string().contains("empty"));
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/2018/day/1/input");
+        assert_eq!(requests[0].cookie.as_deref(), Some("session=abc123"));
+    }
+
+    #[test]
+    fn test_unreachable() {
+        use super::Client;
+        use std::net::TcpListener;
+        // a port that was free a moment ago, nothing is listening on it now
+        let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let client = Client::new(&format!("http://127.0.0.1:{}", port), "abc123");
+        assert!(client.input(2018, 1).unwrap_err().to_string().starts_with("couldn't fetch http://127.0.0.1:"));
+    }
+}
@@ -0,0 +1,290 @@
+//! Line formats described once as a pattern, like `#{id} @ {x},{y}: {w}x{h}`.
+//!
+//! Text in the pattern has to appear as written, except that a space matches any run of
+//! whitespace. A `{name}` field takes everything up to where the following text next
+//! matches, or the rest of the line for the last field, with whitespace around it
+//! trimmed. When a line doesn't fit, the error points at the column where it went wrong.
+
+use std::any;
+use std::str::FromStr;
+
+use crate::error::Error;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Piece {
+    Text(String),
+    Field(String)
+}
+
+#[derive(Clone, Debug)]
+pub struct Format {
+    pattern: String,
+    pieces: Vec<Piece>
+}
+
+// where and why a line didn't fit, column is a byte offset into the line
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mismatch {
+    pub column: usize,
+    pub expected: String
+}
+
+impl Mismatch {
+    // for a mismatch in the text of a field, moves it to where the field starts in the line
+    pub fn within(self, column: usize) -> Mismatch {
+        Mismatch { column: column + self.column, ..self }
+    }
+
+    pub fn error(&self, day: u32, line_no: usize, line: &str) -> Error {
+        Error::parse_at(day, line_no, line, self.column, &self.expected)
+    }
+}
+
+// the text of each field of a line that fitted
+#[derive(Clone, Debug)]
+pub struct Fields<'a> {
+    line: &'a str,
+    // name, start and end of each field in pattern order
+    spans: Vec<(String, usize, usize)>
+}
+
+impl<'a> Fields<'a> {
+    fn span(&self, name: &str) -> &(String, usize, usize) {
+        self.spans.iter().find(|x| x.0 == name).unwrap_or_else(|| panic!("the format has no field {{{}}}", name))
+    }
+
+    pub fn str(&self, name: &str) -> &'a str {
+        let &(_, start, end) = self.span(name);
+        &self.line[start..end]
+    }
+
+    pub fn column(&self, name: &str) -> usize {
+        self.span(name).1
+    }
+
+    pub fn get<T: FromStr>(&self, name: &str) -> Result<T, Mismatch> {
+        let &(_, start, end) = self.span(name);
+        self.line[start..end].parse::<T>().map_err(|_| Mismatch {
+            column: start,
+            expected: format!("{} for {{{}}}", any::type_name::<T>(), name)
+        })
+    }
+
+    fn get_at<T: FromStr>(&self, index: usize) -> Result<T, Mismatch> {
+        self.get(&self.spans[index].0)
+    }
+}
+
+// the fields of a line in order as a tuple, (u32, char) or the like
+pub trait FromFields: Sized {
+    fn from_fields(fields: &Fields) -> Result<Self, Mismatch>;
+}
+
+macro_rules! tuple_from_fields {
+    ($($t:ident $i:tt),+) => {
+        impl<$($t: FromStr),+> FromFields for ($($t,)+) {
+            fn from_fields(fields: &Fields) -> Result<Self, Mismatch> {
+                Ok(($(fields.get_at::<$t>($i)?,)+))
+            }
+        }
+    };
+}
+
+tuple_from_fields!(A 0);
+tuple_from_fields!(A 0, B 1);
+tuple_from_fields!(A 0, B 1, C 2);
+tuple_from_fields!(A 0, B 1, C 2, D 3);
+tuple_from_fields!(A 0, B 1, C 2, D 3, E 4);
+tuple_from_fields!(A 0, B 1, C 2, D 3, E 4, F 5);
+tuple_from_fields!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+
+fn skip_whitespace(line: &str, pos: usize) -> usize {
+    pos + line[pos..].len() - line[pos..].trim_start().len()
+}
+
+// matches text at pos, returning where it ends or, when it doesn't fit, the column it
+// failed at and the part of the text still expected there
+fn match_text<'t>(text: &'t str, line: &str, mut pos: usize) -> Result<usize, (usize, &'t str)> {
+    for (i, ch) in text.char_indices() {
+        if ch == ' ' {
+            let next = skip_whitespace(line, pos);
+            // a space in the pattern needs some whitespace, unless the line has ended
+            if next == pos && pos < line.len() {
+                return Err((pos, &text[i..]));
+            }
+            pos = next;
+        } else if line[pos..].starts_with(ch) {
+            pos += ch.len_utf8();
+        } else {
+            return Err((pos, &text[i..]));
+        }
+    }
+    Ok(pos)
+}
+
+fn mismatch(column: usize, expected: &str) -> Mismatch {
+    Mismatch { column, expected: expected.trim().to_string() }
+}
+
+impl Format {
+    // panics on a malformed pattern, they're written in the code not read from input
+    pub fn new(pattern: &str) -> Format {
+        let mut pieces = Vec::new();
+        let mut rest = pattern;
+        while !rest.is_empty() {
+            match rest.find('{') {
+                Some(0) => {
+                    let end = rest.find('}').unwrap_or_else(|| panic!("unclosed field in `{}`", pattern));
+                    let name = &rest[1..end];
+                    assert!(!name.is_empty() && !name.contains('{'), "bad field name in `{}`", pattern);
+                    assert!(!matches!(pieces.last(), Some(Piece::Field(_))), "fields need text between them in `{}`", pattern);
+                    pieces.push(Piece::Field(name.to_string()));
+                    rest = &rest[end + 1..];
+                },
+                Some(start) => {
+                    pieces.push(Piece::Text(rest[..start].to_string()));
+                    rest = &rest[start..];
+                },
+                None => {
+                    pieces.push(Piece::Text(rest.to_string()));
+                    rest = "";
+                }
+            }
+        }
+        Format { pattern: pattern.to_string(), pieces }
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn fields<'a>(&self, line: &'a str) -> Result<Fields<'a>, Mismatch> {
+        let mut spans = Vec::new();
+        let mut pos = 0;
+        for (i, piece) in self.pieces.iter().enumerate() {
+            match piece {
+                Piece::Text(text) => {
+                    pos = match_text(text, line, pos).map_err(|(column, rest)| mismatch(column, rest))?;
+                },
+                Piece::Field(name) => {
+                    let start = skip_whitespace(line, pos);
+                    if start == line.len() {
+                        return Err(Mismatch { column: start, expected: format!("{{{}}}", name) });
+                    }
+                    // up to the first place the next text fits, the field can't be empty. If
+                    // it fits nowhere the error is from the place it got furthest into, or
+                    // the end of the line when it never got started.
+                    let end = match self.pieces.get(i + 1) {
+                        Some(Piece::Text(next)) => {
+                            let mut closest: Option<(usize, usize, &str)> = None;
+                            let mut end = None;
+                            for at in line.char_indices().map(|(i, _)| i).chain([line.len()]).filter(|&i| i > start) {
+                                match match_text(next, line, at) {
+                                    Ok(_) => {
+                                        end = Some(at);
+                                        break;
+                                    },
+                                    Err((column, rest)) => if column > at && closest.is_none_or(|x| column - at > x.0) {
+                                        closest = Some((column - at, column, rest));
+                                    }
+                                }
+                            }
+                            end.ok_or_else(|| match closest {
+                                Some((_, column, rest)) => mismatch(column, rest),
+                                None => mismatch(line.len(), next)
+                            })?
+                        },
+                        _ => line.len()
+                    };
+                    let value = line[start..end].trim_end();
+                    spans.push((name.clone(), start, start + value.len()));
+                    pos = end;
+                }
+            }
+        }
+        if !line[pos..].trim().is_empty() {
+            return Err(Mismatch { column: pos, expected: String::from("end of line") });
+        }
+        Ok(Fields { line, spans })
+    }
+
+    pub fn scan<T: FromFields>(&self, line: &str) -> Result<T, Mismatch> {
+        T::from_fields(&self.fields(line)?)
+    }
+
+    // scan with the mismatch turned into the day's parse error
+    pub fn parse<T: FromFields>(&self, day: u32, line_no: usize, line: &str) -> crate::error::Result<T> {
+        self.scan(line).map_err(|e| e.error(day, line_no, line))
+    }
+}
+
+// the first of several formats to fit and its index. If none do the mismatch is from
+// the one that got furthest.
+pub fn first_of<'a>(formats: &[&Format], line: &'a str) -> Result<(usize, Fields<'a>), Mismatch> {
+    let mut furthest: Option<Mismatch> = None;
+    for (i, format) in formats.iter().enumerate() {
+        match format.fields(line) {
+            Ok(fields) => return Ok((i, fields)),
+            Err(e) => if furthest.as_ref().is_none_or(|x| e.column > x.column) {
+                furthest = Some(e);
+            }
+        }
+    }
+    Err(furthest.expect("first_of needs at least one format"))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_scan() {
+        use super::Format;
+        let claim = Format::new("#{id} @ {x},{y}: {w}x{h}");
+        assert_eq!(claim.scan::<(u32, u32, u32, u32, u32)>("#123 @ 3,2: 5x4"), Ok((123, 3, 2, 5, 4)));
+        let light = Format::new("position=<{x},{y}> velocity=<{dx},{dy}>");
+        assert_eq!(light.scan::<(i64, i64, i64, i64)>("position=< 9,  -1> velocity=< 0,  2>"), Ok((9, -1, 0, 2)));
+        let step = Format::new("Step {a} must be finished before step {b} can begin.");
+        assert_eq!(step.scan::<(char, char)>("Step C must be finished before step A can begin."), Ok(('C', 'A')));
+        let rule = Format::new("{pattern} => {output}");
+        assert_eq!(rule.scan::<(String, char)>("..#.. => #"), Ok((String::from("..#.."), '#')));
+    }
+
+    #[test]
+    fn test_fields() {
+        use super::Format;
+        let format = Format::new("{units} units each with {hp} hit points");
+        let fields = format.fields("  17 units  each with 5390 hit points ").unwrap();
+        assert_eq!(fields.str("units"), "17");
+        assert_eq!(fields.get::<u32>("hp"), Ok(5390));
+    }
+
+    #[test]
+    fn test_mismatch() {
+        use super::{Format, Mismatch};
+        let claim = Format::new("#{id} @ {x},{y}: {w}x{h}");
+        assert_eq!(claim.scan::<(u32, u32, u32, u32, u32)>("#1 - 3,2: 5x4"), Err(Mismatch { column: 3, expected: String::from("@") }));
+        assert_eq!(claim.scan::<(u32, u32, u32, u32, u32)>("#1 @ 3,a: 5x4"), Err(Mismatch { column: 7, expected: String::from("u32 for {y}") }));
+        assert_eq!(claim.scan::<(u32, u32, u32, u32, u32)>("#1 @ 3,2: 5x"), Err(Mismatch { column: 12, expected: String::from("{h}") }));
+        assert_eq!(claim.scan::<(u32, u32, u32, u32, u32)>("#1 @ 3,2: 5"), Err(Mismatch { column: 11, expected: String::from("x") }));
+        assert_eq!(claim.scan::<(u32, u32, u32, u32, u32)>("#1 @ 3,2: "), Err(Mismatch { column: 10, expected: String::from("{w}") }));
+        let event = Format::new("falls asleep");
+        assert_eq!(event.scan::<(u32,)>("falls awake"), Err(Mismatch { column: 7, expected: String::from("sleep") }));
+        let depth = Format::new("depth: {depth}");
+        assert_eq!(depth.scan::<(u32,)>("depth: 5 6"), Err(Mismatch { column: 7, expected: String::from("u32 for {depth}") }));
+        let wake = Format::new("wakes up");
+        assert_eq!(wake.scan::<(u32,)>("wakes up early"), Err(Mismatch { column: 8, expected: String::from("end of line") }));
+
+        let err = claim.parse::<(u32, u32, u32, u32, u32)>(3, 2, "#1 @ 3,a: 5x4").unwrap_err();
+        assert_eq!(err.to_string(), "day 3 line 2 column 8: expected `u32 for {y}`, found `a: 5x4`");
+    }
+
+    #[test]
+    fn test_first_of() {
+        use super::{first_of, Format, Mismatch};
+        let vertical = Format::new("x={x}, y={y0}..{y1}");
+        let horizontal = Format::new("y={y}, x={x0}..{x1}");
+        let (which, fields) = first_of(&[&vertical, &horizontal], "y=7, x=495..501").unwrap();
+        assert_eq!(which, 1);
+        assert_eq!(fields.get::<u32>("x1"), Ok(501));
+        assert_eq!(first_of(&[&vertical, &horizontal], "x=495, y=7").unwrap_err(), Mismatch { column: 10, expected: String::from("..") });
+    }
+}
@@ -0,0 +1,138 @@
+//! Random puzzle inputs for stress testing the parsers and solvers, and for timing them
+//! on inputs bigger than the real ones. The same seed and knobs always give the same
+//! input, so a failure can be reproduced from the two numbers that made it.
+//!
+//! Every generator takes the same two knobs: how many of the day's things to make,
+//! claims or nanobots or army groups, and how big the space they're put in is. Each
+//! day's generate says what it does with them.
+
+// SplitMix64, small and fast, and the sequence can't change under us with a crate update
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    // 0..n, n has to be above 0
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "below needs a non empty range");
+        // the bias from the modulo is too small to matter for test inputs
+        (self.next_u64() % n as u64) as usize
+    }
+
+    // lo..=hi
+    pub fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        assert!(lo <= hi, "range needs lo <= hi");
+        lo + (self.next_u64() % (hi - lo + 1) as u64) as i64
+    }
+
+    // true one time in n
+    pub fn one_in(&mut self, n: usize) -> bool {
+        self.below(n) == 0
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Knobs {
+    // how many of the day's things to generate
+    pub count: usize,
+    // how big the space they're spread over is
+    pub extent: usize
+}
+
+impl Default for Knobs {
+    fn default() -> Knobs {
+        Knobs { count: 50, extent: 50 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_repeatable() {
+        use super::Rng;
+        let a: Vec<u64> = { let mut rng = Rng::new(7); (0..5).map(|_| rng.next_u64()).collect() };
+        let b: Vec<u64> = { let mut rng = Rng::new(7); (0..5).map(|_| rng.next_u64()).collect() };
+        assert_eq!(a, b);
+        assert_ne!(a, { let mut rng = Rng::new(8); (0..5).map(|_| rng.next_u64()).collect::<Vec<u64>>() });
+        // the first output of SplitMix64 seeded with 0
+        assert_eq!(Rng::new(0).next_u64(), 0xE220A8397B1DCDAF);
+    }
+
+    #[test]
+    fn test_ranges() {
+        use super::Rng;
+        let mut rng = Rng::new(1);
+        let mut seen = [false; 5];
+        for _ in 0..1000 {
+            let x = rng.range(-2, 2);
+            assert!((-2..=2).contains(&x));
+            seen[(x + 2) as usize] = true;
+            assert!(rng.below(3) < 3);
+        }
+        assert!(seen.iter().all(|x| *x));
+
+        let mut items: Vec<u32> = (0..20).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<u32>>());
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn test_generators() {
+        use super::{Knobs, Rng};
+        use crate::error::Error;
+        use crate::registry::get;
+        // every day's input is the same for the same seed and differs between seeds, and
+        // whatever the parts make of it, it parses
+        let table = [
+            (3, Knobs { count: 40, extent: 50 }, 20),
+            (4, Knobs { count: 30, extent: 5 }, 20),
+            (7, Knobs { count: 20, extent: 10 }, 20),
+            (8, Knobs { count: 40, extent: 10 }, 20),
+            (13, Knobs { count: 3, extent: 20 }, 5),
+            (15, Knobs { count: 6, extent: 9 }, 20),
+            (17, Knobs { count: 12, extent: 30 }, 20),
+            (20, Knobs { count: 60, extent: 4 }, 20),
+            (23, Knobs { count: 20, extent: 30 }, 20),
+            (24, Knobs { count: 4, extent: 20 }, 20),
+        ];
+        for (n, knobs, seeds) in table {
+            let day = get(n).unwrap();
+            let generate = day.generate.unwrap();
+            let inputs: Vec<String> = (0..seeds).map(|seed| generate(&knobs, &mut Rng::new(seed))).collect();
+            assert!(inputs.iter().any(|x| *x != inputs[0]), "day {}", n);
+            for (seed, input) in inputs.iter().enumerate() {
+                assert_eq!(*input, generate(&knobs, &mut Rng::new(seed as u64)), "day {} seed {}", n, seed);
+                for solve in [Some(day.part1), day.part2].into_iter().flatten() {
+                    if let Err(e @ Error::Parse { .. }) = solve(input) {
+                        panic!("day {} seed {}: {}", n, seed, e);
+                    }
+                }
+            }
+        }
+    }
+}
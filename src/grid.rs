@@ -0,0 +1,227 @@
+//! A rectangular grid of cells stored row by row, indexed by (x, y) with y growing
+//! downwards. Iteration is in reading order, top to bottom then left to right.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::error::{Error, Result};
+
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>
+}
+
+// neighbour offsets in reading order
+const NEIGHBOURS4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const NEIGHBOURS8: [(isize, isize); 8] = [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        Grid { width, height, cells: vec![fill; width * height] }
+    }
+
+    pub fn fill(&mut self, value: T) {
+        for cell in self.cells.iter_mut() {
+            *cell = value.clone();
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    // parses character art, one row per line, f maps each character to a cell or
+    // None if it isn't allowed. Rows must all be the same width.
+    pub fn parse_with<F>(day: u32, input: &str, expected: &str, mut f: F) -> Result<Grid<T>>
+        where F: FnMut((usize, usize), char) -> Option<T> {
+        let mut cells = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+        for (y, line) in input.lines().enumerate() {
+            let err = || Error::parse(day, y + 1, line, expected);
+            let before = cells.len();
+            for (x, ch) in line.chars().enumerate() {
+                cells.push(f((x, y), ch).ok_or_else(err)?);
+            }
+            let row = cells.len() - before;
+            if y == 0 {
+                width = row;
+            }
+            if row == 0 || row != width {
+                return Err(err());
+            }
+            height += 1;
+        }
+        if height == 0 {
+            return Err(Error::parse(day, 1, "", expected));
+        }
+        Ok(Grid { width, height, cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    pub fn get(&self, x: isize, y: isize) -> Option<&T> {
+        if self.contains(x, y) { Some(&self.cells[y as usize * self.width + x as usize]) } else { None }
+    }
+
+    pub fn get_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
+        if self.contains(x, y) { Some(&mut self.cells[y as usize * self.width + x as usize]) } else { None }
+    }
+
+    // coordinates off an edge come back in on the opposite one
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self[(x, y)]
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    // every cell with its coordinates in reading order
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells.iter().enumerate().map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i % width, i / width))
+    }
+
+    fn offsets<'a>(&self, x: usize, y: usize, offsets: &'a [(isize, isize)]) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let (width, height) = (self.width as isize, self.height as isize);
+        offsets.iter()
+            .map(move |(dx, dy)| (x as isize + dx, y as isize + dy))
+            .filter(move |&(x, y)| x >= 0 && y >= 0 && x < width && y < height)
+            .map(|(x, y)| (x as usize, y as usize))
+    }
+
+    // up, left, right, down, skipping any off the grid
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.offsets(x, y, &NEIGHBOURS4)
+    }
+
+    // the surrounding 8 in reading order, skipping any off the grid
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.offsets(x, y, &NEIGHBOURS8)
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+
+    pub fn count<F: FnMut(&T) -> bool>(&self, mut f: F) -> usize {
+        self.cells.iter().filter(|x| f(x)).count()
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.width && y < self.height, "({}, {}) is outside the {}x{} grid", x, y, self.width, self.height);
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.width && y < self.height, "({}, {}) is outside the {}x{} grid", x, y, self.width, self.height);
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+// one line per row with no separators, so a Grid<char> prints as the art it was parsed from
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    const ART: &str = "\
+#.#
+.|.
+##.
+";
+
+    fn parse() -> super::Grid<char> {
+        super::Grid::parse_with(0, ART, "# . |", |_, ch| if "#.|".contains(ch) { Some(ch) } else { None }).unwrap()
+    }
+
+    #[test]
+    fn test_parse_display() {
+        let grid = parse();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid[(1, 1)], '|');
+        assert_eq!(grid.row(2), &['#', '#', '.']);
+        assert_eq!(grid.to_string(), ART);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        use super::Grid;
+        let err = Grid::parse_with(18, "#.\n#\n", "row of . #", |_, ch| Some(ch)).unwrap_err();
+        assert_eq!(err.to_string(), "day 18 line 2: expected `row of . #`, found `#`");
+        let err = Grid::parse_with(18, "#x\n", "row of . #", |_, ch| if ch == 'x' { None } else { Some(ch) }).unwrap_err();
+        assert_eq!(err.to_string(), "day 18 line 1: expected `row of . #`, found `#x`");
+        assert!(Grid::parse_with(18, "", "row of . #", |_, ch| Some(ch)).is_err());
+    }
+
+    #[test]
+    fn test_access() {
+        let mut grid = parse();
+        assert_eq!(grid.get(-1, 0), None);
+        assert_eq!(grid.get(2, 3), None);
+        assert_eq!(grid.get(2, 2), Some(&'.'));
+        assert_eq!(*grid.get_wrapping(-1, 3), '#');
+        *grid.get_mut(0, 1).unwrap() = '|';
+        grid[(2, 2)] = '|';
+        assert_eq!(grid.count(|x| *x == '|'), 3);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = parse();
+        assert_eq!(grid.neighbours4(0, 0).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbours4(1, 1).collect::<Vec<_>>(), vec![(1, 0), (0, 1), (2, 1), (1, 2)]);
+        assert_eq!(grid.neighbours8(1, 1).count(), 8);
+        assert_eq!(grid.neighbours8(2, 2).collect::<Vec<_>>(), vec![(1, 1), (2, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn test_reading_order() {
+        let grid = parse();
+        let hashes: Vec<(usize, usize)> = grid.iter().filter(|(_, ch)| **ch == '#').map(|(pos, _)| pos).collect();
+        assert_eq!(hashes, vec![(0, 0), (2, 0), (0, 2), (1, 2)]);
+        assert_eq!(grid.positions().nth(4), Some((1, 1)));
+        assert_eq!(grid.map(|ch| *ch == '#').values().filter(|x| **x).count(), 4);
+    }
+}
@@ -0,0 +1,195 @@
+//! Finds the puzzle input for a day. In order it tries an explicit path, the
+//! directory named by AOC_INPUT_DIR, inputs/dayNN.txt under the current directory, the
+//! input store (see `store`), then examples/dayNN_*.txt. Inputs can also be looked up by
+//! file name, for the entries in answers.txt.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+use crate::store::Store;
+
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+pub fn resolve(day: u32, path: Option<&str>) -> Result<PathBuf> {
+    let env_dir = env::var_os(INPUT_DIR_VAR).map(PathBuf::from);
+    search(day, path.map(Path::new), env_dir.as_deref(), Some(&Store::from_env()), Path::new("."))
+}
+
+// resolve with the environment and working directory passed in
+pub fn search(day: u32, path: Option<&Path>, env_dir: Option<&Path>, store: Option<&Store>, root: &Path) -> Result<PathBuf> {
+    let file_name = format!("day{:02}.txt", day);
+    let mut searched = Vec::new();
+
+    let mut candidates = Vec::new();
+    if let Some(path) = path {
+        candidates.push(path.to_path_buf());
+    }
+    if let Some(dir) = env_dir {
+        candidates.push(dir.join(&file_name));
+    }
+    candidates.push(root.join("inputs").join(&file_name));
+
+    for candidate in candidates {
+        if candidate.is_file() {
+            return Ok(candidate);
+        }
+        searched.push(candidate.display().to_string());
+    }
+
+    // a stored input that no longer matches its checksum is an error, not a miss
+    if let Some(store) = store {
+        let stored = store.path(day);
+        if stored.is_file() {
+            store.load(day)?;
+            return Ok(stored);
+        }
+        searched.push(stored.display().to_string());
+    }
+
+    // there may be several examples for a day, take the first by name
+    let examples = root.join("examples");
+    let prefix = format!("day{:02}_", day);
+    let mut found: Vec<PathBuf> = fs::read_dir(&examples).into_iter().flatten()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|p| p.is_file())
+        .filter(|p| {
+            let name = p.file_name().and_then(|x| x.to_str()).unwrap_or("");
+            name.starts_with(&prefix) && name.ends_with(".txt")
+        })
+        .collect();
+    found.sort();
+    if let Some(first) = found.into_iter().next() {
+        return Ok(first);
+    }
+    searched.push(examples.join(format!("{}*.txt", prefix)).display().to_string());
+
+    Err(Error::not_found(day, searched))
+}
+
+// FNV-1a over the bytes, enough to tell which input a result came from
+pub fn checksum(text: &str) -> String {
+    let hash = text.bytes().fold(0xcbf29ce484222325u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3));
+    format!("{:016x}", hash)
+}
+
+pub fn named(day: u32, name: &str) -> Result<PathBuf> {
+    let env_dir = env::var_os(INPUT_DIR_VAR).map(PathBuf::from);
+    search_named(day, name, env_dir.as_deref(), Path::new("."))
+}
+
+// a file name is looked for in AOC_INPUT_DIR, inputs/ then examples/
+pub fn search_named(day: u32, name: &str, env_dir: Option<&Path>, root: &Path) -> Result<PathBuf> {
+    let mut candidates = Vec::new();
+    if let Some(dir) = env_dir {
+        candidates.push(dir.join(name));
+    }
+    candidates.push(root.join("inputs").join(name));
+    candidates.push(root.join("examples").join(name));
+
+    match candidates.iter().find(|x| x.is_file()) {
+        Some(found) => Ok(found.clone()),
+        None => Err(Error::not_found(day, candidates.iter().map(|x| x.display().to_string()).collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-input-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("inputs")).unwrap();
+        fs::create_dir_all(dir.join("examples")).unwrap();
+        fs::create_dir_all(dir.join("env")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_search_order() {
+        use super::search;
+        let root = scratch("order");
+        let env_dir = root.join("env");
+        let explicit = root.join("mine.txt");
+        for file in [root.join("examples/day07_b.txt"), root.join("examples/day07_a.txt"), root.join("examples/day07.md")] {
+            fs::write(file, "").unwrap();
+        }
+        assert_eq!(search(7, None, None, None, &root).unwrap(), root.join("examples/day07_a.txt"));
+        fs::write(root.join("inputs/day07.txt"), "").unwrap();
+        assert_eq!(search(7, None, Some(&env_dir), None, &root).unwrap(), root.join("inputs/day07.txt"));
+        fs::write(env_dir.join("day07.txt"), "").unwrap();
+        assert_eq!(search(7, Some(&explicit), Some(&env_dir), None, &root).unwrap(), env_dir.join("day07.txt"));
+        fs::write(&explicit, "").unwrap();
+        assert_eq!(search(7, Some(&explicit), Some(&env_dir), None, &root).unwrap(), explicit);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_not_found() {
+        use super::search;
+        use crate::error::Error;
+        use crate::store::Store;
+        let root = scratch("missing");
+        let env_dir = root.join("env");
+        let explicit = root.join("mine.txt");
+        let store = Store::new(root.join("store"), 2018, "alice");
+        match search(3, Some(&explicit), Some(&env_dir), Some(&store), &root) {
+            Err(Error::NotFound { day, searched }) => {
+                assert_eq!(day, 3);
+                assert_eq!(searched, vec![
+                    explicit.display().to_string(),
+                    env_dir.join("day03.txt").display().to_string(),
+                    root.join("inputs/day03.txt").display().to_string(),
+                    root.join("store/2018/alice/day03.txt").display().to_string(),
+                    root.join("examples/day03_*.txt").display().to_string(),
+                ]);
+            },
+            other => panic!("expected NotFound, got {:?}", other),
+        }
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_search_store() {
+        use super::search;
+        use crate::store::Store;
+        let root = scratch("store");
+        let store = Store::new(root.join("store"), 2018, "alice");
+        fs::write(root.join("examples/day07_a.txt"), "").unwrap();
+        let stored = store.save(7, "Step C must be finished before step A can begin.\n").unwrap();
+        assert_eq!(search(7, None, None, Some(&store), &root).unwrap(), stored);
+        fs::write(&stored, "Step C must be finished").unwrap();
+        assert!(search(7, None, None, Some(&store), &root).unwrap_err().to_string().contains("cut short"));
+        fs::write(root.join("inputs/day07.txt"), "").unwrap();
+        assert_eq!(search(7, None, None, Some(&store), &root).unwrap(), root.join("inputs/day07.txt"));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_checksum() {
+        use super::checksum;
+        // the published FNV-1a 64 test vectors
+        assert_eq!(checksum(""), "cbf29ce484222325");
+        assert_eq!(checksum("a"), "af63dc4c8601ec8c");
+        assert_ne!(checksum("+1\n-2\n"), checksum("+1\n-2\r\n"));
+    }
+
+    #[test]
+    fn test_search_named() {
+        use super::search_named;
+        let root = scratch("named");
+        let env_dir = root.join("env");
+        assert!(search_named(22, "day22_b.txt", Some(&env_dir), &root).is_err());
+        fs::write(root.join("examples/day22_b.txt"), "").unwrap();
+        assert_eq!(search_named(22, "day22_b.txt", Some(&env_dir), &root).unwrap(), root.join("examples/day22_b.txt"));
+        fs::write(root.join("inputs/day22_b.txt"), "").unwrap();
+        assert_eq!(search_named(22, "day22_b.txt", None, &root).unwrap(), root.join("inputs/day22_b.txt"));
+        fs::write(env_dir.join("day22_b.txt"), "").unwrap();
+        assert_eq!(search_named(22, "day22_b.txt", Some(&env_dir), &root).unwrap(), env_dir.join("day22_b.txt"));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
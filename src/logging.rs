@@ -0,0 +1,79 @@
+//! Log levels for the days and the shared subsystems. Each module logs under its own
+//! name, so `day15=trace` shows every round of the battle and `elfcode=trace` every
+//! instruction the device runs, while everything else stays quiet.
+//!
+//! Levels come from AOC_LOG and then `--log`, both taking comma separated directives
+//! like `debug` or `day15=trace,elfcode=debug`. `-v` raises the default level for the
+//! whole crate one step at a time from warn.
+
+use std::env;
+
+use log::LevelFilter;
+
+pub const LOG_VAR: &str = "AOC_LOG";
+
+const CRATE: &str = env!("CARGO_CRATE_NAME");
+
+// the level for the whole crate after -v repeated verbosity times
+pub fn level(verbosity: usize) -> LevelFilter {
+    match verbosity {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Info,
+        2 => LevelFilter::Debug,
+        _ => LevelFilter::Trace
+    }
+}
+
+// turns short module names into log targets, day15=trace becomes
+// adventofcode2018::day15=trace. A bare level applies to the whole crate.
+pub fn expand(directives: &str) -> String {
+    directives.split(',')
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .map(|directive| match directive.split_once('=') {
+            Some((name, level)) if !name.contains("::") && name != CRATE => format!("{}::{}={}", CRATE, name, level),
+            Some(_) => directive.to_string(),
+            None if directive.parse::<LevelFilter>().is_ok() => format!("{}={}", CRATE, directive),
+            None if directive.contains("::") || directive == CRATE => directive.to_string(),
+            None => format!("{}::{}", CRATE, directive)
+        })
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+// logs go to stderr so they never mix with the answers on stdout
+pub fn init(verbosity: usize, directives: Option<&str>) {
+    let mut builder = env_logger::Builder::new();
+    builder.filter_module(CRATE, level(verbosity))
+        .format_timestamp(None);
+    if let Ok(var) = env::var(LOG_VAR) {
+        builder.parse_filters(&expand(&var));
+    }
+    if let Some(directives) = directives {
+        builder.parse_filters(&expand(directives));
+    }
+    // a second init, from tests running in the same process, keeps the first logger
+    let _ = builder.try_init();
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_expand() {
+        use super::expand;
+        assert_eq!(expand("day15=trace, elfcode=debug"), "adventofcode2018::day15=trace,adventofcode2018::elfcode=debug");
+        assert_eq!(expand("debug"), "adventofcode2018=debug");
+        assert_eq!(expand("day24"), "adventofcode2018::day24");
+        assert_eq!(expand("adventofcode2018::cycle=info"), "adventofcode2018::cycle=info");
+        assert_eq!(expand(""), "");
+    }
+
+    #[test]
+    fn test_level() {
+        use super::level;
+        use log::LevelFilter;
+        assert_eq!(level(0), LevelFilter::Warn);
+        assert_eq!(level(2), LevelFilter::Debug);
+        assert_eq!(level(5), LevelFilter::Trace);
+    }
+}
@@ -0,0 +1,713 @@
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Read, Write};
+use std::process;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use adventofcode2018::answers::{self, Outcome};
+use adventofcode2018::deadline;
+use adventofcode2018::elfcode::{self, asm, compiled, disasm, debugger::Debugger};
+use adventofcode2018::error::{Error, Result};
+use adventofcode2018::fetch::{self, Client};
+use adventofcode2018::generate::{Knobs, Rng};
+use adventofcode2018::input;
+use adventofcode2018::logging;
+use adventofcode2018::oracle::Verdict;
+use adventofcode2018::recorder::{Output, Settings};
+use adventofcode2018::registry::{self, Measurement, Solver};
+use adventofcode2018::report::{self, Run};
+use adventofcode2018::store::{self, Store};
+use adventofcode2018::submit::{self, History, Reply};
+
+fn usage() -> ! {
+    eprintln!("usage: aoc <day> <part> [input] [--json]    (input - reads stdin)");
+    eprintln!("       aoc all [input_dir] [--timeout secs] [--jobs n] [--json]");
+    eprintln!("       aoc verify [answers_file]");
+    eprintln!("       aoc bench <day> [input] [--runs n]");
+    eprintln!("       aoc generate <day> [--seed n] [--count n] [--extent n]");
+    eprintln!("       aoc check <day> [--seeds n] [--count n] [--extent n]");
+    eprintln!("       aoc record <day> <output.gif | frame_dir> [input] [--delay cs] [--scale n] [--skip n]");
+    eprintln!("       aoc disasm <day> [input] [--dot file] [--name reg=name]...");
+    eprintln!("       aoc debug <day> [input] [--script file]");
+    eprintln!("       aoc asm <source> [output]");
+    eprintln!("       aoc fetch <day>... [--user name]    (needs {})", fetch::SESSION_VAR);
+    eprintln!("       aoc inputs [--user name]");
+    eprintln!("       aoc submit <day> <part> [input] [--user name]");
+    eprintln!("--timeout: a part still going is reported as timed out, only days 14, 15 and 24 stop running at it");
+    eprintln!("options: -v, -vv, -vvv for more logging, --log day15=trace,elfcode=debug (or {})", logging::LOG_VAR);
+    process::exit(2);
+}
+
+fn time(solver: Solver, input: &str) -> (Result<String>, Duration) {
+    let start = Instant::now();
+    let answer = solver(input);
+    (answer, start.elapsed())
+}
+
+fn read_input(day: u32, path: Option<&str>) -> Result<String> {
+    read_named_input(day, path).map(|(_, input)| input)
+}
+
+// the input along with where it came from
+fn read_named_input(day: u32, path: Option<&str>) -> Result<(String, String)> {
+    if path == Some("-") {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input).map_err(|e| Error::io("stdin", e))?;
+        Ok((String::from("stdin"), input))
+    } else {
+        let path = input::resolve(day, path)?;
+        let name = path.display().to_string();
+        let input = fs::read_to_string(&path).map_err(|e| Error::io(&name, e))?;
+        Ok((name, input))
+    }
+}
+
+fn measure(entry: &registry::Day, part: u32, path: Option<&str>) -> Run {
+    match read_named_input(entry.day, path) {
+        Ok((name, input)) => Run::new(entry.day, part, &name, &input, (entry.measure)(&input, part)),
+        Err(e) => Run::failed(entry.day, part, e)
+    }
+}
+
+fn print_report(runs: &[Run]) {
+    println!("{}", report::document(runs));
+    if runs.iter().any(|x| x.measurement.answer.is_err()) {
+        process::exit(1);
+    }
+}
+
+fn run_one(day: &str, part: &str, path: Option<&str>, json: bool) {
+    let day = day.parse::<u32>().unwrap_or_else(|_| usage());
+    let part = part.parse::<u32>().unwrap_or_else(|_| usage());
+    let entry = registry::get(day).unwrap_or_else(|| {
+        eprintln!("no solution for day {}", day);
+        process::exit(1);
+    });
+    let solver = entry.part(part).unwrap_or_else(|| {
+        eprintln!("day {} has no part {}", day, part);
+        process::exit(1);
+    });
+    if json {
+        print_report(&[measure(&entry, part, path)]);
+        return;
+    }
+
+    let input = match read_input(day, path) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let (answer, elapsed) = time(solver, &input);
+    match answer {
+        Ok(answer) => println!("{}", answer),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        }
+    }
+    eprintln!("day {} part {}: {:?}", day, part, elapsed);
+}
+
+// the answer cell of the table, multi line answers (day 10) go after it
+fn cell(run: &Run, extra: &mut Vec<String>) -> String {
+    match &run.measurement.answer {
+        Ok(answer) if answer.contains('\n') => {
+            extra.push(format!("day {} part {}:\n{}", run.day, run.part, answer));
+            String::from("see below")
+        },
+        Ok(answer) => answer.clone(),
+        Err(Error::Timeout { .. }) => String::from("timed out"),
+        Err(_) => String::from("error")
+    }
+}
+
+fn print_table(days: &[registry::Day], runs: &[Run]) {
+    let mut extra = Vec::new();
+    println!("day | {:<20} | {:>10} | {:<20} | {:>10}", "part 1", "time", "part 2", "time");
+    for day in days {
+        let mut row = format!("{:>3}", day.day);
+        for part in 1..=2 {
+            let run = runs.iter().find(|x| x.day == day.day && x.part == part);
+            let cell = match run {
+                _ if day.part(part).is_none() => (String::from("-"), String::new()),
+                None => (String::from("no input"), String::new()),
+                Some(run) if run.input.is_none() => (String::from("error"), String::new()),
+                Some(run) => (cell(run, &mut extra), format!("{:.3?}", run.measurement.parse + run.measurement.solve))
+            };
+            row.push_str(&format!(" | {:<20} | {:>10}", cell.0, cell.1));
+        }
+        println!("{}", row);
+    }
+    for answer in &extra {
+        println!();
+        println!("{}", answer);
+    }
+
+    let errors: Vec<String> = runs.iter().filter_map(|x| x.measurement.answer.as_ref().err()).map(|e| e.to_string()).collect();
+    if !errors.is_empty() {
+        eprintln!();
+        for (i, e) in errors.iter().enumerate() {
+            // a day whose input couldn't be read fails both parts the same way
+            if i == 0 || errors[i - 1] != *e {
+                eprintln!("error: {}", e);
+            }
+        }
+        process::exit(1);
+    }
+}
+
+// solves the parts of every day with an input at once, each with limit to finish in
+fn run_all(args: &[String], json: bool) {
+    let mut input_dir = None;
+    let mut limit = Duration::from_secs(60);
+    let mut threads = 0;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().and_then(|x| x.parse::<u64>().ok()).unwrap_or_else(|| usage());
+        match arg.as_str() {
+            "--timeout" => limit = Duration::from_secs(value()),
+            "--jobs" => threads = value() as usize,
+            _ if input_dir.is_none() => input_dir = Some(arg.as_str()),
+            _ => usage()
+        }
+    }
+
+    let days = registry::days();
+    // read up front, a day without an input is left out
+    let inputs: Vec<Option<Result<(String, String)>>> = days.iter().map(|day| {
+        let path = input_dir.map(|dir| format!("{}/day{:02}.txt", dir, day.day));
+        match read_named_input(day.day, path.as_deref()) {
+            Err(Error::NotFound { .. }) => None,
+            other => Some(other)
+        }
+    }).collect();
+    let jobs: Vec<(usize, u32)> = days.iter().enumerate()
+        .filter(|(i, _)| inputs[*i].is_some())
+        .flat_map(|(i, day)| (1..=2).filter(|part| day.part(*part).is_some()).map(move |part| (i, part)))
+        .collect();
+
+    // 0 is one per core
+    let threads = match threads {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n
+    };
+    let (sender, receiver) = mpsc::channel();
+    let mut runs: Vec<Option<Run>> = jobs.iter().map(|_| None).collect();
+    // the jobs on a thread and when each is due, a thread past its deadline is left to
+    // finish on its own and doesn't count against threads
+    let mut running: Vec<(usize, Instant)> = Vec::new();
+    let mut next = 0;
+    while runs.iter().any(Option::is_none) {
+        while running.len() < threads && next < jobs.len() {
+            let (i, part) = jobs[next];
+            match inputs[i].as_ref().unwrap() {
+                Ok((_, input)) => {
+                    let (job, sender, measure, input) = (next, sender.clone(), days[i].measure, input.clone());
+                    thread::spawn(move || {
+                        // nobody is listening once the part has been reported as timed out
+                        let _ = sender.send((job, deadline::within(limit, || measure(&input, part))));
+                    });
+                    running.push((next, Instant::now() + limit));
+                },
+                Err(e) => runs[next] = Some(Run::failed(days[i].day, part, e.clone()))
+            }
+            next += 1;
+        }
+        let Some(&(job, due)) = running.iter().min_by_key(|(_, due)| *due) else {
+            continue;
+        };
+        let (job, measurement) = match receiver.recv_timeout(due.saturating_duration_since(Instant::now())) {
+            Ok((job, (mut measurement, late))) => {
+                if late {
+                    measurement.answer = Err(Error::timeout(days[jobs[job].0].day, limit));
+                }
+                (job, measurement)
+            },
+            Err(_) => {
+                let error = Error::timeout(days[jobs[job].0].day, limit);
+                (job, Measurement { answer: Err(error), parse: Duration::ZERO, solve: limit, extras: Default::default() })
+            }
+        };
+        running.retain(|(other, _)| *other != job);
+        let (i, part) = jobs[job];
+        let (name, input) = inputs[i].as_ref().unwrap().as_ref().unwrap();
+        runs[job] = Some(Run::new(days[i].day, part, name, input, measurement));
+    }
+    let runs: Vec<Run> = runs.into_iter().flatten().collect();
+
+    if json {
+        print_report(&runs);
+    } else {
+        print_table(&days, &runs);
+    }
+}
+
+fn run_record(args: &[String]) {
+    let mut positional = Vec::new();
+    let mut settings = Settings::new(Output::Gif(Default::default()));
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().and_then(|x| x.parse::<usize>().ok()).unwrap_or_else(|| usage());
+        match arg.as_str() {
+            "--delay" => settings.delay = u16::try_from(value()).unwrap_or_else(|_| usage()),
+            "--scale" => settings.scale = value(),
+            "--skip" => settings.skip = value(),
+            _ => positional.push(arg.as_str())
+        }
+    }
+    if positional.len() < 2 || positional.len() > 3 {
+        usage();
+    }
+
+    let day = positional[0].parse::<u32>().unwrap_or_else(|_| usage());
+    settings.output = Output::from_path(positional[1]);
+    let animate = match registry::get(day).and_then(|entry| entry.animate) {
+        Some(animate) => animate,
+        None => {
+            eprintln!("day {} can't be recorded", day);
+            process::exit(1);
+        }
+    };
+
+    let result = read_input(day, positional.get(2).copied()).and_then(|input| animate(&input, &settings));
+    match result {
+        Ok(frames) => eprintln!("wrote {} frames to {}", frames, positional[1]),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+fn run_generate(args: &[String]) {
+    let mut positional = Vec::new();
+    let mut seed = 0;
+    let mut knobs = Knobs::default();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().and_then(|x| x.parse::<usize>().ok()).unwrap_or_else(|| usage());
+        match arg.as_str() {
+            "--seed" => seed = value() as u64,
+            "--count" => knobs.count = value(),
+            "--extent" => knobs.extent = value(),
+            _ => positional.push(arg.as_str())
+        }
+    }
+    if positional.len() != 1 {
+        usage();
+    }
+
+    let day = positional[0].parse::<u32>().unwrap_or_else(|_| usage());
+    match registry::get(day).and_then(|entry| entry.generate) {
+        Some(generate) => print!("{}", generate(&knobs, &mut Rng::new(seed))),
+        None => {
+            eprintln!("day {} has no input generator", day);
+            process::exit(1);
+        }
+    }
+}
+
+fn run_check(args: &[String]) {
+    let mut positional = Vec::new();
+    let mut seeds = 20;
+    // the references are slow, so the inputs are smaller than generate's
+    let mut knobs = Knobs { count: 20, extent: 10 };
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().and_then(|x| x.parse::<usize>().ok()).unwrap_or_else(|| usage());
+        match arg.as_str() {
+            "--seeds" => seeds = value() as u64,
+            "--count" => knobs.count = value(),
+            "--extent" => knobs.extent = value(),
+            _ => positional.push(arg.as_str())
+        }
+    }
+    if positional.len() != 1 {
+        usage();
+    }
+
+    let day = positional[0].parse::<u32>().unwrap_or_else(|_| usage());
+    let check = match registry::get(day).and_then(|entry| entry.check) {
+        Some(check) => check,
+        None => {
+            eprintln!("day {} has no oracles", day);
+            process::exit(1);
+        }
+    };
+    let cases = match check(&knobs, seeds) {
+        Ok(cases) => cases,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let (mut agree, mut disagree, mut unanswered) = (0, 0, 0);
+    for case in &cases {
+        match &case.verdict {
+            Verdict::Agree => agree += 1,
+            Verdict::Disagree { fast, slow } => {
+                disagree += 1;
+                println!("seed {:>4} {:<12} fast {:?}, reference {:?}", case.seed, case.name, fast, slow);
+            },
+            Verdict::Unanswered(e) => {
+                unanswered += 1;
+                println!("seed {:>4} {:<12} no answer, {}", case.seed, case.name, e);
+            }
+        }
+    }
+    if disagree > 0 {
+        println!("reproduce with: aoc generate {} --seed <seed> --count {} --extent {}", day, knobs.count, knobs.extent);
+    }
+    println!("{} agree, {} disagree, {} unanswered", agree, disagree, unanswered);
+    if disagree > 0 {
+        process::exit(1);
+    }
+}
+
+fn run_verify(path: Option<&str>) {
+    let path = path.unwrap_or(answers::ANSWERS_FILE);
+    let entries = match answers::load(path) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let (mut ok, mut skipped, mut failed) = (0, 0, 0);
+    for expected in &entries {
+        let (outcome, elapsed) = answers::check(expected);
+        let status = match &outcome {
+            Outcome::Ok => String::from("ok"),
+            Outcome::Wrong(answer) => format!("WRONG, expected {:?} got {:?}", expected.answer, answer),
+            Outcome::Slow(_) => format!("SLOW, over the {:?} budget", expected.budget.unwrap_or_default()),
+            Outcome::Failed(e) => format!("ERROR, {}", e),
+            Outcome::Skipped => String::from("skipped, no input")
+        };
+        match outcome {
+            Outcome::Ok => ok += 1,
+            Outcome::Skipped => skipped += 1,
+            _ => failed += 1
+        }
+        let elapsed = if elapsed.is_zero() { String::new() } else { format!("{:.3?}", elapsed) };
+        println!("{:>3} {} {:<20} {:>10}  {}", expected.day, expected.part, expected.input, elapsed, status);
+    }
+
+    println!();
+    println!("{} ok, {} failed, {} skipped", ok, failed, skipped);
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+fn run_bench(args: &[String]) {
+    let mut positional = Vec::new();
+    let mut runs = 10;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--runs" => runs = iter.next().and_then(|x| x.parse::<usize>().ok()).unwrap_or_else(|| usage()),
+            _ => positional.push(arg.as_str())
+        }
+    }
+    if positional.is_empty() || positional.len() > 2 {
+        usage();
+    }
+
+    let day = positional[0].parse::<u32>().unwrap_or_else(|_| usage());
+    let bench = match registry::get(day) {
+        Some(entry) => entry.bench,
+        None => {
+            eprintln!("no solution for day {}", day);
+            process::exit(1);
+        }
+    };
+
+    let fail = |e: Error| -> ! {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    };
+    let input = read_input(day, positional.get(1).copied()).unwrap_or_else(|e| fail(e));
+    let timings = bench(&input, runs).unwrap_or_else(|e| fail(e));
+
+    println!("part | {:<12} | {:>10} | {:>10} | {:>10} | answer", "variant", "mean", "min", "stddev");
+    for timing in &timings {
+        let answer = if timing.answer.contains('\n') { "(multi line)" } else { &timing.answer };
+        let mark = if timing.agrees { "" } else { "  MISMATCH" };
+        println!("{:>4} | {:<12} | {:>10} | {:>10} | {:>10} | {}{}", timing.part, timing.name,
+                 format!("{:.3?}", timing.stats.mean), format!("{:.3?}", timing.stats.min),
+                 format!("{:.3?}", timing.stats.stddev), answer, mark);
+    }
+
+    // how fast the device runs the ElfCode days' programs, register 0 set to 1 so they
+    // go on long enough
+    if day == 19 || day == 21 {
+        let mut device = elfcode::Device::<6>::load(day, &input).unwrap_or_else(|e| fail(e));
+        device.registers[0] = 1;
+        let (interpreted, compiled) = compiled::speed(&device, 20_000_000);
+        println!();
+        println!("elfcode | {:<12} | {:>14} instructions/s", "execute_ip", format!("{:.0}", interpreted));
+        println!("elfcode | {:<12} | {:>14} instructions/s, {:.1}x execute_ip", "compiled", format!("{:.0}", compiled), compiled / interpreted);
+    }
+    if timings.iter().any(|x| !x.agrees) {
+        eprintln!("error: some variants disagree with the default answer");
+        process::exit(1);
+    }
+}
+
+// an annotated listing and pseudo-code for an #ip program, and its flow graph with --dot
+fn run_disasm(args: &[String]) {
+    let mut positional = Vec::new();
+    let mut dot = None;
+    let mut names = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--dot" => dot = Some(iter.next().unwrap_or_else(|| usage())),
+            "--name" => {
+                let (reg, name) = iter.next().and_then(|x| x.split_once('=')).unwrap_or_else(|| usage());
+                let reg = reg.trim_start_matches('r').parse::<usize>().unwrap_or_else(|_| usage());
+                names.push((reg, name));
+            },
+            _ => positional.push(arg.as_str())
+        }
+    }
+    if positional.is_empty() || positional.len() > 2 {
+        usage();
+    }
+    let day = positional[0].parse::<u32>().unwrap_or_else(|_| usage());
+    let fail = |e: Error| -> ! {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    };
+    let input = read_input(day, positional.get(1).copied()).unwrap_or_else(|e| fail(e));
+    let device = elfcode::Device::<6>::load(day, &input).unwrap_or_else(|e| fail(e));
+    let mut disassembly = disasm::disassemble(&device);
+    for (reg, name) in names {
+        if reg >= disassembly.registers() {
+            eprintln!("there is no register {}", reg);
+            process::exit(2);
+        }
+        disassembly.rename(reg, name);
+    }
+    println!("{}", disassembly.listing());
+    print!("{}", disassembly.pseudocode());
+    if let Some(path) = dot {
+        fs::write(path, disassembly.dot()).unwrap_or_else(|e| fail(Error::io(path, e)));
+    }
+}
+
+// assembles source into the #ip format days 19 and 21 read, to stdout or output
+fn run_asm(args: &[String]) {
+    if args.is_empty() || args.len() > 2 {
+        usage();
+    }
+    let fail = |e: Error| -> ! {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    };
+    let source = fs::read_to_string(&args[0]).unwrap_or_else(|e| fail(Error::io(&args[0], e)));
+    let program = asm::assemble::<6>(&args[0], &source).unwrap_or_else(|e| fail(e));
+    match args.get(1) {
+        Some(path) => fs::write(path, program).unwrap_or_else(|e| fail(Error::io(path, e))),
+        None => print!("{}", program)
+    }
+}
+
+// runs the script's commands, then reads more from stdin until quit or the end of it
+fn run_debug(args: &[String]) {
+    let mut positional = Vec::new();
+    let mut script = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--script" => script = Some(iter.next().unwrap_or_else(|| usage())),
+            _ => positional.push(arg.as_str())
+        }
+    }
+    if positional.is_empty() || positional.len() > 2 {
+        usage();
+    }
+    let day = positional[0].parse::<u32>().unwrap_or_else(|_| usage());
+    let fail = |e: Error| -> ! {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    };
+    let input = read_input(day, positional.get(1).copied()).unwrap_or_else(|e| fail(e));
+    let mut debugger = Debugger::new(elfcode::Device::<6>::load(day, &input).unwrap_or_else(|e| fail(e)));
+
+    if let Some(path) = script {
+        let text = fs::read_to_string(path).unwrap_or_else(|e| fail(Error::io(path, e)));
+        let (output, quit) = debugger.script(path, &text).unwrap_or_else(|e| fail(e));
+        print!("{}", output);
+        if quit {
+            return;
+        }
+    }
+    let stdin = io::stdin();
+    loop {
+        eprint!("(elf) ");
+        let _ = io::stderr().flush();
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).unwrap_or(0) == 0 {
+            break;
+        }
+        match debugger.command(&line) {
+            Ok(Some(output)) => print!("{}", output),
+            Ok(None) => break,
+            // there is no file or line to point at
+            Err(Error::Format { expected, .. }) => eprintln!("usage: {}", expected),
+            Err(e) => eprintln!("error: {}", e)
+        }
+    }
+}
+
+// the store for --user, or for AOC_USER without it. Returns the other arguments.
+fn store_for(args: &[String]) -> (Store, Vec<&String>) {
+    let mut store = Store::from_env();
+    let mut rest = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--user" => store = Store::for_user(iter.next().unwrap_or_else(|| usage())),
+            _ => rest.push(arg)
+        }
+    }
+    (store, rest)
+}
+
+fn run_fetch(args: &[String]) {
+    let (store, positional) = store_for(args);
+    if positional.is_empty() {
+        usage();
+    }
+    let days: Vec<u32> = positional.iter().map(|x| x.parse::<u32>().unwrap_or_else(|_| usage())).collect();
+    let client = Client::from_env().unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    });
+    let mut failed = false;
+    for day in days {
+        match store.fetch(&client, day) {
+            Ok((path, true)) => println!("day {:>2}: saved {}", day, path.display()),
+            Ok((path, false)) => println!("day {:>2}: already have {}", day, path.display()),
+            Err(e) => {
+                eprintln!("day {:>2}: error: {}", day, e);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn run_inputs(args: &[String]) {
+    let (store, rest) = store_for(args);
+    if !rest.is_empty() {
+        usage();
+    }
+    let days = store.days();
+    if days.is_empty() {
+        println!("no inputs stored for {} in {}", store.user(), store.dir().display());
+        return;
+    }
+    let mut failed = false;
+    for day in days {
+        match store.load(day) {
+            Ok(text) => println!("day {:>2}: ok, {} bytes, checksum {}", day, text.len(), input::checksum(&text)),
+            Err(e) => {
+                println!("day {:>2}: {}", day, e);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+// solves the part and sends the answer, unless the history says how that would go
+fn run_submit(args: &[String]) {
+    let (store, positional) = store_for(args);
+    if positional.len() < 2 || positional.len() > 3 {
+        usage();
+    }
+    let day = positional[0].parse::<u32>().unwrap_or_else(|_| usage());
+    let part = positional[1].parse::<u32>().unwrap_or_else(|_| usage());
+    let fail = |e: Error| -> ! {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    };
+    let solver = registry::get(day).and_then(|entry| entry.part(part)).unwrap_or_else(|| {
+        eprintln!("day {} has no part {}", day, part);
+        process::exit(1);
+    });
+    let input = read_input(day, positional.get(2).map(|x| x.as_str())).unwrap_or_else(|e| fail(e));
+    let answer = solver(&input).unwrap_or_else(|e| fail(e));
+    println!("day {} part {}: {}", day, part, answer);
+
+    let mut history = History::load(&store.submissions()).unwrap_or_else(|e| fail(e));
+    let client = Client::from_env().unwrap_or_else(|e| fail(e));
+    let reply = submit::submit(&client, &mut history, store::YEAR, day, part, &answer).unwrap_or_else(|e| fail(e));
+    match &reply {
+        Reply::Right => println!("right"),
+        Reply::Wrong => println!("wrong"),
+        Reply::TooHigh => println!("wrong, too high"),
+        Reply::TooLow => println!("wrong, too low"),
+        Reply::Wait(wait) => println!("not judged, wait {} before sending another answer", wait),
+        Reply::Solved => println!("not judged, the part is already solved"),
+        Reply::Known(reason) => println!("not sent, {}", reason),
+        Reply::Unknown(text) => println!("unrecognised reply: {}", text)
+    }
+    if reply != Reply::Right {
+        process::exit(1);
+    }
+}
+
+// takes the logging options out of the arguments, they can go anywhere
+fn init_logging(args: Vec<String>) -> Vec<String> {
+    let mut rest = Vec::new();
+    let mut verbosity = 0;
+    let mut directives = None;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--log" => directives = Some(iter.next().unwrap_or_else(|| usage())),
+            _ if arg.len() > 1 && arg.starts_with('-') && arg[1..].chars().all(|c| c == 'v') => verbosity += arg.len() - 1,
+            _ => rest.push(arg)
+        }
+    }
+    logging::init(verbosity, directives.as_deref());
+    rest
+}
+
+fn main() {
+    let mut args = init_logging(env::args().skip(1).collect());
+    let json = args.iter().any(|x| x == "--json");
+    args.retain(|x| x != "--json");
+    match args.first().map(|x| x.as_str()) {
+        // the other commands have their own reports
+        Some("verify" | "bench" | "record" | "generate" | "check" | "disasm" | "debug" | "asm" | "fetch" | "inputs" | "submit") if json => usage(),
+        Some("all") => run_all(&args[1..], json),
+        Some("verify") if args.len() <= 2 => run_verify(args.get(1).map(|x| x.as_str())),
+        Some("bench") => run_bench(&args[1..]),
+        Some("record") => run_record(&args[1..]),
+        Some("generate") => run_generate(&args[1..]),
+        Some("check") => run_check(&args[1..]),
+        Some("disasm") => run_disasm(&args[1..]),
+        Some("debug") => run_debug(&args[1..]),
+        Some("asm") => run_asm(&args[1..]),
+        Some("fetch") => run_fetch(&args[1..]),
+        Some("inputs") => run_inputs(&args[1..]),
+        Some("submit") => run_submit(&args[1..]),
+        Some(_) if args.len() == 2 || args.len() == 3 => run_one(&args[0], &args[1], args.get(2).map(|x| x.as_str()), json),
+        _ => usage()
+    }
+}
@@ -0,0 +1,89 @@
+//! Slow but obviously right reference implementations, run against the fast paths that
+//! rely on a shortcut: a cycle, a search that prunes, or the shape of the author's input.
+//! `run` feeds both the same generated inputs and reports where they disagree.
+
+use crate::error::Result;
+use crate::generate::{Knobs, Rng};
+use crate::solution::Solution;
+
+// a fast path and the reference it should agree with on any input
+pub struct Oracle<I> {
+    pub name: &'static str,
+    pub fast: fn(&I) -> Result<String>,
+    pub slow: fn(&I) -> Result<String>
+}
+
+impl<I> Oracle<I> {
+    pub fn new(name: &'static str, fast: fn(&I) -> Result<String>, slow: fn(&I) -> Result<String>) -> Oracle<I> {
+        Oracle { name, fast, slow }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Verdict {
+    Agree,
+    Disagree { fast: String, slow: String },
+    // the fast path gave up, like finding no cycle within its limit, so there was
+    // nothing to compare
+    Unanswered(String)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Case {
+    pub seed: u64,
+    pub name: &'static str,
+    pub verdict: Verdict
+}
+
+pub fn judge<I>(oracle: &Oracle<I>, input: &I) -> Verdict {
+    match ((oracle.fast)(input), (oracle.slow)(input)) {
+        (Err(e), _) => Verdict::Unanswered(e.to_string()),
+        (Ok(fast), Ok(slow)) if fast == slow => Verdict::Agree,
+        (Ok(fast), Ok(slow)) => Verdict::Disagree { fast, slow },
+        (Ok(fast), Err(e)) => Verdict::Disagree { fast, slow: format!("error: {}", e) }
+    }
+}
+
+// every oracle of the day against the inputs generated from seeds 0..seeds
+pub fn run<S: Solution>(knobs: &Knobs, seeds: u64) -> Result<Vec<Case>> {
+    let oracles = S::oracles();
+    let mut cases = Vec::new();
+    for seed in 0..seeds {
+        let input = S::parse(&S::generate(knobs, &mut Rng::new(seed)))?;
+        for oracle in &oracles {
+            cases.push(Case { seed, name: oracle.name, verdict: judge(oracle, &input) });
+        }
+    }
+    Ok(cases)
+}
+
+// the cases that disagree, for tests to show when they fail
+pub fn disagreements(cases: &[Case]) -> Vec<&Case> {
+    cases.iter().filter(|x| matches!(x.verdict, Verdict::Disagree { .. })).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_verdicts() {
+        use super::{disagreements, judge, run, Oracle, Verdict};
+        use crate::day23::Day23;
+        use crate::error::Error;
+        use crate::generate::Knobs;
+        // stand ins for a fast path that is always right, one that is wrong and one
+        // that gives up
+        let oracles: Vec<Oracle<u32>> = vec![
+            Oracle::new("right", |x| Ok(x.to_string()), |x| Ok(x.to_string())),
+            Oracle::new("wrong", |x| Ok((x + 1).to_string()), |x| Ok(x.to_string())),
+            Oracle::new("gives up", |_| Err(Error::solve(0, "no cycle")), |x| Ok(x.to_string())),
+        ];
+        let verdicts: Vec<Verdict> = oracles.iter().map(|oracle| judge(oracle, &1)).collect();
+        assert_eq!(verdicts[0], Verdict::Agree);
+        assert_eq!(verdicts[1], Verdict::Disagree { fast: String::from("2"), slow: String::from("1") });
+        assert_eq!(verdicts[2], Verdict::Unanswered(String::from("day 0: no cycle")));
+
+        let cases = run::<Day23>(&Knobs { count: 5, extent: 5 }, 3).unwrap();
+        assert_eq!(cases.len(), 3);
+        assert!(disagreements(&cases).is_empty());
+    }
+}
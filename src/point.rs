@@ -0,0 +1,310 @@
+//! Integer points and vectors in N dimensions. Points are positions and vectors are the
+//! differences between them, so a point minus a point is a vector and a point plus a
+//! vector is a point.
+//!
+//! Points order in reading order: the last axis is compared first, so 2D points sort
+//! top to bottom then left to right with y growing downwards.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Point<const N: usize>(pub [i64; N]);
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Vector<const N: usize>(pub [i64; N]);
+
+impl<const N: usize> Point<N> {
+    pub fn new(coords: [i64; N]) -> Point<N> {
+        Point(coords)
+    }
+
+    pub fn origin() -> Point<N> {
+        Point([0; N])
+    }
+
+    pub fn manhattan(&self, other: &Point<N>) -> i64 {
+        (*self - *other).manhattan()
+    }
+
+    pub fn chebyshev(&self, other: &Point<N>) -> i64 {
+        (*self - *other).chebyshev()
+    }
+
+    // squared so it stays an integer
+    pub fn euclidean_squared(&self, other: &Point<N>) -> i64 {
+        (*self - *other).euclidean_squared()
+    }
+
+    // the 2N points one step along an axis, in reading order
+    pub fn neighbours(&self) -> Vec<Point<N>> {
+        let mut result: Vec<Point<N>> = Vector::axes().map(|v| *self + v).collect();
+        result.sort();
+        result
+    }
+}
+
+impl<const N: usize> Vector<N> {
+    pub fn new(coords: [i64; N]) -> Vector<N> {
+        Vector(coords)
+    }
+
+    pub fn zero() -> Vector<N> {
+        Vector([0; N])
+    }
+
+    // a unit vector each way along every axis
+    pub fn axes() -> impl Iterator<Item = Vector<N>> {
+        (0..N).flat_map(|axis| [-1, 1].map(|sign| {
+            let mut v = Vector::zero();
+            v.0[axis] = sign;
+            v
+        }))
+    }
+
+    pub fn manhattan(&self) -> i64 {
+        self.0.iter().map(|x| x.abs()).sum()
+    }
+
+    pub fn chebyshev(&self) -> i64 {
+        self.0.iter().map(|x| x.abs()).max().unwrap_or(0)
+    }
+
+    pub fn euclidean_squared(&self) -> i64 {
+        self.0.iter().map(|x| x * x).sum()
+    }
+
+    // each component as -1, 0 or 1
+    pub fn signum(&self) -> Vector<N> {
+        Vector(self.0.map(i64::signum))
+    }
+}
+
+impl<const N: usize> Default for Point<N> {
+    fn default() -> Point<N> {
+        Point::origin()
+    }
+}
+
+impl<const N: usize> Default for Vector<N> {
+    fn default() -> Vector<N> {
+        Vector::zero()
+    }
+}
+
+fn zip<const N: usize>(a: [i64; N], b: [i64; N], f: impl Fn(i64, i64) -> i64) -> [i64; N] {
+    std::array::from_fn(|i| f(a[i], b[i]))
+}
+
+impl<const N: usize> Sub for Point<N> {
+    type Output = Vector<N>;
+
+    fn sub(self, other: Point<N>) -> Vector<N> {
+        Vector(zip(self.0, other.0, |a, b| a - b))
+    }
+}
+
+impl<const N: usize> Add<Vector<N>> for Point<N> {
+    type Output = Point<N>;
+
+    fn add(self, v: Vector<N>) -> Point<N> {
+        Point(zip(self.0, v.0, |a, b| a + b))
+    }
+}
+
+impl<const N: usize> Sub<Vector<N>> for Point<N> {
+    type Output = Point<N>;
+
+    fn sub(self, v: Vector<N>) -> Point<N> {
+        Point(zip(self.0, v.0, |a, b| a - b))
+    }
+}
+
+impl<const N: usize> AddAssign<Vector<N>> for Point<N> {
+    fn add_assign(&mut self, v: Vector<N>) {
+        *self = *self + v;
+    }
+}
+
+impl<const N: usize> SubAssign<Vector<N>> for Point<N> {
+    fn sub_assign(&mut self, v: Vector<N>) {
+        *self = *self - v;
+    }
+}
+
+impl<const N: usize> Add for Vector<N> {
+    type Output = Vector<N>;
+
+    fn add(self, other: Vector<N>) -> Vector<N> {
+        Vector(zip(self.0, other.0, |a, b| a + b))
+    }
+}
+
+impl<const N: usize> Sub for Vector<N> {
+    type Output = Vector<N>;
+
+    fn sub(self, other: Vector<N>) -> Vector<N> {
+        Vector(zip(self.0, other.0, |a, b| a - b))
+    }
+}
+
+impl<const N: usize> Mul<i64> for Vector<N> {
+    type Output = Vector<N>;
+
+    fn mul(self, k: i64) -> Vector<N> {
+        Vector(self.0.map(|x| x * k))
+    }
+}
+
+impl<const N: usize> Neg for Vector<N> {
+    type Output = Vector<N>;
+
+    fn neg(self) -> Vector<N> {
+        Vector(self.0.map(|x| -x))
+    }
+}
+
+impl<const N: usize> Index<usize> for Point<N> {
+    type Output = i64;
+
+    fn index(&self, axis: usize) -> &i64 {
+        &self.0[axis]
+    }
+}
+
+impl<const N: usize> IndexMut<usize> for Point<N> {
+    fn index_mut(&mut self, axis: usize) -> &mut i64 {
+        &mut self.0[axis]
+    }
+}
+
+impl<const N: usize> Index<usize> for Vector<N> {
+    type Output = i64;
+
+    fn index(&self, axis: usize) -> &i64 {
+        &self.0[axis]
+    }
+}
+
+impl<const N: usize> Ord for Point<N> {
+    fn cmp(&self, other: &Point<N>) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl<const N: usize> PartialOrd for Point<N> {
+    fn partial_cmp(&self, other: &Point<N>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// comma separated, the way most puzzle inputs write them
+impl<const N: usize> fmt::Display for Point<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, x) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", x)?;
+        }
+        Ok(())
+    }
+}
+
+// the smallest box holding a set of points, both corners are inside it
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Bounds<const N: usize> {
+    pub min: Point<N>,
+    pub max: Point<N>
+}
+
+impl<const N: usize> Bounds<N> {
+    pub fn new(point: Point<N>) -> Bounds<N> {
+        Bounds { min: point, max: point }
+    }
+
+    // None when there are no points
+    pub fn of<'a, I: IntoIterator<Item = &'a Point<N>>>(points: I) -> Option<Bounds<N>> {
+        let mut points = points.into_iter();
+        let mut bounds = Bounds::new(*points.next()?);
+        for point in points {
+            bounds.include(point);
+        }
+        Some(bounds)
+    }
+
+    pub fn include(&mut self, point: &Point<N>) {
+        self.min = Point(zip(self.min.0, point.0, i64::min));
+        self.max = Point(zip(self.max.0, point.0, i64::max));
+    }
+
+    pub fn contains(&self, point: &Point<N>) -> bool {
+        (0..N).all(|i| self.min[i] <= point[i] && point[i] <= self.max[i])
+    }
+
+    // grown by margin on every side
+    pub fn expand(&self, margin: i64) -> Bounds<N> {
+        Bounds { min: Point(self.min.0.map(|x| x - margin)), max: Point(self.max.0.map(|x| x + margin)) }
+    }
+
+    // cells along each axis, counting both ends
+    pub fn size(&self) -> Vector<N> {
+        Vector(zip(self.max.0, self.min.0, |a, b| a - b + 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_arithmetic() {
+        use super::{Point, Vector};
+        let a = Point::new([1, 2, 3]);
+        let b = Point::new([4, -2, 3]);
+        let v = b - a;
+        assert_eq!(v, Vector::new([3, -4, 0]));
+        assert_eq!(a + v, b);
+        assert_eq!(b - v, a);
+        assert_eq!(-v * 2, Vector::new([-6, 8, 0]));
+        assert_eq!(v.signum(), Vector::new([1, -1, 0]));
+        let mut c = a;
+        c += v;
+        c -= Vector::new([0, 0, 1]);
+        assert_eq!(c, Point::new([4, -2, 2]));
+        assert_eq!(c.to_string(), "4,-2,2");
+    }
+
+    #[test]
+    fn test_metrics() {
+        use super::Point;
+        let a = Point::new([0, 0, 0, 0]);
+        let b = Point::new([3, -4, 0, 1]);
+        assert_eq!(a.manhattan(&b), 8);
+        assert_eq!(a.chebyshev(&b), 4);
+        assert_eq!(a.euclidean_squared(&b), 26);
+        assert_eq!(Point::<2>::origin().manhattan(&Point::new([-1, -1])), 2);
+    }
+
+    #[test]
+    fn test_reading_order() {
+        use super::Point;
+        let mut points = vec![Point::new([2, 1]), Point::new([5, 0]), Point::new([0, 1]), Point::new([1, 0])];
+        points.sort();
+        assert_eq!(points, vec![Point::new([1, 0]), Point::new([5, 0]), Point::new([0, 1]), Point::new([2, 1])]);
+        assert_eq!(Point::new([1, 1]).neighbours(), vec![Point::new([1, 0]), Point::new([0, 1]), Point::new([2, 1]), Point::new([1, 2])]);
+        assert_eq!(Point::new([0, 0, 0]).neighbours().len(), 6);
+    }
+
+    #[test]
+    fn test_bounds() {
+        use super::{Bounds, Point, Vector};
+        let points = [Point::new([3, -1]), Point::new([-2, 4]), Point::new([0, 0])];
+        let bounds = Bounds::of(&points).unwrap();
+        assert_eq!(bounds, Bounds { min: Point::new([-2, -1]), max: Point::new([3, 4]) });
+        assert_eq!(bounds.size(), Vector::new([6, 6]));
+        assert!(bounds.contains(&Point::new([3, 4])));
+        assert!(!bounds.contains(&Point::new([4, 4])));
+        assert!(bounds.expand(1).contains(&Point::new([4, 4])));
+        assert_eq!(Bounds::<2>::of(&[]), None);
+    }
+}
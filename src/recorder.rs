@@ -0,0 +1,308 @@
+//! Records the frames of a grid simulation as an animated GIF or as a directory of
+//! numbered PNGs. Cells are turned into pixels through a palette of states.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::hash::Hash;
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+
+use gif::{Encoder, Frame, Repeat};
+
+use crate::error::{Error, Result};
+use crate::grid::Grid;
+
+// anything that can be drawn a cell at a time
+pub trait Canvas {
+    type Cell;
+
+    fn width(&self) -> usize;
+    fn height(&self) -> usize;
+    fn cell(&self, x: usize, y: usize) -> Self::Cell;
+}
+
+impl<T: Clone> Canvas for Grid<T> {
+    type Cell = T;
+
+    fn width(&self) -> usize {
+        Grid::width(self)
+    }
+
+    fn height(&self) -> usize {
+        Grid::height(self)
+    }
+
+    fn cell(&self, x: usize, y: usize) -> T {
+        self[(x, y)].clone()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Palette<T> {
+    colours: Vec<[u8; 3]>,
+    states: HashMap<T, u8>
+}
+
+impl<T: Eq + Hash> Palette<T> {
+    // background is used for any state without a colour of its own
+    pub fn new(background: [u8; 3]) -> Palette<T> {
+        Palette { colours: vec![background], states: HashMap::new() }
+    }
+
+    pub fn with(mut self, state: T, rgb: [u8; 3]) -> Palette<T> {
+        assert!(self.colours.len() < 256, "a palette holds at most 256 colours");
+        self.states.insert(state, self.colours.len() as u8);
+        self.colours.push(rgb);
+        self
+    }
+
+    fn index(&self, state: &T) -> u8 {
+        self.states.get(state).copied().unwrap_or(0)
+    }
+
+    fn flat(&self) -> Vec<u8> {
+        self.colours.iter().flatten().copied().collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Output {
+    Gif(PathBuf),
+    // one frame_NNNNN.png per frame
+    Png(PathBuf)
+}
+
+impl Output {
+    // a path ending in .gif is an animation, anything else a directory of frames
+    pub fn from_path(path: &str) -> Output {
+        if path.to_lowercase().ends_with(".gif") {
+            Output::Gif(PathBuf::from(path))
+        } else {
+            Output::Png(PathBuf::from(path))
+        }
+    }
+
+    fn path(&self) -> &Path {
+        match self {
+            Output::Gif(path) | Output::Png(path) => path
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Settings {
+    pub output: Output,
+    // hundredths of a second between frames
+    pub delay: u16,
+    // each cell is drawn as scale x scale pixels
+    pub scale: usize,
+    // only every skip'th frame pushed is written
+    pub skip: usize
+}
+
+impl Settings {
+    pub fn new(output: Output) -> Settings {
+        Settings { output, delay: 10, scale: 1, skip: 1 }
+    }
+}
+
+pub struct Recorder<T> {
+    settings: Settings,
+    palette: Palette<T>,
+    size: Option<(usize, usize)>,
+    pushed: usize,
+    written: usize,
+    gif: Option<Encoder<BufWriter<File>>>
+}
+
+impl<T: Eq + Hash> Recorder<T> {
+    pub fn new(settings: &Settings, palette: Palette<T>) -> Recorder<T> {
+        Recorder { settings: settings.clone(), palette, size: None, pushed: 0, written: 0, gif: None }
+    }
+
+    fn error(&self, e: io::Error) -> Error {
+        Error::io(&self.settings.output.path().display().to_string(), e)
+    }
+
+    pub fn push<C: Canvas<Cell = T>>(&mut self, canvas: &C) -> Result<()> {
+        self.pushed += 1;
+        if !(self.pushed - 1).is_multiple_of(self.settings.skip.max(1)) {
+            return Ok(());
+        }
+
+        let scale = self.settings.scale.max(1);
+        // checked before any pixels are allocated, a big enough scale asks for more memory
+        // than there is
+        let (most, format) = match self.settings.output {
+            Output::Gif(_) => (u16::MAX as usize, "gif"),
+            Output::Png(_) => (u32::MAX as usize, "png")
+        };
+        let size = canvas.width().checked_mul(scale).zip(canvas.height().checked_mul(scale))
+            .filter(|&(width, height)| width <= most && height <= most && width.checked_mul(height).is_some());
+        let Some((width, height)) = size else {
+            let message = format!("{}x{} cells at scale {} is too big for a {}", canvas.width(), canvas.height(), scale, format);
+            return Err(self.error(io::Error::new(io::ErrorKind::InvalidInput, message)));
+        };
+        match self.size {
+            Some(size) => assert_eq!(size, (width, height), "every frame has to be the same size"),
+            None => self.size = Some((width, height))
+        }
+
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                pixels.push(self.palette.index(&canvas.cell(x / scale, y / scale)));
+            }
+        }
+
+        match &self.settings.output {
+            Output::Gif(path) => self.write_gif(path.clone(), width, height, &pixels)?,
+            Output::Png(dir) => self.write_png(dir.join(format!("frame_{:05}.png", self.written)), width, height, pixels)?
+        }
+        self.written += 1;
+        Ok(())
+    }
+
+    // width and height fit a u16, push checks
+    fn write_gif(&mut self, path: PathBuf, width: usize, height: usize, pixels: &[u8]) -> Result<()> {
+        let (width, height) = (width as u16, height as u16);
+
+        if self.gif.is_none() {
+            let file = File::create(&path).map_err(|e| self.error(e))?;
+            let mut encoder = Encoder::new(BufWriter::new(file), width, height, &self.palette.flat()).map_err(|e| self.error(io::Error::other(e)))?;
+            encoder.set_repeat(Repeat::Infinite).map_err(|e| self.error(io::Error::other(e)))?;
+            self.gif = Some(encoder);
+        }
+
+        let mut frame = Frame::default();
+        frame.delay = self.settings.delay;
+        frame.width = width;
+        frame.height = height;
+        frame.buffer = Cow::Borrowed(pixels);
+        let result = self.gif.as_mut().unwrap().write_frame(&frame);
+        result.map_err(|e| self.error(io::Error::other(e)))
+    }
+
+    fn write_png(&self, path: PathBuf, width: usize, height: usize, pixels: Vec<u8>) -> Result<()> {
+        if self.written == 0 {
+            fs::create_dir_all(self.settings.output.path()).map_err(|e| self.error(e))?;
+        }
+        let file = File::create(&path).map_err(|e| self.error(e))?;
+        let mut encoder = png::Encoder::new(BufWriter::new(file), width as u32, height as u32);
+        encoder.set_color(png::ColorType::Indexed);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_palette(self.palette.flat());
+        let mut writer = encoder.write_header().map_err(|e| self.error(io::Error::other(e)))?;
+        writer.write_image_data(&pixels).map_err(|e| self.error(io::Error::other(e)))
+    }
+
+    // frames written so far, after skipping
+    pub fn frames(&self) -> usize {
+        self.written
+    }
+
+    // flushes the output and returns the number of frames written
+    pub fn finish(self) -> Result<usize> {
+        if let Some(encoder) = self.gif {
+            let path = self.settings.output.path().display().to_string();
+            let mut writer = encoder.into_inner().map_err(|e| Error::io(&path, e))?;
+            io::Write::flush(&mut writer).map_err(|e| Error::io(&path, e))?;
+        }
+        Ok(self.written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-recorder-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn frames() -> Vec<crate::grid::Grid<char>> {
+        use crate::grid::Grid;
+        (0..5).map(|i| {
+            let mut grid = Grid::new(3, 2, '.');
+            grid[(i % 3, i % 2)] = '#';
+            grid
+        }).collect()
+    }
+
+    #[test]
+    fn test_output_from_path() {
+        use super::Output;
+        assert_eq!(Output::from_path("out/water.GIF"), Output::Gif(PathBuf::from("out/water.GIF")));
+        assert_eq!(Output::from_path("out/frames"), Output::Png(PathBuf::from("out/frames")));
+    }
+
+    #[test]
+    fn test_gif() {
+        use super::{Output, Palette, Recorder, Settings};
+        let dir = scratch("gif");
+        let path = dir.join("test.gif");
+        let mut settings = Settings::new(Output::Gif(path.clone()));
+        settings.skip = 2;
+        settings.scale = 4;
+        let mut recorder = Recorder::new(&settings, Palette::new([0, 0, 0]).with('#', [255, 0, 0]));
+        for frame in &frames() {
+            recorder.push(frame).unwrap();
+        }
+        assert_eq!(recorder.finish().unwrap(), 3);
+
+        let mut decoder = gif::DecodeOptions::new();
+        decoder.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = decoder.read_info(fs::File::open(&path).unwrap()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (12, 8));
+        let mut count = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.buffer.iter().filter(|x| **x == 1).count(), 16);
+            count += 1;
+        }
+        assert_eq!(count, 3);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_too_big() {
+        use super::{Output, Palette, Recorder, Settings};
+        let dir = scratch("big");
+        let path = dir.join("test.gif");
+        let mut settings = Settings::new(Output::Gif(path.clone()));
+        settings.scale = 100000;
+        let mut recorder = Recorder::new(&settings, Palette::new([0, 0, 0]).with('#', [255, 0, 0]));
+        let error = recorder.push(&frames()[0]).unwrap_err().to_string();
+        assert!(error.ends_with("3x2 cells at scale 100000 is too big for a gif"), "{}", error);
+        assert!(!path.exists());
+
+        settings.output = Output::Png(dir.join("frames"));
+        settings.scale = usize::MAX / 2;
+        let mut recorder = Recorder::new(&settings, Palette::new([0, 0, 0]));
+        assert!(recorder.push(&frames()[0]).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_png_dir() {
+        use super::{Output, Palette, Recorder, Settings};
+        let dir = scratch("png").join("frames");
+        let settings = Settings::new(Output::Png(dir.clone()));
+        let mut recorder = Recorder::new(&settings, Palette::new([0, 0, 0]).with('#', [255, 0, 0]));
+        for frame in &frames() {
+            recorder.push(frame).unwrap();
+        }
+        assert_eq!(recorder.finish().unwrap(), 5);
+
+        let decoder = png::Decoder::new(fs::File::open(dir.join("frame_00004.png")).unwrap());
+        let reader = decoder.read_info().unwrap();
+        assert_eq!((reader.info().width, reader.info().height), (3, 2));
+        assert_eq!(reader.info().palette.as_ref().unwrap().len(), 6);
+        assert!(!dir.join("frame_00005.png").exists());
+        fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+    }
+}
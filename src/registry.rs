@@ -0,0 +1,182 @@
+//! Table of every day's solvers so the runner can call them by number.
+//! Each solver takes the text of the puzzle input and returns the answer as text, or
+//! the error from parsing or solving it.
+//! `measure` runs a part the same way but times parsing and solving apart and keeps the
+//! diagnostics the day records, for the runner's JSON output.
+
+use std::time::{Duration, Instant};
+
+use serde_json::{Map, Value};
+
+use crate::*;
+use crate::bench::{self, Timing};
+use crate::diagnostics;
+use crate::error::Result;
+use crate::generate::{Knobs, Rng};
+use crate::oracle::{self, Case};
+use crate::recorder::Settings;
+use crate::solution::Solution;
+
+pub type Solver = fn(&str) -> Result<String>;
+pub type Animator = fn(&str, &Settings) -> Result<usize>;
+// times every variant of both parts, given the number of timed runs
+pub type Benchmark = fn(&str, usize) -> Result<Vec<Timing>>;
+pub type Generator = fn(&Knobs, &mut Rng) -> String;
+// runs the given part, only called for parts the day has
+pub type Measure = fn(&str, u32) -> Measurement;
+// runs the oracles against the inputs made from the given number of seeds
+pub type Checker = fn(&Knobs, u64) -> Result<Vec<Case>>;
+
+pub struct Day {
+    pub day: u32,
+    pub part1: Solver,
+    // day 25 only has one puzzle
+    pub part2: Option<Solver>,
+    pub animate: Option<Animator>,
+    pub bench: Benchmark,
+    pub generate: Option<Generator>,
+    pub check: Option<Checker>,
+    pub measure: Measure,
+}
+
+pub struct Measurement {
+    pub answer: Result<String>,
+    pub parse: Duration,
+    // zero when parsing failed
+    pub solve: Duration,
+    pub extras: Map<String, Value>
+}
+
+impl Day {
+    pub fn part(&self, part: u32) -> Option<Solver> {
+        match part {
+            1 => Some(self.part1),
+            2 => self.part2,
+            _ => None
+        }
+    }
+}
+
+fn part1<S: Solution>(input: &str) -> Result<String> {
+    Ok(S::part1(&S::parse(input)?)?.to_string())
+}
+
+fn part2<S: Solution>(input: &str) -> Result<String> {
+    Ok(S::part2(&S::parse(input)?)?.to_string())
+}
+
+fn measure<S: Solution>(input: &str, part: u32) -> Measurement {
+    let ((answer, parse, solve), extras) = diagnostics::collect(|| {
+        let start = Instant::now();
+        let parsed = S::parse(input);
+        let parse = start.elapsed();
+        let parsed = match parsed {
+            Ok(parsed) => parsed,
+            Err(e) => return (Err(e), parse, Duration::ZERO)
+        };
+        let start = Instant::now();
+        let answer = match part {
+            1 => S::part1(&parsed).map(|x| x.to_string()),
+            _ => S::part2(&parsed).map(|x| x.to_string())
+        };
+        (answer, parse, start.elapsed())
+    });
+    Measurement { answer, parse, solve, extras }
+}
+
+fn animate<S: Solution>(input: &str, settings: &Settings) -> Result<usize> {
+    S::animate(&S::parse(input)?, settings)
+}
+
+fn day<S: Solution>() -> Day {
+    Day {
+        day: S::DAY,
+        part1: part1::<S>,
+        part2: if S::PART2 { Some(part2::<S>) } else { None },
+        animate: if S::ANIMATED { Some(animate::<S>) } else { None },
+        bench: bench::run::<S>,
+        generate: if S::GENERATED { Some(S::generate) } else { None },
+        check: if S::GENERATED && !S::oracles().is_empty() { Some(oracle::run::<S>) } else { None },
+        measure: measure::<S>,
+    }
+}
+
+pub fn days() -> Vec<Day> {
+    vec![
+        day::<day01::Day01>(),
+        day::<day02::Day02>(),
+        day::<day03::Day03>(),
+        day::<day04::Day04>(),
+        day::<day05::Day05>(),
+        day::<day06::Day06>(),
+        day::<day07::Day07>(),
+        day::<day08::Day08>(),
+        day::<day09::Day09>(),
+        day::<day10::Day10>(),
+        day::<day11::Day11>(),
+        day::<day12::Day12>(),
+        day::<day13::Day13>(),
+        day::<day14::Day14>(),
+        day::<day15::Day15>(),
+        day::<day16::Day16>(),
+        day::<day17::Day17>(),
+        day::<day18::Day18>(),
+        day::<day19::Day19>(),
+        day::<day20::Day20>(),
+        day::<day21::Day21>(),
+        day::<day22::Day22>(),
+        day::<day23::Day23>(),
+        day::<day24::Day24>(),
+        day::<day25::Day25>(),
+    ]
+}
+
+pub fn get(day: u32) -> Option<Day> {
+    days().into_iter().find(|x| x.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_days_registered() {
+        use super::days;
+        let days = days();
+        assert_eq!(days.len(), 25);
+        for (i, day) in days.iter().enumerate() {
+            assert_eq!(day.day, i as u32 + 1);
+        }
+    }
+
+    #[test]
+    fn test_get() {
+        use super::get;
+        assert!(get(15).unwrap().part(2).is_some());
+        assert!(get(25).unwrap().part(2).is_none());
+        assert!(get(26).is_none());
+        assert!(get(1).unwrap().part(3).is_none());
+    }
+
+    #[test]
+    fn test_bad_input() {
+        use super::get;
+        let solver = get(1).unwrap().part(1).unwrap();
+        assert_eq!(solver("+1\nfoo\n").unwrap_err().to_string(), "day 1 line 2: expected `+N or -N`, found `foo`");
+        assert_eq!(solver("+1\n-3\n").unwrap(), "-2");
+    }
+
+    #[test]
+    fn test_measure() {
+        use super::get;
+        use serde_json::json;
+        let day15 = get(15).unwrap();
+        let input = "#######\n#.G...#\n#...EG#\n#.#.#G#\n#..G#E#\n#.....#\n#######\n";
+        let measurement = (day15.measure)(input, 1);
+        assert_eq!(measurement.answer.unwrap(), "27730");
+        assert_eq!(measurement.extras.get("rounds"), Some(&json!(47)));
+        assert_eq!(measurement.extras.get("hp"), Some(&json!(590)));
+
+        let measurement = (get(1).unwrap().measure)("+1\nfoo\n", 1);
+        assert!(measurement.answer.is_err());
+        assert!(measurement.solve.is_zero());
+    }
+}
@@ -0,0 +1,81 @@
+//! The JSON document `--json` prints in place of the runner's text output, for tools that
+//! track answers and timings without reading the table:
+//!
+//! ```text
+//! {"runs": [{"day": 15, "part": 1, "input": "inputs/day15.txt", "checksum": "…",
+//!            "answer": "250594", "error": null, "parse_us": 212, "solve_us": 480123,
+//!            "extras": {"rounds": 82, "hp": 3041}}]}
+//! ```
+//!
+//! `answer` is null when the part failed and `error` says why. `input` and `checksum`
+//! are null when the input couldn't be read, and extras are whatever the day recorded
+//! through `diagnostics`.
+
+use serde_json::{json, Value};
+
+use crate::error::Error;
+use crate::input;
+use crate::registry::Measurement;
+
+pub struct Run {
+    pub day: u32,
+    pub part: u32,
+    pub input: Option<String>,
+    pub checksum: Option<String>,
+    pub measurement: Measurement
+}
+
+impl Run {
+    pub fn new(day: u32, part: u32, input: &str, text: &str, measurement: Measurement) -> Run {
+        Run { day, part, input: Some(input.to_string()), checksum: Some(input::checksum(text)), measurement }
+    }
+
+    // a part that never ran because its input couldn't be read
+    pub fn failed(day: u32, part: u32, error: Error) -> Run {
+        let measurement = Measurement { answer: Err(error), parse: Default::default(), solve: Default::default(), extras: Default::default() };
+        Run { day, part, input: None, checksum: None, measurement }
+    }
+
+    pub fn to_json(&self) -> Value {
+        let measurement = &self.measurement;
+        json!({
+            "day": self.day,
+            "part": self.part,
+            "input": self.input,
+            "checksum": self.checksum,
+            "answer": measurement.answer.as_ref().ok(),
+            "error": measurement.answer.as_ref().err().map(|e| e.to_string()),
+            "parse_us": measurement.parse.as_micros() as u64,
+            "solve_us": measurement.solve.as_micros() as u64,
+            "extras": measurement.extras
+        })
+    }
+}
+
+pub fn document(runs: &[Run]) -> String {
+    let runs: Vec<Value> = runs.iter().map(Run::to_json).collect();
+    serde_json::to_string_pretty(&json!({ "runs": runs })).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_document() {
+        use super::{document, Run};
+        use crate::error::Error;
+        use crate::registry::get;
+        use serde_json::{json, Value};
+        let text = "+1\n-2\n";
+        let run = Run::new(1, 1, "day01.txt", text, (get(1).unwrap().measure)(text, 1));
+        let missing = Run::failed(2, 1, Error::not_found(2, vec![String::from("inputs/day02.txt")]));
+        let value: Value = serde_json::from_str(&document(&[run, missing])).unwrap();
+        let runs = value["runs"].as_array().unwrap();
+        assert_eq!(runs[0]["answer"], json!("-1"));
+        assert_eq!(runs[0]["error"], Value::Null);
+        assert_eq!(runs[0]["checksum"], json!(crate::input::checksum(text)));
+        assert!(runs[0]["solve_us"].is_u64());
+        assert_eq!(runs[1]["answer"], Value::Null);
+        assert_eq!(runs[1]["input"], Value::Null);
+        assert!(runs[1]["error"].as_str().unwrap().contains("day 2"));
+    }
+}
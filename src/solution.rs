@@ -0,0 +1,85 @@
+//! Common shape for every day: parse the puzzle input once, then solve each part from the parsed form.
+
+use std::fmt::Display;
+use std::fs;
+use std::io::BufRead;
+use std::path::Path;
+
+use crate::bench::Variant;
+use crate::error::{Error, Result};
+use crate::generate::{Knobs, Rng};
+use crate::input;
+use crate::oracle::Oracle;
+use crate::recorder::Settings;
+
+pub trait Solution {
+    const DAY: u32;
+    // day 25 only has one puzzle
+    const PART2: bool = true;
+    // days that can record their simulation with animate
+    const ANIMATED: bool = false;
+    // days that can make random inputs with generate
+    const GENERATED: bool = false;
+
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    // same as parse for input coming from a file, stdin or anything else buffered
+    fn parse_reader<R: BufRead>(mut reader: R) -> Result<Self::Input> {
+        let mut input = String::new();
+        reader.read_to_string(&mut input).map_err(|e| Error::io("reader", e))?;
+        Self::parse(&input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2>;
+
+    // other implementations of either part, timed and checked against part1 and part2
+    // by aoc bench
+    fn variants() -> Vec<Variant<Self::Input>> {
+        Vec::new()
+    }
+
+    // records the simulation to settings.output, returns the number of frames written
+    fn animate(_input: &Self::Input, _settings: &Settings) -> Result<usize> {
+        Err(Error::solve(Self::DAY, "there is nothing to animate"))
+    }
+
+    // a random input in the day's format, only called when GENERATED is set
+    fn generate(_knobs: &Knobs, _rng: &mut Rng) -> String {
+        unreachable!("day {} has no input generator", Self::DAY)
+    }
+
+    // slow reference implementations for the parts that take a shortcut, checked
+    // against the fast path on generated inputs by aoc check
+    fn oracles() -> Vec<Oracle<Self::Input>> {
+        Vec::new()
+    }
+}
+
+// reads and parses a puzzle input file
+pub fn load<S: Solution>(path: impl AsRef<Path>) -> Result<S::Input> {
+    let path = path.as_ref();
+    let input = fs::read_to_string(path).map_err(|e| Error::io(&path.display().to_string(), e))?;
+    S::parse(&input)
+}
+
+// finds the day's input the same way the runner does and parses it
+pub fn load_input<S: Solution>() -> Result<S::Input> {
+    load::<S>(input::resolve(S::DAY, None)?)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_parse_reader() {
+        use std::io::Cursor;
+        use super::Solution;
+        use crate::day01::Day01;
+        let input = Day01::parse_reader(Cursor::new("+1\n-2\n+3\n+1\n")).unwrap();
+        assert_eq!(Day01::part1(&input).unwrap(), 3);
+    }
+}
@@ -0,0 +1,250 @@
+//! Puzzle inputs kept by year, user and day, so every checkout finds them in the same
+//! place whoever fetched them:
+//!
+//! ```text
+//! <root>/<year>/<user>/dayNN.txt
+//! <root>/<year>/<user>/checksums.txt    FILE CHECKSUM BYTES, one line per input
+//! <root>/<year>/<user>/submissions.txt  answers sent with aoc submit
+//! ```
+//!
+//! The root is AOC_STORE, or inputs/ under the current directory, and the user is
+//! AOC_USER or `default`. The checksum and size are taken when an input is saved, so one
+//! that was cut short, edited or had its line endings changed since shows up when it's
+//! read back.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::error::{Error, Result};
+use crate::fetch::Client;
+use crate::input;
+
+pub const STORE_VAR: &str = "AOC_STORE";
+pub const USER_VAR: &str = "AOC_USER";
+pub const YEAR: u32 = 2018;
+
+const MANIFEST: &str = "checksums.txt";
+
+#[derive(Clone, Debug, PartialEq)]
+struct Entry {
+    checksum: String,
+    bytes: usize
+}
+
+pub struct Store {
+    root: PathBuf,
+    year: u32,
+    user: String
+}
+
+// what is wrong with an input on its own, whatever was recorded for it. Every input from
+// the site ends in a newline and none has a carriage return.
+pub fn inspect(text: &str) -> Option<String> {
+    if text.is_empty() {
+        Some(String::from("is empty"))
+    } else if text.contains("\r\n") {
+        Some(String::from("has CRLF line endings, it was probably saved on Windows"))
+    } else if text.contains('\r') {
+        Some(String::from("has carriage returns in it"))
+    } else if !text.ends_with('\n') {
+        Some(String::from("doesn't end with a newline, it looks cut short"))
+    } else {
+        None
+    }
+}
+
+impl Store {
+    pub fn new(root: impl Into<PathBuf>, year: u32, user: &str) -> Store {
+        Store { root: root.into(), year, user: user.to_string() }
+    }
+
+    pub fn from_env() -> Store {
+        Store::for_user(&env::var(USER_VAR).unwrap_or_else(|_| String::from("default")))
+    }
+
+    // the store under AOC_STORE for a user other than AOC_USER
+    pub fn for_user(user: &str) -> Store {
+        let root = env::var_os(STORE_VAR).map(PathBuf::from).unwrap_or_else(|| PathBuf::from("inputs"));
+        Store::new(root, YEAR, user)
+    }
+
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    pub fn dir(&self) -> PathBuf {
+        self.root.join(self.year.to_string()).join(&self.user)
+    }
+
+    pub fn path(&self, day: u32) -> PathBuf {
+        self.dir().join(format!("day{:02}.txt", day))
+    }
+
+    // the answers this user has sent, see `submit`
+    pub fn submissions(&self) -> PathBuf {
+        self.dir().join("submissions.txt")
+    }
+
+    fn manifest(&self) -> Result<BTreeMap<String, Entry>> {
+        let path = self.dir().join(MANIFEST);
+        let name = path.display().to_string();
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+            Err(e) => return Err(Error::io(&name, e))
+        };
+        let mut entries = BTreeMap::new();
+        for (n, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields[..] {
+                [file, checksum, bytes] if bytes.parse::<usize>().is_ok() => {
+                    entries.insert(file.to_string(), Entry { checksum: checksum.to_string(), bytes: bytes.parse().unwrap() });
+                },
+                _ => return Err(Error::format(&name, n + 1, line, "FILE CHECKSUM BYTES"))
+            }
+        }
+        Ok(entries)
+    }
+
+    fn write_manifest(&self, entries: &BTreeMap<String, Entry>) -> Result<()> {
+        let path = self.dir().join(MANIFEST);
+        let mut text = String::from("# written by aoc fetch, FILE CHECKSUM BYTES\n");
+        for (file, entry) in entries {
+            text.push_str(&format!("{} {} {}\n", file, entry.checksum, entry.bytes));
+        }
+        fs::write(&path, text).map_err(|e| Error::io(&path.display().to_string(), e))
+    }
+
+    // keeps text as the day's input and records its checksum, refusing one that is
+    // already mangled
+    pub fn save(&self, day: u32, text: &str) -> Result<PathBuf> {
+        let path = self.path(day);
+        let name = path.display().to_string();
+        if let Some(problem) = inspect(text) {
+            return Err(Error::corrupt(&name, &problem));
+        }
+        fs::create_dir_all(self.dir()).map_err(|e| Error::io(&self.dir().display().to_string(), e))?;
+        fs::write(&path, text).map_err(|e| Error::io(&name, e))?;
+        let mut entries = self.manifest()?;
+        entries.insert(format!("day{:02}.txt", day), Entry { checksum: input::checksum(text), bytes: text.len() });
+        self.write_manifest(&entries)?;
+        Ok(path)
+    }
+
+    // the day's input, as long as it is still what was saved
+    pub fn load(&self, day: u32) -> Result<String> {
+        let path = self.path(day);
+        let name = path.display().to_string();
+        let text = fs::read_to_string(&path).map_err(|e| Error::io(&name, e))?;
+        let entry = self.manifest()?.remove(&format!("day{:02}.txt", day))
+            .ok_or_else(|| Error::corrupt(&name, &format!("has no checksum in {}, store it with aoc fetch", MANIFEST)))?;
+        if let Some(problem) = inspect(&text) {
+            return Err(Error::corrupt(&name, &problem));
+        }
+        if text.len() < entry.bytes {
+            return Err(Error::corrupt(&name, &format!("is {} bytes but was {} when it was saved, it looks cut short", text.len(), entry.bytes)));
+        }
+        if input::checksum(&text) != entry.checksum {
+            return Err(Error::corrupt(&name, "has changed since it was saved"));
+        }
+        Ok(text)
+    }
+
+    // the days with an input file, whether or not they check out
+    pub fn days(&self) -> Vec<u32> {
+        let mut days: Vec<u32> = fs::read_dir(self.dir()).into_iter().flatten()
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                name.strip_prefix("day")?.strip_suffix(".txt")?.parse().ok()
+            })
+            .collect();
+        days.sort();
+        days
+    }
+
+    // downloads the day's input unless a good copy is already kept. Says whether it
+    // had to download.
+    pub fn fetch(&self, client: &Client, day: u32) -> Result<(PathBuf, bool)> {
+        if self.path(day).is_file() && self.load(day).is_ok() {
+            return Ok((self.path(day), false));
+        }
+        let text = client.input(self.year, day)?;
+        Ok((self.save(day, &text)?, true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-store-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_inspect() {
+        use super::inspect;
+        assert_eq!(inspect("+1\n-2\n"), None);
+        assert!(inspect("+1\r\n-2\r\n").unwrap().contains("CRLF"));
+        assert!(inspect("+1\n-").unwrap().contains("cut short"));
+        assert!(inspect("").is_some());
+    }
+
+    #[test]
+    fn test_save_load() {
+        use super::Store;
+        let root = scratch("save");
+        let store = Store::new(&root, 2018, "alice");
+        let path = store.save(1, "+1\n-2\n").unwrap();
+        assert_eq!(path, root.join("2018/alice/day01.txt"));
+        store.save(12, "initial state: #\n").unwrap();
+        assert_eq!(store.load(1).unwrap(), "+1\n-2\n");
+        assert_eq!(store.days(), vec![1, 12]);
+        // another user's inputs are kept apart
+        assert!(Store::new(&root, 2018, "bob").load(1).is_err());
+        assert!(store.save(2, "abc\r\n").is_err());
+
+        // what can happen to a file after it's saved
+        fs::write(&path, "+1\r\n-2\r\n").unwrap();
+        assert!(store.load(1).unwrap_err().to_string().ends_with("day01.txt has CRLF line endings, it was probably saved on Windows"));
+        fs::write(&path, "+1\n").unwrap();
+        assert!(store.load(1).unwrap_err().to_string().contains("is 3 bytes but was 6 when it was saved"));
+        fs::write(&path, "+1\n-3\n").unwrap();
+        assert!(store.load(1).unwrap_err().to_string().ends_with("has changed since it was saved"));
+        fs::write(root.join("2018/alice/day05.txt"), "abc\n").unwrap();
+        assert!(store.load(5).unwrap_err().to_string().contains("has no checksum"));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_fetch() {
+        use super::Store;
+        use crate::fetch::mock::serve;
+        use crate::fetch::Client;
+        let root = scratch("fetch");
+        let store = Store::new(&root, 2018, "alice");
+        // only one response, the second fetch has to come from the store
+        let (base_url, server) = serve(vec![(200, "+1\n-2\n")]);
+        let client = Client::new(&base_url, "abc123");
+        assert_eq!(store.fetch(&client, 1).unwrap(), (root.join("2018/alice/day01.txt"), true));
+        assert_eq!(store.fetch(&client, 1).unwrap(), (root.join("2018/alice/day01.txt"), false));
+        assert_eq!(server.join().unwrap().len(), 1);
+        assert_eq!(store.load(1).unwrap(), "+1\n-2\n");
+
+        // a mangled copy is fetched again
+        fs::write(store.path(1), "+1\r\n-2\r\n").unwrap();
+        let (base_url, server) = serve(vec![(200, "+1\n-2\n")]);
+        assert!(store.fetch(&Client::new(&base_url, "abc123"), 1).unwrap().1);
+        assert_eq!(server.join().unwrap()[0].path, "/2018/day/1/input");
+        fs::remove_dir_all(root).unwrap();
+    }
+}
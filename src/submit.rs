@@ -0,0 +1,294 @@
+//! Sends answers to the site and keeps what it said about them, so the same wrong
+//! answer is never sent twice. The history sits next to the user's inputs in the store
+//! as submissions.txt, one line per answer the site judged:
+//!
+//! ```text
+//! DAY PART REPLY ANSWER
+//! ```
+//!
+//! REPLY is right, wrong, too-high or too-low. A number on the wrong side of one that
+//! was too high or too low isn't sent either.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+use crate::fetch::Client;
+
+const FORMAT: &str = "DAY PART REPLY ANSWER";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Reply {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    // an answer went in too recently, with the wait the site gave
+    Wait(String),
+    // the part was solved before
+    Solved,
+    // what the history already says, nothing was sent
+    Known(String),
+    // a page we don't recognise, with its text
+    Unknown(String)
+}
+
+impl Reply {
+    fn word(&self) -> Option<&'static str> {
+        match self {
+            Reply::Right => Some("right"),
+            Reply::Wrong => Some("wrong"),
+            Reply::TooHigh => Some("too-high"),
+            Reply::TooLow => Some("too-low"),
+            _ => None
+        }
+    }
+
+    fn from_word(word: &str) -> Option<Reply> {
+        match word {
+            "right" => Some(Reply::Right),
+            "wrong" => Some(Reply::Wrong),
+            "too-high" => Some(Reply::TooHigh),
+            "too-low" => Some(Reply::TooLow),
+            _ => None
+        }
+    }
+}
+
+// the site answers with a page, what matters is the text of its <article>
+pub fn parse_reply(body: &str) -> Reply {
+    let article = match (body.find("<article>"), body.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &body[start..end],
+        _ => body
+    };
+    // drop the tags, the wording is split up by links
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    let text = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+
+    if text.contains("That's the right answer") {
+        Reply::Right
+    } else if text.contains("your answer is too high") {
+        Reply::TooHigh
+    } else if text.contains("your answer is too low") {
+        Reply::TooLow
+    } else if text.contains("That's not the right answer") {
+        Reply::Wrong
+    } else if text.contains("You gave an answer too recently") {
+        let wait = text.split_once("You have ").and_then(|(_, rest)| rest.split_once(" left to wait"));
+        Reply::Wait(wait.map(|(wait, _)| wait.to_string()).unwrap_or_default())
+    } else if text.contains("Did you already complete it") {
+        Reply::Solved
+    } else {
+        Reply::Unknown(text)
+    }
+}
+
+// form encoding, answers are mostly digits and letters anyway
+fn encode(text: &str) -> String {
+    text.bytes().map(|b| match b {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+        _ => format!("%{:02X}", b)
+    }).collect()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    pub day: u32,
+    pub part: u32,
+    pub reply: Reply,
+    pub answer: String
+}
+
+pub struct History {
+    path: PathBuf,
+    submissions: Vec<Submission>
+}
+
+impl History {
+    // a missing file is an empty history
+    pub fn load(path: &Path) -> Result<History> {
+        let name = path.display().to_string();
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(Error::io(&name, e))
+        };
+        let mut submissions = Vec::new();
+        for (n, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.splitn(4, ' ').collect();
+            let submission = match fields[..] {
+                [day, part, reply, answer] => match (day.parse(), part.parse(), Reply::from_word(reply)) {
+                    (Ok(day), Ok(part), Some(reply)) => Some(Submission { day, part, reply, answer: answer.to_string() }),
+                    _ => None
+                },
+                _ => None
+            };
+            submissions.push(submission.ok_or_else(|| Error::format(&name, n + 1, line, FORMAT))?);
+        }
+        Ok(History { path: path.to_path_buf(), submissions })
+    }
+
+    pub fn submissions(&self) -> &[Submission] {
+        &self.submissions
+    }
+
+    // why answer shouldn't be sent, if the history already knows how it would go
+    pub fn known(&self, day: u32, part: u32, answer: &str) -> Option<String> {
+        let number = answer.parse::<i64>().ok();
+        for submission in self.submissions.iter().filter(|x| x.day == day && x.part == part) {
+            let bound = submission.answer.parse::<i64>().ok();
+            match (&submission.reply, number, bound) {
+                (Reply::Right, _, _) if submission.answer == answer => return Some(String::from("already solved with this answer")),
+                (Reply::Right, _, _) => return Some(format!("already solved with {}, this answer is different", submission.answer)),
+                (_, _, _) if submission.answer == answer => return Some(format!("already sent and it was {}", submission.reply.word().unwrap())),
+                (Reply::TooHigh, Some(number), Some(bound)) if number >= bound => return Some(format!("{} was already too high", bound)),
+                (Reply::TooLow, Some(number), Some(bound)) if number <= bound => return Some(format!("{} was already too low", bound)),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    // keeps replies that judged the answer, the others say nothing about it
+    pub fn record(&mut self, day: u32, part: u32, reply: &Reply, answer: &str) -> Result<()> {
+        let word = match reply.word() {
+            Some(word) => word,
+            None => return Ok(())
+        };
+        let name = self.path.display().to_string();
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(|e| Error::io(&dir.display().to_string(), e))?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path).map_err(|e| Error::io(&name, e))?;
+        writeln!(file, "{} {} {} {}", day, part, word, answer).map_err(|e| Error::io(&name, e))?;
+        self.submissions.push(Submission { day, part, reply: reply.clone(), answer: answer.to_string() });
+        Ok(())
+    }
+}
+
+// sends answer unless the history already knows what the site would say
+pub fn submit(client: &Client, history: &mut History, year: u32, day: u32, part: u32, answer: &str) -> Result<Reply> {
+    let path = format!("/{}/day/{}/answer", year, day);
+    // a debug line or an ascii art answer (day 10) is never what the site wants
+    if answer.is_empty() || answer.contains(char::is_whitespace) {
+        return Err(Error::http(&client.url(&path), &format!("{:?} doesn't look like an answer", answer)));
+    }
+    if let Some(reason) = history.known(day, part, answer) {
+        return Ok(Reply::Known(reason));
+    }
+    let (status, body) = client.request(&path, Some(&format!("level={}&answer={}", part, encode(answer))))?;
+    let reply = match status {
+        200 => parse_reply(&body),
+        400 | 401 | 403 => return Err(Error::http(&client.url(&path), "the session cookie was turned down, it may have expired")),
+        _ => return Err(Error::http(&client.url(&path), &format!("the server answered {}", status)))
+    };
+    history.record(day, part, &reply, answer)?;
+    Ok(reply)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-submit-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    // the pages the site gives back, cut down to their articles
+    const RIGHT: &str = "<main><article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.</p></article></main>";
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.</p></article>";
+    const TOO_LOW: &str = "<article><p>That's not the right answer; your answer is too low.</p></article>";
+    const WRONG: &str = "<article><p>That's not the right answer.  If you're stuck, <a href=\"/2018/day/7\">[Return to Day 7]</a></p></article>";
+    const WAIT: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 37s left to wait. <a href=\"/2018/day/1\">[Return to Day 1]</a></p></article>";
+    const SOLVED: &str = "<article><p>You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2018/day/1\">[Return to Day 1]</a></p></article>";
+
+    #[test]
+    fn test_parse_reply() {
+        use super::{parse_reply, Reply};
+        assert_eq!(parse_reply(RIGHT), Reply::Right);
+        assert_eq!(parse_reply(TOO_HIGH), Reply::TooHigh);
+        assert_eq!(parse_reply(TOO_LOW), Reply::TooLow);
+        assert_eq!(parse_reply(WRONG), Reply::Wrong);
+        assert_eq!(parse_reply(WAIT), Reply::Wait(String::from("37s")));
+        assert_eq!(parse_reply(SOLVED), Reply::Solved);
+        assert_eq!(parse_reply("<article><p>Please don't repeatedly request this endpoint.</p></article>"),
+                   Reply::Unknown(String::from("Please don't repeatedly request this endpoint.")));
+    }
+
+    #[test]
+    fn test_encode() {
+        use super::encode;
+        assert_eq!(encode("CABDFE"), "CABDFE");
+        assert_eq!(encode("235,14,13"), "235%2C14%2C13");
+    }
+
+    #[test]
+    fn test_known() {
+        use super::History;
+        let root = scratch("known");
+        let path = root.join("submissions.txt");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(&path, "# DAY PART REPLY ANSWER\n21 1 too-high 6483\n21 1 too-low 12\n21 2 wrong 5\n13 1 wrong 83,49\n").unwrap();
+        let history = History::load(&path).unwrap();
+        assert_eq!(history.submissions().len(), 4);
+        assert_eq!(history.known(21, 1, "6483").unwrap(), "already sent and it was too-high");
+        assert_eq!(history.known(21, 1, "7000").unwrap(), "6483 was already too high");
+        assert_eq!(history.known(21, 1, "3").unwrap(), "12 was already too low");
+        assert_eq!(history.known(21, 1, "100"), None);
+        assert_eq!(history.known(21, 2, "4"), None);
+        assert_eq!(history.known(13, 1, "83,49").unwrap(), "already sent and it was wrong");
+        assert_eq!(history.known(13, 2, "83,49"), None);
+
+        fs::write(&path, "21 1 maybe 6483\n").unwrap();
+        assert!(History::load(&path).is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_submit() {
+        use super::{submit, History, Reply};
+        use crate::fetch::mock::serve;
+        use crate::fetch::Client;
+        let root = scratch("submit");
+        let path = root.join("2018/alice/submissions.txt");
+        let mut history = History::load(&path).unwrap();
+        let (base_url, server) = serve(vec![(200, TOO_HIGH), (200, WAIT), (200, RIGHT)]);
+        let client = Client::new(&base_url, "abc123");
+
+        assert_eq!(submit(&client, &mut history, 2018, 21, 1, "6483").unwrap(), Reply::TooHigh);
+        // none of these reach the server
+        assert!(matches!(submit(&client, &mut history, 2018, 21, 1, "6483").unwrap(), Reply::Known(_)));
+        assert!(matches!(submit(&client, &mut history, 2018, 21, 1, "9000").unwrap(), Reply::Known(_)));
+        assert!(submit(&client, &mut history, 2018, 21, 1, "device.registers[4]: 6483\n").is_err());
+        assert_eq!(submit(&client, &mut history, 2018, 21, 1, "212").unwrap(), Reply::Wait(String::from("37s")));
+        assert_eq!(submit(&client, &mut history, 2018, 21, 1, "212").unwrap(), Reply::Right);
+        assert_eq!(submit(&client, &mut history, 2018, 21, 1, "212").unwrap(), Reply::Known(String::from("already solved with this answer")));
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2018/day/21/answer");
+        assert_eq!(requests[0].body, "level=1&answer=6483");
+        assert_eq!(requests[0].cookie.as_deref(), Some("session=abc123"));
+        // the wait isn't kept, the answer is still worth sending
+        assert_eq!(fs::read_to_string(&path).unwrap(), "21 1 too-high 6483\n21 1 right 212\n");
+        assert_eq!(History::load(&path).unwrap().submissions().len(), 2);
+        fs::remove_dir_all(root).unwrap();
+    }
+}